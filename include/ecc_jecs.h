@@ -0,0 +1,48 @@
+/* C interface of ecc_jecs_lib, enabled by the 'ffi' cargo feature. */
+#ifndef ECC_JECS_H
+#define ECC_JECS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque tree node. Nodes returned as 'const' are borrowed from their tree and become invalid once it is freed. */
+typedef struct JecsType JecsType;
+
+#define JECS_KIND_ANY 0
+#define JECS_KIND_VALUE 1
+#define JECS_KIND_MAP 2
+#define JECS_KIND_LIST 3
+#define JECS_KIND_NULL 4
+
+/* Parses JECS text. Returns NULL on failure, then *error_out (if error_out is not NULL) receives a message to free with jecs_free_string. */
+JecsType *jecs_parse(const char *text, char **error_out);
+/* Releases a tree returned by jecs_parse. */
+void jecs_free(JecsType *tree);
+
+/* Returns the node at a path like "MainInfo.Components[3]", or NULL. */
+const JecsType *jecs_get_path(const JecsType *node, const char *path);
+/* Returns one of the JECS_KIND_* values, or -1 for NULL. */
+int32_t jecs_node_kind(const JecsType *node);
+/* Returns the amount of entries of maps and lists, 0 otherwise. */
+size_t jecs_child_count(const JecsType *node);
+/* Returns the list entry at the index, or NULL. */
+const JecsType *jecs_list_get(const JecsType *node, size_t index);
+/* Returns the key of the n-th map entry, or NULL. Free with jecs_free_string. */
+char *jecs_map_key(const JecsType *node, size_t index);
+/* Returns the text of a value node, or NULL. Free with jecs_free_string. */
+char *jecs_get_value(const JecsType *node);
+
+/* Writes a tree as JECS text, or returns NULL if it cannot be written. Free with jecs_free_string. */
+char *jecs_write(const JecsType *tree);
+/* Releases a string returned by this library. */
+void jecs_free_string(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
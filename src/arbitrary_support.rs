@@ -0,0 +1,62 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::types::{JecsMap, JecsType};
+
+//Bounds of the generated trees, to keep them small enough for property tests:
+const MAX_DEPTH: usize = 4;
+const MAX_CHILDREN: usize = 6;
+const MAX_TEXT_LENGTH: usize = 12;
+
+//Only generates content that can be written and parsed again without changes.
+//Thus keys avoid ':' and '#', values have no leading/trailing spaces, and collections are never empty (those become Any).
+const KEY_CHARACTERS: &[char] = &['a', 'b', 'c', 'X', 'Y', 'Z', '0', '1', '9', '_', '.', ' ', 'ä', '└'];
+const VALUE_CHARACTERS: &[char] = &['a', 'b', 'c', 'X', 'Y', 'Z', '0', '1', '9', '_', '.', ' ', '-', ':', '#', '\\', 'ä', '└'];
+
+//Generates a map, as every JECS file has a map as root.
+impl<'a> Arbitrary<'a> for JecsType {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(JecsType::Map(arbitrary_map(u, 0)?))
+	}
+}
+
+fn arbitrary_entry(u: &mut Unstructured, depth: usize) -> Result<JecsType> {
+	let kind = if depth >= MAX_DEPTH { u.int_in_range(0..=1)? } else { u.int_in_range(0..=3)? };
+	Ok(match kind {
+		0 => JecsType::Any(),
+		1 => JecsType::value(arbitrary_text(u, VALUE_CHARACTERS)?),
+		2 => JecsType::Map(arbitrary_map(u, depth + 1)?),
+		_ => {
+			let count = u.int_in_range(1..=MAX_CHILDREN)?;
+			let mut list = Vec::with_capacity(count);
+			for _ in 0..count {
+				list.push(arbitrary_entry(u, depth + 1)?);
+			}
+			JecsType::List(list)
+		}
+	})
+}
+
+fn arbitrary_map(u: &mut Unstructured, depth: usize) -> Result<JecsMap> {
+	let count = u.int_in_range(1..=MAX_CHILDREN)?;
+	let mut map = JecsMap::with_capacity_and_hasher(count, Default::default());
+	for _ in 0..count {
+		let mut key = arbitrary_text(u, KEY_CHARACTERS)?;
+		while map.contains_key(&key) {
+			key.push('_');
+		}
+		let entry = arbitrary_entry(u, depth)?;
+		map.insert(key, entry);
+	}
+	Ok(map)
+}
+
+fn arbitrary_text(u: &mut Unstructured, characters: &[char]) -> Result<String> {
+	let length = u.int_in_range(1..=MAX_TEXT_LENGTH)?;
+	let mut text = String::with_capacity(length);
+	for _ in 0..length {
+		text.push(*u.choose(characters)?);
+	}
+	//Spaces at the start and end are lost while parsing:
+	let text = text.trim_matches(' ');
+	Ok(if text.is_empty() { "x".to_string() } else { text.to_string() })
+}
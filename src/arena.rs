@@ -0,0 +1,64 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::types::{JecsMap, JecsType};
+
+pub use bumpalo::collections::Vec as BumpVec;
+
+//Same as JecsType, but the whole tree lives in a bump allocator (see parser::parse_jecs_string_in()) and is freed together with it.
+//Maps keep their entries in file order. Like in JecsType the last entry counts, if a key is used multiple times.
+#[derive(Debug)]
+pub enum JecsArenaType<'bump> {
+	Any(),
+	Value(&'bump str),
+	Map(BumpVec<'bump, (&'bump str, JecsArenaType<'bump>)>),
+	List(BumpVec<'bump, JecsArenaType<'bump>>),
+}
+
+impl<'bump> JecsArenaType<'bump> {
+	pub fn get_value(&self) -> Option<&'bump str> {
+		match self {
+			JecsArenaType::Value(value) => Some(value),
+			_ => None,
+		}
+	}
+	
+	pub fn get_map(&self) -> Option<&[(&'bump str, JecsArenaType<'bump>)]> {
+		match self {
+			JecsArenaType::Map(map) => Some(map),
+			_ => None,
+		}
+	}
+	
+	pub fn get_list(&self) -> Option<&[JecsArenaType<'bump>]> {
+		match self {
+			JecsArenaType::List(list) => Some(list),
+			_ => None,
+		}
+	}
+	
+	//Looks up a key by going through the entries, maps are not hashed.
+	pub fn get(&self, key: &str) -> Option<&JecsArenaType<'bump>> {
+		self.get_map()?.iter().rev().find(|(entry_key, _)| *entry_key == key).map(|(_, entry)| entry)
+	}
+	
+	pub fn get_index(&self, index: usize) -> Option<&JecsArenaType<'bump>> {
+		self.get_list()?.get(index)
+	}
+	
+	//Copies the tree out of the arena.
+	pub fn to_jecs_type(&self) -> JecsType {
+		match self {
+			JecsArenaType::Any() => JecsType::Any(),
+			JecsArenaType::Value(value) => JecsType::value(*value),
+			JecsArenaType::Map(map) => {
+				let mut output = JecsMap::with_capacity_and_hasher(map.len(), Default::default());
+				for (key, entry) in map.iter() {
+					output.insert(key.to_string(), entry.to_jecs_type());
+				}
+				JecsType::Map(output)
+			}
+			JecsArenaType::List(list) => JecsType::List(list.iter().map(JecsArenaType::to_jecs_type).collect::<Vec<_>>()),
+		}
+	}
+}
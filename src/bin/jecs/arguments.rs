@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+//Command line arguments of one command. Options are written as '--name value' or '--name=value', flags as '--name'.
+pub struct Arguments {
+	positional: Vec<String>,
+	options: HashMap<String, Vec<String>>,
+}
+
+impl Arguments {
+	pub fn parse(arguments: impl Iterator<Item = String>, options: &[&str], flags: &[&str]) -> Result<Self, String> {
+		let mut parsed = Self {
+			positional: Vec::new(),
+			options: HashMap::new(),
+		};
+		let mut arguments = arguments.peekable();
+		while let Some(argument) = arguments.next() {
+			if !argument.starts_with("--") {
+				parsed.positional.push(argument);
+				continue;
+			}
+			let (name, inline_value) = match argument.split_once('=') {
+				Some((name, value)) => (name.to_owned(), Some(value.to_owned())),
+				None => (argument, None),
+			};
+			if flags.contains(&name.as_str()) {
+				if inline_value.is_some() {
+					return Err(format!("Flag '{}' does not take a value.", name));
+				}
+				parsed.options.entry(name).or_default();
+			} else if options.contains(&name.as_str()) {
+				let value = match inline_value {
+					Some(value) => value,
+					None => arguments.next().ok_or_else(|| format!("Option '{}' requires a value.", name))?,
+				};
+				parsed.options.entry(name).or_default().push(value);
+			} else {
+				return Err(format!("Unknown option '{}'.", name));
+			}
+		}
+		Ok(parsed)
+	}
+	
+	pub fn flag(&self, name: &str) -> bool {
+		self.options.contains_key(name)
+	}
+	
+	//The last value given for the option.
+	pub fn value(&self, name: &str) -> Option<&str> {
+		self.options.get(name).and_then(|values| values.last()).map(String::as_str)
+	}
+	
+	pub fn parsed_value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+		match self.value(name) {
+			None => Ok(None),
+			Some(value) => value.parse().map(Some).map_err(|_| format!("Invalid value '{}' for option '{}'.", value, name)),
+		}
+	}
+	
+	pub fn positional(&self) -> &[String] {
+		&self.positional
+	}
+	
+	//For commands that operate on exactly one file.
+	pub fn single_file(&self) -> Result<&Path, String> {
+		match &self.positional[..] {
+			[file] => Ok(Path::new(file)),
+			[] => Err("Missing file argument.".to_owned()),
+			_ => Err("Expected exactly one file argument.".to_owned()),
+		}
+	}
+}
@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use ecc_jecs_lib::path::JecsPath;
+use ecc_jecs_lib::types::JecsType;
+
+use crate::arguments::Arguments;
+use crate::load_tree;
+
+const HELP: &str = "↑↓ move  → expand  ← collapse  / search  n/N next/previous match  q quit";
+
+//One visible line of the tree.
+struct Row<'a> {
+	path: JecsPath,
+	prefix: String, //Tree connectors, like the debug printer draws them
+	key: Option<String>, //None for list entries
+	entry: &'a JecsType,
+}
+
+struct Explorer<'a> {
+	tree: &'a JecsType,
+	expanded: HashSet<JecsPath>,
+	selected: usize,
+	scroll: usize,
+	search: Option<String>, //Text typed after '/', until Enter
+	last_search: String,
+	message: Option<String>,
+}
+
+//Restores the terminal, even if drawing failed.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+	fn drop(&mut self) {
+		let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+		let _ = terminal::disable_raw_mode();
+	}
+}
+
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let tree = load_tree(arguments.single_file()?)?;
+	let mut explorer = Explorer {
+		tree: &tree,
+		expanded: HashSet::from([JecsPath::root()]),
+		selected: 0,
+		scroll: 0,
+		search: None,
+		last_search: String::new(),
+		message: None,
+	};
+	
+	let terminal_error = |error: io::Error| format!("Terminal error: {}", error);
+	terminal::enable_raw_mode().map_err(terminal_error)?;
+	let _guard = TerminalGuard;
+	execute!(io::stdout(), EnterAlternateScreen, cursor::Hide).map_err(terminal_error)?;
+	loop {
+		explorer.draw().map_err(terminal_error)?;
+		if let Event::Key(key) = event::read().map_err(terminal_error)? {
+			if key.kind != KeyEventKind::Release && !explorer.handle_key(key) {
+				return Ok(ExitCode::SUCCESS);
+			}
+		}
+	}
+}
+
+impl<'a> Explorer<'a> {
+	fn rows(&self) -> Vec<Row<'a>> {
+		let mut rows = vec![Row {
+			path: JecsPath::root(),
+			prefix: String::new(),
+			key: None,
+			entry: self.tree,
+		}];
+		self.add_children(&mut rows, self.tree, &JecsPath::root(), "");
+		rows
+	}
+	
+	fn add_children(&self, rows: &mut Vec<Row<'a>>, entry: &'a JecsType, path: &JecsPath, prefix: &str) {
+		if !self.expanded.contains(path) {
+			return;
+		}
+		let children = children(entry, path);
+		let count = children.len();
+		for (index, (child_path, key, child)) in children.into_iter().enumerate() {
+			let last = index == count - 1;
+			rows.push(Row {
+				path: child_path.clone(),
+				prefix: format!("{}{} ", prefix, if last { '└' } else { '├' }),
+				key,
+				entry: child,
+			});
+			self.add_children(rows, child, &child_path, &format!("{}{} ", prefix, if last { ' ' } else { '│' }));
+		}
+	}
+	
+	//Returns false, when the explorer should close.
+	fn handle_key(&mut self, key: KeyEvent) -> bool {
+		self.message = None;
+		if let Some(search) = &mut self.search {
+			match key.code {
+				KeyCode::Enter => {
+					self.last_search = self.search.take().unwrap();
+					self.find(true);
+				}
+				KeyCode::Esc => self.search = None,
+				KeyCode::Backspace => {
+					search.pop();
+				}
+				KeyCode::Char(c) => search.push(c),
+				_ => {}
+			}
+			return true;
+		}
+		let rows = self.rows();
+		let page = terminal::size().map_or(20, |(_, height)| height.saturating_sub(3) as usize);
+		match key.code {
+			KeyCode::Char('q') | KeyCode::Esc => return false,
+			KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+			KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+			KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(rows.len() - 1),
+			KeyCode::PageUp => self.selected = self.selected.saturating_sub(page),
+			KeyCode::PageDown => self.selected = (self.selected + page).min(rows.len() - 1),
+			KeyCode::Home => self.selected = 0,
+			KeyCode::End => self.selected = rows.len() - 1,
+			KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => {
+				let row = &rows[self.selected];
+				if !self.expanded.insert(row.path.clone()) && !children(row.entry, &row.path).is_empty() {
+					//Already expanded, move to the first child:
+					self.selected += 1;
+				}
+			}
+			KeyCode::Left | KeyCode::Char('h') => {
+				let row = &rows[self.selected];
+				if !self.expanded.remove(&row.path) || children(row.entry, &row.path).is_empty() {
+					//Already collapsed, move to the parent:
+					let mut parent = row.path.clone();
+					if parent.pop().is_some() {
+						self.selected = rows.iter().position(|row| row.path == parent).unwrap();
+					}
+				}
+			}
+			KeyCode::Char('/') => self.search = Some(String::new()),
+			KeyCode::Char('n') => self.find(true),
+			KeyCode::Char('N') => self.find(false),
+			_ => {}
+		}
+		true
+	}
+	
+	//Selects the next entry whose key or value contains the search text, ignoring case. Collapsed entries are searched too.
+	fn find(&mut self, forward: bool) {
+		if self.last_search.is_empty() {
+			return;
+		}
+		let mut entries = Vec::new();
+		collect_entries(self.tree, &JecsPath::root(), &self.last_search.to_lowercase(), &mut entries);
+		let count = entries.len();
+		let current = self.rows().swap_remove(self.selected).path;
+		//The root is no entry, searching from it starts at the first or last entry:
+		let start = entries.iter().position(|(path, _)| *path == current).unwrap_or(if forward { count.saturating_sub(1) } else { 0 });
+		let found = (1..=count)
+			.map(|step| if forward { (start + step) % count } else { (start + count - step) % count })
+			.find(|index| entries[*index].1);
+		let target = match found {
+			Some(index) => entries.swap_remove(index).0,
+			None => {
+				self.message = Some(format!("No match for '{}'", self.last_search));
+				return;
+			}
+		};
+		let mut parent = target.clone();
+		while parent.pop().is_some() {
+			self.expanded.insert(parent.clone());
+		}
+		self.selected = self.rows().iter().position(|row| row.path == target).unwrap();
+	}
+	
+	fn draw(&mut self) -> io::Result<()> {
+		let (width, height) = terminal::size()?;
+		let list_height = (height as usize).saturating_sub(2).max(1);
+		let rows = self.rows();
+		self.selected = self.selected.min(rows.len() - 1);
+		if self.selected < self.scroll {
+			self.scroll = self.selected;
+		} else if self.selected >= self.scroll + list_height {
+			self.scroll = self.selected + 1 - list_height;
+		}
+		
+		let mut stdout = io::stdout();
+		queue!(stdout, Clear(ClearType::All))?;
+		for (line, (index, row)) in rows.iter().enumerate().skip(self.scroll).take(list_height).enumerate() {
+			queue!(stdout, cursor::MoveTo(0, line as u16))?;
+			if index == self.selected {
+				queue!(stdout, SetAttribute(Attribute::Reverse))?;
+			}
+			queue!(stdout, SetForegroundColor(Color::DarkGrey), Print(&row.prefix))?;
+			let mut text = String::new();
+			if let Some(key) = &row.key {
+				text.push_str(key);
+				text.push_str(": ");
+			}
+			text.push_str(&summary(row.entry, self.expanded.contains(&row.path)));
+			let available = (width as usize).saturating_sub(row.prefix.chars().count());
+			queue!(stdout, SetForegroundColor(Color::White), Print(truncate(&text, available)), SetAttribute(Attribute::Reset), ResetColor)?;
+		}
+		
+		//Preview of the selected entry and the status line:
+		let row = &rows[self.selected];
+		let preview = match row.entry {
+			JecsType::Value(value) => format!("{} = {}", row.path, value),
+			JecsType::Any() => format!("{} has no value", row.path),
+			JecsType::Null() => format!("{} is null", row.path),
+			JecsType::Map(map) => format!("{} is a map with {} entries", row.path, map.len()),
+			JecsType::List(list) => format!("{} is a list with {} entries", row.path, list.len()),
+		};
+		let status = match (&self.search, &self.message) {
+			(Some(search), _) => format!("/{}", search),
+			(None, Some(message)) => message.clone(),
+			(None, None) => HELP.to_owned(),
+		};
+		queue!(stdout,
+			cursor::MoveTo(0, height.saturating_sub(2)), SetForegroundColor(Color::Yellow), Print(truncate(&preview, width as usize)),
+			cursor::MoveTo(0, height.saturating_sub(1)), SetForegroundColor(Color::DarkGrey), Print(truncate(&status, width as usize)), ResetColor,
+		)?;
+		stdout.flush()
+	}
+}
+
+//Children in display order, map keys are sorted.
+fn children<'a>(entry: &'a JecsType, path: &JecsPath) -> Vec<(JecsPath, Option<String>, &'a JecsType)> {
+	match entry {
+		JecsType::Map(map) => {
+			let mut children: Vec<_> = map.iter().map(|(key, child)| (path.join_key(key), Some(key.clone()), child)).collect();
+			children.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
+			children
+		}
+		JecsType::List(list) => list.iter().enumerate().map(|(index, child)| (path.join_index(index), None, child)).collect(),
+		_ => Vec::new(),
+	}
+}
+
+//All entries in display order, with whether their key or value contains the search text.
+fn collect_entries(entry: &JecsType, path: &JecsPath, search: &str, entries: &mut Vec<(JecsPath, bool)>) {
+	for (child_path, key, child) in children(entry, path) {
+		let key_matches = key.is_some_and(|key| key.to_lowercase().contains(search));
+		let value_matches = child.get_value().is_some_and(|value| value.to_lowercase().contains(search));
+		entries.push((child_path.clone(), key_matches || value_matches));
+		collect_entries(child, &child_path, search, entries);
+	}
+}
+
+fn summary(entry: &JecsType, expanded: bool) -> String {
+	let marker = if expanded { '▾' } else { '▸' };
+	match entry {
+		JecsType::Any() => "---".to_owned(),
+		JecsType::Null() => "null".to_owned(),
+		JecsType::Value(value) => format!("'{}'", value),
+		JecsType::Map(map) => format!("{} <map> ({} entries)", marker, map.len()),
+		JecsType::List(list) => format!("{} <list> ({} entries)", marker, list.len()),
+	}
+}
+
+fn truncate(text: &str, width: usize) -> String {
+	if text.chars().count() <= width {
+		return text.to_owned();
+	}
+	let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+	truncated.push('…');
+	truncated
+}
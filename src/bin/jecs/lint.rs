@@ -0,0 +1,59 @@
+use std::fs;
+use std::process::ExitCode;
+
+use ecc_jecs_lib::diagnostic;
+use ecc_jecs_lib::document::JecsDocument;
+use ecc_jecs_lib::errors::JecsErrorCode;
+use ecc_jecs_lib::lint::{lint_indentation, lint_key_case, KeyCase};
+use ecc_jecs_lib::parser::{parse_jecs_string_with_warnings, ParserOptions};
+
+use crate::arguments::Arguments;
+use crate::report::{print_json, Diagnostic, OutputFormat, Severity};
+
+//Reports parse errors, the warnings of the parser and the lints, exits with 1 if there is any of them.
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let format = OutputFormat::from_arguments(&arguments)?;
+	let key_case: Option<KeyCase> = arguments.parsed_value("--key-case")?;
+	let files = arguments.positional();
+	if files.is_empty() {
+		return Err("Missing file arguments.".to_owned());
+	}
+	
+	let mut diagnostics = Vec::new();
+	let mut problems = 0;
+	for file in files {
+		let text = fs::read_to_string(file).map_err(|error| format!("Could not read '{}': {}", file, error))?;
+		let body = text.strip_prefix('\u{feff}').unwrap_or(&text);
+		let mut warnings = match parse_jecs_string_with_warnings(body, &ParserOptions::default()) {
+			Ok((_, warnings)) => warnings,
+			Err(error) => {
+				problems += 1;
+				match format {
+					OutputFormat::Text => print!("{}: {}", file, diagnostic::render_error(&text, &error)),
+					OutputFormat::Json => diagnostics.push(Diagnostic::new(file, &text, error.row, error.column..usize::MAX, Severity::Error, error.code, error.description)),
+				}
+				continue;
+			}
+		};
+		//The indentation lint compares with the step of the whole file, instead of the first step like the parser:
+		if let Ok(document) = JecsDocument::parse(&text) {
+			warnings.retain(|warning| warning.code != JecsErrorCode::InconsistentIndentationStep);
+			warnings.extend(lint_indentation(&document));
+			if let Some(key_case) = key_case {
+				warnings.extend(lint_key_case(&document, key_case));
+			}
+			warnings.sort_by_key(|warning| warning.row);
+		}
+		problems += warnings.len();
+		for warning in warnings {
+			match format {
+				OutputFormat::Text => print!("{}: {}", file, diagnostic::render_warning(&text, &warning)),
+				OutputFormat::Json => diagnostics.push(Diagnostic::new(file, &text, warning.row, warning.column..usize::MAX, Severity::Warning, warning.code, warning.description)),
+			}
+		}
+	}
+	if format == OutputFormat::Json {
+		print_json(&diagnostics);
+	}
+	Ok(if problems == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
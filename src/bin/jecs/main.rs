@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use ecc_jecs_lib::{diagnostic, parser};
+use ecc_jecs_lib::types::JecsType;
+
+use crate::arguments::Arguments;
+
+mod arguments;
+mod explore;
+mod lint;
+mod merge;
+mod query;
+mod report;
+mod tree;
+mod validate;
+mod watch;
+
+const USAGE: &str = "\
+Usage: jecs <command> [options]
+
+Commands:
+	tree <file>    Prints the entries of a file as tree
+		--path <path>       Only prints the entry at this path, like 'MainInfo.Components[3]'
+		--max-depth <n>     Collapses maps and lists deeper than this
+		--grep <regex>      Only prints entries whose key or value matches, and their parents
+		--no-color          Prints without ANSI color codes
+	query <file> <query>    Prints the path and value of all entries matching the query, like 'Components.*.Color'
+		--json              Prints the matches as JSON array of objects with 'path' and 'value'
+		                    '*' matches any key or list entry, '[*]' any list entry and '**' any amount of entries
+		                    Exits with 1 if no entry matched
+	merge <file> <file>...    Merges the files in order, maps are merged key by key and later files override entries
+		--output <file>     Writes the result into this file, instead of printing it
+		--keep-existing     Earlier files win instead
+		--append-lists      Appends list entries, instead of replacing the list
+		--strict            Fails with exit code 1 on conflicts, instead of only printing them
+	validate --schema <schema> <file>...    Checks the files against a schema written in JECS
+		                    Prints every violation with its line and exits with 1 if any file is invalid
+		--format <format>   'text' or 'json' for an array of LSP style diagnostics
+		--unknown-keys      Warns about keys the schema does not describe, with the closest described key
+	lint <file>...    Reports parse errors and warnings, like trailing whitespace, tabs or skipped indentation levels, exits with 1 if there are any
+		--format <format>   'text' or 'json' for an array of LSP style diagnostics
+		--key-case <case>   Also reports keys which are not 'pascal' (PascalCase) or 'snake' (snake_case), with the renamed key
+	watch <file or directory>    Prints the changed entries whenever a file changes, until stopped
+		--interval <ms>     Time between checks for changes, 500 by default
+	explore <file>    Opens the file in an interactive tree view, with search and a preview of the selected value";
+
+fn main() -> ExitCode {
+	let mut arguments = std::env::args().skip(1);
+	let command = arguments.next();
+	let result = match command.as_deref() {
+		Some("tree") => Arguments::parse(arguments, &["--path", "--max-depth", "--grep"], &["--no-color"]).and_then(tree::run),
+		Some("query") => Arguments::parse(arguments, &[], &["--json"]).and_then(query::run),
+		Some("merge") => Arguments::parse(arguments, &["--output"], &["--keep-existing", "--append-lists", "--strict"]).and_then(merge::run),
+		Some("validate") => Arguments::parse(arguments, &["--schema", "--format"], &["--unknown-keys"]).and_then(validate::run),
+		Some("lint") => Arguments::parse(arguments, &["--format", "--key-case"], &[]).and_then(lint::run),
+		Some("watch") => Arguments::parse(arguments, &["--interval"], &[]).and_then(watch::run),
+		Some("explore") => Arguments::parse(arguments, &[], &[]).and_then(explore::run),
+		Some("help" | "--help" | "-h") => {
+			println!("{}", USAGE);
+			return ExitCode::SUCCESS;
+		}
+		Some(command) => Err(format!("Unknown command '{}'.\n\n{}", command, USAGE)),
+		None => Err(USAGE.to_owned()),
+	};
+	match result {
+		Ok(code) => code,
+		Err(message) => {
+			eprintln!("{}", message);
+			ExitCode::from(2)
+		}
+	}
+}
+
+//Parses a file, errors in the content are rendered with the affected line.
+pub fn load_tree(path: &Path) -> Result<JecsType, String> {
+	let bytes = fs::read(path).map_err(|error| format!("Could not read '{}': {}", path.display(), error))?;
+	let text = std::str::from_utf8(&bytes).map_err(|error| format!("File '{}' is not valid UTF-8: {}", path.display(), error))?;
+	let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+	match parser::parse_jecs_string(text) {
+		Ok(map) => Ok(JecsType::Map(map)),
+		Err(error) => Err(format!("File '{}' has invalid content:\n{}", path.display(), diagnostic::render_error(text, &error))),
+	}
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use ecc_jecs_lib::merge::{JecsListMerge, JecsMergeOptions, JecsMergeStrategy};
+use ecc_jecs_lib::types::JecsType;
+use ecc_jecs_lib::writer::{write_jecs_string_with_options, KeyOrder, WriterOptions};
+
+use crate::arguments::Arguments;
+use crate::load_tree;
+
+//Conflicts are printed to stderr. With '--strict' they fail the merge (exit code 1) and nothing is written.
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let files = arguments.positional();
+	if files.len() < 2 {
+		return Err("Expected at least two files to merge.".to_owned());
+	}
+	let options = JecsMergeOptions {
+		strategy: if arguments.flag("--keep-existing") { JecsMergeStrategy::KeepExisting } else { JecsMergeStrategy::Override },
+		lists: if arguments.flag("--append-lists") { JecsListMerge::Append } else { JecsListMerge::Replace },
+	};
+	
+	let mut tree = load_tree(files[0].as_ref())?;
+	let mut conflict_count = 0;
+	for file in &files[1..] {
+		for conflict in tree.merge(load_tree(file.as_ref())?, &options) {
+			conflict_count += 1;
+			let (kept, dropped) = match options.strategy {
+				JecsMergeStrategy::Override => (&conflict.incoming, &conflict.existing),
+				JecsMergeStrategy::KeepExisting => (&conflict.existing, &conflict.incoming),
+			};
+			eprintln!("Conflict in '{}' at '{}': kept {}, dropped {}", file, conflict.path, describe(kept), describe(dropped));
+		}
+	}
+	if conflict_count != 0 && arguments.flag("--strict") {
+		eprintln!("{} conflicts, nothing got written.", conflict_count);
+		return Ok(ExitCode::FAILURE);
+	}
+	
+	let options = WriterOptions {
+		key_order: KeyOrder::Alphabetical,
+		..WriterOptions::default()
+	};
+	let text = write_jecs_string_with_options(tree.get_map().unwrap(), &options).map_err(|error| format!("Could not write the merged tree: {}", error))?;
+	match arguments.value("--output") {
+		None => print!("{}", text),
+		Some(output) => fs::write(Path::new(output), text).map_err(|error| format!("Could not write '{}': {}", output, error))?,
+	}
+	Ok(ExitCode::SUCCESS)
+}
+
+fn describe(entry: &JecsType) -> String {
+	match entry {
+		JecsType::Value(value) => format!("'{}'", value),
+		_ => format!("<{}>", entry.name()),
+	}
+}
@@ -0,0 +1,55 @@
+use std::process::ExitCode;
+
+use ecc_jecs_lib::query::JecsQuery;
+use ecc_jecs_lib::types::JecsType;
+use serde_json::json;
+
+use crate::arguments::Arguments;
+use crate::load_tree;
+
+//Exits with 1 if nothing matched, so that scripts can test for entries like with grep.
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let (file, query) = match arguments.positional() {
+		[file, query] => (file, JecsQuery::parse(query)),
+		_ => return Err("Expected a file and a query argument.".to_owned()),
+	};
+	let tree = load_tree(file.as_ref())?;
+	let results = tree.query(query);
+	
+	if arguments.flag("--json") {
+		let results: Vec<_> = results.iter().map(|(path, entry)| json!({
+			"path": path.to_string(),
+			"value": entry,
+		})).collect();
+		println!("{}", serde_json::to_string_pretty(&results).unwrap());
+	} else {
+		for (path, entry) in &results {
+			println!("{}\t{}", escape(&path.to_string()), escape(&value_text(entry)));
+		}
+	}
+	Ok(if results.is_empty() { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+//Values are printed as they are, entries without value as empty text, maps and lists as JSON on a single line.
+fn value_text(entry: &JecsType) -> String {
+	match entry {
+		JecsType::Any() => String::new(),
+		JecsType::Value(value) => value.to_string(),
+		_ => serde_json::to_string(entry).unwrap(),
+	}
+}
+
+//Keeps every result on one line with exactly one TAB, escaped the same way as by 'jq -r @tsv'.
+fn escape(text: &str) -> String {
+	let mut output = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'\\' => output.push_str("\\\\"),
+			'\t' => output.push_str("\\t"),
+			'\n' => output.push_str("\\n"),
+			'\r' => output.push_str("\\r"),
+			_ => output.push(c),
+		}
+	}
+	output
+}
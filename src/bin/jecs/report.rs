@@ -0,0 +1,92 @@
+use std::ops::Range;
+
+use ecc_jecs_lib::errors::JecsErrorCode;
+use serde_json::{json, Value};
+
+use crate::arguments::Arguments;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum OutputFormat {
+	Text, //Human readable, with the source line where possible
+	Json, //One array of LSP style diagnostics for all files
+}
+
+impl OutputFormat {
+	pub fn from_arguments(arguments: &Arguments) -> Result<Self, String> {
+		match arguments.value("--format") {
+			None | Some("text") => Ok(OutputFormat::Text),
+			Some("json") => Ok(OutputFormat::Json),
+			Some(format) => Err(format!("Unknown format '{}', expected 'text' or 'json'.", format)),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Severity {
+	Error,
+	Warning,
+}
+
+//A problem within a file, in the shape of a 'Diagnostic' of the Language Server Protocol plus the file:
+// { "file": "a.jecs", "range": { "start": { "line": 2, "character": 4 }, "end": { ... } }, "severity": 1, "code": "JECS0101", "source": "jecs", "message": "..." }
+//Lines start at 0 and characters count UTF-16 code units, as LSP defines them.
+pub struct Diagnostic {
+	pub file: String,
+	pub start: (usize, usize),
+	pub end: (usize, usize),
+	pub severity: Severity,
+	pub code: JecsErrorCode,
+	pub message: String,
+}
+
+impl Diagnostic {
+	//Rows start at 1 like everywhere in this library, 0 marks problems which are not on a specific line. Columns are bytes within the row.
+	pub fn new(file: &str, source: &str, row: usize, columns: Range<usize>, severity: Severity, code: JecsErrorCode, message: String) -> Self {
+		let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+		let line = row.checked_sub(1).and_then(|index| source.lines().nth(index)).unwrap_or("");
+		let character = |column: usize| {
+			let mut column = column.min(line.len());
+			while !line.is_char_boundary(column) {
+				column -= 1;
+			}
+			line[..column].encode_utf16().count()
+		};
+		let line_index = row.saturating_sub(1);
+		Self {
+			file: file.to_owned(),
+			start: (line_index, character(columns.start)),
+			end: (line_index, character(columns.end.max(columns.start))),
+			severity,
+			code,
+			message,
+		}
+	}
+	
+	pub fn to_json(&self) -> Value {
+		json!({
+			"file": self.file,
+			"range": {
+				"start": { "line": self.start.0, "character": self.start.1 },
+				"end": { "line": self.end.0, "character": self.end.1 },
+			},
+			"severity": match self.severity {
+				Severity::Error => 1,
+				Severity::Warning => 2,
+			},
+			"code": self.code.code(),
+			"source": "jecs",
+			"message": self.message,
+		})
+	}
+}
+
+pub fn print_json(diagnostics: &[Diagnostic]) {
+	let diagnostics: Vec<_> = diagnostics.iter().map(Diagnostic::to_json).collect();
+	println!("{}", serde_json::to_string_pretty(&diagnostics).unwrap());
+}
+
+//Errors of the library are displayed with their code in front, which is a separate field in diagnostics.
+pub fn message_without_code(message: &str, code: JecsErrorCode) -> String {
+	let message = message.trim_end();
+	message.strip_prefix(&format!("[{}] ", code.code())).unwrap_or(message).to_owned()
+}
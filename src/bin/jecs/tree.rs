@@ -0,0 +1,71 @@
+use std::process::ExitCode;
+
+use ecc_jecs_lib::debug::debug_string;
+use ecc_jecs_lib::path::{JecsPath, JecsPathSegment};
+use ecc_jecs_lib::types::JecsType;
+use regex::Regex;
+
+use crate::arguments::Arguments;
+use crate::load_tree;
+
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let file = arguments.single_file()?;
+	let max_depth = arguments.parsed_value::<usize>("--max-depth")?;
+	let grep = match arguments.value("--grep") {
+		None => None,
+		Some(pattern) => Some(Regex::new(pattern).map_err(|error| format!("Invalid regex for '--grep': {}", error))?),
+	};
+	
+	let tree = load_tree(file)?;
+	let mut tree = match arguments.value("--path") {
+		None => tree,
+		Some(path) => match tree.get_path(JecsPath::parse(path)) {
+			None => return Err(format!("There is no entry at path '{}'.", path)),
+			Some(entry) => entry.clone(),
+		},
+	};
+	if let Some(regex) = &grep {
+		tree = grep_tree(&tree, regex);
+	}
+	
+	let output = debug_string(&tree, max_depth);
+	if arguments.flag("--no-color") {
+		print!("{}", strip_colors(&output));
+	} else {
+		print!("{}", output);
+	}
+	Ok(ExitCode::SUCCESS)
+}
+
+//Keeps entries whose key or value matches, their parents and everything inside of matching keys.
+fn grep_tree(tree: &JecsType, regex: &Regex) -> JecsType {
+	return tree.filter(|path, entry| {
+		let key_matches = |segment: &JecsPathSegment| matches!(segment, JecsPathSegment::Key(key) if regex.is_match(key));
+		path.segments().iter().any(key_matches) || contains_match(entry, regex)
+	});
+	
+	fn contains_match(entry: &JecsType, regex: &Regex) -> bool {
+		match entry {
+			JecsType::Any() | JecsType::Null() => false,
+			JecsType::Value(value) => regex.is_match(value),
+			JecsType::Map(map) => map.iter().any(|(key, child)| regex.is_match(key) || contains_match(child, regex)),
+			JecsType::List(list) => list.iter().any(|child| contains_match(child, regex)),
+		}
+	}
+}
+
+//Removes the ANSI escape sequences, which are all of the form 'ESC [ ... m'.
+fn strip_colors(text: &str) -> String {
+	let mut output = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find('\x1b') {
+		output.push_str(&rest[..start]);
+		rest = &rest[start..];
+		rest = match rest.find('m') {
+			Some(end) => &rest[end + 1..],
+			None => "",
+		};
+	}
+	output.push_str(rest);
+	output
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::process::ExitCode;
+
+use ecc_jecs_lib::decode::FromJecs;
+use ecc_jecs_lib::diagnostic;
+use ecc_jecs_lib::document::JecsDocument;
+use ecc_jecs_lib::path::JecsPath;
+use ecc_jecs_lib::schema::JecsSchema;
+use ecc_jecs_lib::spanned::JecsSpan;
+use ecc_jecs_lib::types::JecsType;
+
+use crate::arguments::Arguments;
+use crate::load_tree;
+use crate::report::{message_without_code, print_json, Diagnostic, OutputFormat, Severity};
+
+//Prints every violation as 'file:line: message' (or as JSON diagnostics) and exits with 1 if any file is invalid.
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let format = OutputFormat::from_arguments(&arguments)?;
+	let schema_file = arguments.value("--schema").ok_or("Missing option '--schema <file>'.")?;
+	let files = arguments.positional();
+	if files.is_empty() {
+		return Err("Missing file arguments.".to_owned());
+	}
+	let schema = JecsSchema::from_jecs(&load_tree(schema_file.as_ref())?)
+		.map_err(|error| format!("Schema '{}' is invalid: {}", schema_file, error.to_string().trim_end()))?;
+	
+	let mut diagnostics = Vec::new();
+	let mut invalid_files = 0;
+	for file in files {
+		let text = fs::read_to_string(file).map_err(|error| format!("Could not read '{}': {}", file, error))?;
+		let document = match JecsDocument::parse(&text) {
+			Ok(document) => document,
+			Err(error) => {
+				invalid_files += 1;
+				match format {
+					OutputFormat::Text => eprint!("{}: {}", file, diagnostic::render_error(&text, &error)),
+					//Parse errors only know where they start, thus they cover the rest of the line:
+					OutputFormat::Json => diagnostics.push(Diagnostic::new(file, &text, error.row, error.column..usize::MAX, Severity::Error, error.code, error.description)),
+				}
+				continue;
+			}
+		};
+		let violations = schema.validate(&JecsType::Map(document.to_jecs_map()));
+		if !violations.is_empty() {
+			invalid_files += 1;
+		}
+		for violation in violations {
+			let message = violation.to_string();
+			let span = violation.path().and_then(|path| span_of(&document, path));
+			match format {
+				OutputFormat::Text => match span {
+					Some(span) => println!("{}:{}: {}", file, span.row, message.trim_end()),
+					None => println!("{}: {}", file, message.trim_end()),
+				},
+				OutputFormat::Json => {
+					let (row, columns) = match span {
+						Some(span) => (span.row, span.key_columns.start..span.value_columns.as_ref().unwrap_or(&span.key_columns).end),
+						None => (0, 0..0),
+					};
+					let code = violation.error_code();
+					diagnostics.push(Diagnostic::new(file, &text, row, columns, Severity::Error, code, message_without_code(&message, code)));
+				}
+			}
+		}
+		//Unknown keys are only warnings, they do not make the file invalid:
+		if arguments.flag("--unknown-keys") {
+			for warning in schema.unknown_key_warnings(&document) {
+				match format {
+					OutputFormat::Text => print!("{}: {}", file, diagnostic::render_warning(&text, &warning)),
+					OutputFormat::Json => diagnostics.push(Diagnostic::new(file, &text, warning.row, warning.column..usize::MAX, Severity::Warning, warning.code, warning.description)),
+				}
+			}
+		}
+	}
+	if format == OutputFormat::Json {
+		print_json(&diagnostics);
+	}
+	if invalid_files != 0 {
+		eprintln!("{} of {} files are invalid.", invalid_files, files.len());
+		return Ok(ExitCode::FAILURE);
+	}
+	Ok(ExitCode::SUCCESS)
+}
+
+//Span of the entry, or of its closest existing parent. The root has no span.
+fn span_of<'a>(document: &'a JecsDocument, path: &JecsPath) -> Option<&'a JecsSpan> {
+	let mut path = path.clone();
+	while !path.is_root() {
+		if let Some(entry) = document.get_entry(&path) {
+			return Some(&entry.span);
+		}
+		path.pop();
+	}
+	None
+}
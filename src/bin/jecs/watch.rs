@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use ecc_jecs_lib::diff::JecsChange;
+use ecc_jecs_lib::types::JecsType;
+
+use crate::arguments::Arguments;
+use crate::load_tree;
+
+struct WatchedFile {
+	modified: SystemTime,
+	tree: JecsType, //Last version which could be parsed
+}
+
+//Polls the modification times, as that works the same on every platform and for files on network drives.
+//Runs until the process is stopped.
+pub fn run(arguments: Arguments) -> Result<ExitCode, String> {
+	let target = arguments.single_file()?;
+	let interval = Duration::from_millis(arguments.parsed_value("--interval")?.unwrap_or(500));
+	if !target.exists() {
+		return Err(format!("'{}' does not exist.", target.display()));
+	}
+	
+	let mut files: BTreeMap<PathBuf, WatchedFile> = BTreeMap::new();
+	for (path, modified) in scan(target) {
+		let tree = load_tree(&path).unwrap_or_else(|message| {
+			eprintln!("{}", message);
+			JecsType::new_map()
+		});
+		files.insert(path, WatchedFile {
+			modified,
+			tree,
+		});
+	}
+	println!("Watching {} files, stop with Ctrl+C.", files.len());
+	
+	loop {
+		thread::sleep(interval);
+		let current = scan(target);
+		for (path, file) in &files {
+			if !current.contains_key(path) {
+				println!("{}: removed", path.display());
+				print_changes(&file.tree.diff(&JecsType::new_map()));
+			}
+		}
+		files.retain(|path, _| current.contains_key(path));
+		for (path, modified) in current {
+			let (old_tree, state) = match files.get(&path) {
+				Some(file) if file.modified == modified => continue,
+				Some(file) => (file.tree.clone(), "changed"),
+				None => (JecsType::new_map(), "created"),
+			};
+			//Keep the last parsed version on errors, as files are often read while being written:
+			let tree = match load_tree(&path) {
+				Ok(tree) => tree,
+				Err(message) => {
+					eprintln!("{}", message);
+					old_tree.clone()
+				}
+			};
+			let changes = old_tree.diff(&tree);
+			if !changes.is_empty() || state == "created" {
+				println!("{}: {}", path.display(), state);
+				print_changes(&changes);
+			}
+			files.insert(path, WatchedFile {
+				modified,
+				tree,
+			});
+		}
+	}
+}
+
+fn print_changes(changes: &[JecsChange]) {
+	for change in changes {
+		match change {
+			JecsChange::Added { path, entry } => println!("  + {}: {}", path, describe(entry)),
+			JecsChange::Removed { path, entry } => println!("  - {}: {}", path, describe(entry)),
+			JecsChange::Changed { path, old, new } => println!("  ~ {}: {} -> {}", path, describe(old), describe(new)),
+		}
+	}
+}
+
+fn describe(entry: &JecsType) -> String {
+	match entry {
+		JecsType::Value(value) => format!("'{}'", value),
+		JecsType::Any() => "---".to_owned(),
+		JecsType::Null() => "null".to_owned(),
+		JecsType::Map(map) => format!("<map with {} entries>", map.len()),
+		JecsType::List(list) => format!("<list with {} entries>", list.len()),
+	}
+}
+
+//The file itself, or all JECS files within the directory and its subdirectories.
+fn scan(target: &Path) -> BTreeMap<PathBuf, SystemTime> {
+	let mut files = BTreeMap::new();
+	if target.is_dir() {
+		walk_folder(target, &mut files);
+	} else if let Ok(modified) = fs::metadata(target).and_then(|metadata| metadata.modified()) {
+		files.insert(target.to_owned(), modified);
+	}
+	return files;
+	
+	fn walk_folder(path: &Path, files: &mut BTreeMap<PathBuf, SystemTime>) {
+		//Folders might get deleted while scanning, those are skipped:
+		let entries = match fs::read_dir(path) {
+			Ok(entries) => entries,
+			Err(_) => return,
+		};
+		for entry in entries.flatten() {
+			let entry_path = entry.path();
+			let metadata = match entry_path.symlink_metadata() {
+				Ok(metadata) => metadata,
+				Err(_) => continue,
+			};
+			if metadata.is_dir() {
+				walk_folder(&entry_path, files);
+			} else if metadata.is_file() && is_jecs_file(&entry_path) {
+				if let Ok(modified) = metadata.modified() {
+					files.insert(entry_path, modified);
+				}
+			}
+		}
+	}
+}
+
+//SUCC is the old name of JECS, Logic World still uses it for its files.
+fn is_jecs_file(path: &Path) -> bool {
+	matches!(path.extension().and_then(|extension| extension.to_str()), Some("succ" | "jecs"))
+}
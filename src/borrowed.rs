@@ -0,0 +1,85 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::types::{HashMap, JecsHasher, JecsMap, JecsType};
+
+pub type JecsRefMap<'a> = HashMap<Cow<'a, str>, JecsRef<'a>, JecsHasher>;
+
+//Same as JecsType, but keys and values can borrow from the parsed text (see parser::parse_jecs_string_borrowed()).
+//Inspecting a file thus does not copy its content, trees which are kept or edited can be turned into a JecsType with into_owned().
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub enum JecsRef<'a> {
+	Any(),
+	Null(),
+	Value(Cow<'a, str>),
+	Map(JecsRefMap<'a>),
+	List(Vec<JecsRef<'a>>),
+}
+
+impl<'a> JecsRef<'a> {
+	pub fn get_value(&self) -> Option<&str> {
+		match self {
+			JecsRef::Value(value) => Some(value),
+			_ => None,
+		}
+	}
+	
+	pub fn get_map(&self) -> Option<&JecsRefMap<'a>> {
+		match self {
+			JecsRef::Map(map) => Some(map),
+			_ => None,
+		}
+	}
+	
+	pub fn get_list(&self) -> Option<&[JecsRef<'a>]> {
+		match self {
+			JecsRef::List(list) => Some(list),
+			_ => None,
+		}
+	}
+	
+	pub fn get(&self, key: &str) -> Option<&JecsRef<'a>> {
+		self.get_map()?.get(key)
+	}
+	
+	pub fn get_index(&self, index: usize) -> Option<&JecsRef<'a>> {
+		self.get_list()?.get(index)
+	}
+	
+	//Moves owned keys and values into the new tree, only borrowed ones are copied.
+	pub fn into_owned(self) -> JecsType {
+		match self {
+			JecsRef::Any() => JecsType::Any(),
+			JecsRef::Null() => JecsType::Null(),
+			JecsRef::Value(value) => JecsType::value(value),
+			JecsRef::Map(map) => {
+				let mut output = JecsMap::with_capacity_and_hasher(map.len(), Default::default());
+				for (key, entry) in map {
+					output.insert(key.into_owned(), entry.into_owned());
+				}
+				JecsType::Map(output)
+			}
+			JecsRef::List(list) => JecsType::List(list.into_iter().map(JecsRef::into_owned).collect()),
+		}
+	}
+}
+
+impl<'a> From<JecsRef<'a>> for JecsType {
+	fn from(entry: JecsRef<'a>) -> Self {
+		entry.into_owned()
+	}
+}
+
+//Borrows the keys and values of an existing tree.
+impl<'a> From<&'a JecsType> for JecsRef<'a> {
+	fn from(entry: &'a JecsType) -> Self {
+		match entry {
+			JecsType::Any() => JecsRef::Any(),
+			JecsType::Null() => JecsRef::Null(),
+			JecsType::Value(value) => JecsRef::Value(Cow::Borrowed(value)),
+			JecsType::Map(map) => JecsRef::Map(map.iter().map(|(key, child)| (Cow::Borrowed(key.as_str()), JecsRef::from(child))).collect()),
+			JecsType::List(list) => JecsRef::List(list.iter().map(JecsRef::from).collect()),
+		}
+	}
+}
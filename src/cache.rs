@@ -0,0 +1,173 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::str::from_utf8;
+
+use crate::errors::JecsCacheError;
+use crate::types::{JecsMap, JecsType};
+
+//Binary form of a tree, for applications which load the same big files on every start.
+//Layout: "JECS" + version byte, then every entry as type byte followed by its content.
+//Values and keys are written as length + UTF-8 bytes, maps and lists as length + entries. Lengths are LEB128 encoded.
+const MAGIC: &[u8; 4] = b"JECS";
+const VERSION: u8 = 1;
+
+const TAG_ANY: u8 = 0;
+const TAG_VALUE: u8 = 1;
+const TAG_MAP: u8 = 2;
+const TAG_LIST: u8 = 3;
+const TAG_NULL: u8 = 4;
+
+//Reading is recursive, thus a corrupted cache with deeply nested entries would overflow the stack. Such deep trees are parsed from the text instead.
+const MAX_DEPTH: usize = 256;
+
+pub fn compile_to_cache(tree: &JecsType) -> Vec<u8> {
+	let mut output = Vec::new();
+	output.extend_from_slice(MAGIC);
+	output.push(VERSION);
+	write_entry(&mut output, tree);
+	return output;
+	
+	fn write_entry(output: &mut Vec<u8>, entry: &JecsType) {
+		match entry {
+			JecsType::Any() => output.push(TAG_ANY),
+			JecsType::Null() => output.push(TAG_NULL),
+			JecsType::Value(value) => {
+				output.push(TAG_VALUE);
+				write_str(output, value);
+			}
+			JecsType::Map(map) => {
+				output.push(TAG_MAP);
+				write_length(output, map.len());
+				for (key, child) in map {
+					write_str(output, key);
+					write_entry(output, child);
+				}
+			}
+			JecsType::List(list) => {
+				output.push(TAG_LIST);
+				write_length(output, list.len());
+				for child in list {
+					write_entry(output, child);
+				}
+			}
+		}
+	}
+	
+	fn write_str(output: &mut Vec<u8>, text: &str) {
+		write_length(output, text.len());
+		output.extend_from_slice(text.as_bytes());
+	}
+	
+	fn write_length(output: &mut Vec<u8>, mut length: usize) {
+		while length >= 0x80 {
+			output.push((length as u8 & 0x7F) | 0x80);
+			length >>= 7;
+		}
+		output.push(length as u8);
+	}
+}
+
+//Caches of other versions are rejected, in that case parse the text again and replace the cache.
+pub fn load_from_cache(bytes: &[u8]) -> Result<JecsType, JecsCacheError> {
+	let mut reader = CacheReader {
+		bytes,
+		position: 0,
+	};
+	if reader.read_bytes(MAGIC.len())? != MAGIC {
+		return Err(cache_error("Not a JECS cache"));
+	}
+	let version = reader.read_byte()?;
+	if version != VERSION {
+		return Err(cache_error(&format!("Cache has version {}, but only version {} is supported", version, VERSION)));
+	}
+	let tree = reader.read_entry(0)?;
+	if reader.position != bytes.len() {
+		return Err(cache_error("Unexpected data after the tree"));
+	}
+	Ok(tree)
+}
+
+struct CacheReader<'a> {
+	bytes: &'a [u8],
+	position: usize,
+}
+
+impl<'a> CacheReader<'a> {
+	fn read_entry(&mut self, depth: usize) -> Result<JecsType, JecsCacheError> {
+		if depth > MAX_DEPTH {
+			return Err(cache_error(&format!("Entries are nested deeper than {} levels at byte {}", MAX_DEPTH, self.position)));
+		}
+		Ok(match self.read_byte()? {
+			TAG_ANY => JecsType::Any(),
+			TAG_NULL => JecsType::Null(),
+			TAG_VALUE => JecsType::value(self.read_str()?),
+			TAG_MAP => {
+				let length = self.read_length()?;
+				//The length is not trusted for the allocation, every entry needs at least 2 bytes:
+				let mut map = JecsMap::with_capacity_and_hasher(length.min(self.remaining() / 2), Default::default());
+				for _ in 0..length {
+					let key = self.read_str()?.to_string();
+					map.insert(key, self.read_entry(depth + 1)?);
+				}
+				JecsType::Map(map)
+			}
+			TAG_LIST => {
+				let length = self.read_length()?;
+				let mut list = Vec::with_capacity(length.min(self.remaining()));
+				for _ in 0..length {
+					list.push(self.read_entry(depth + 1)?);
+				}
+				JecsType::List(list)
+			}
+			tag => return Err(cache_error(&format!("Unknown entry type {} at byte {}", tag, self.position - 1))),
+		})
+	}
+	
+	fn read_str(&mut self) -> Result<&'a str, JecsCacheError> {
+		let length = self.read_length()?;
+		let position = self.position;
+		from_utf8(self.read_bytes(length)?).map_err(|_| cache_error(&format!("Invalid UTF-8 at byte {}", position)))
+	}
+	
+	fn read_length(&mut self) -> Result<usize, JecsCacheError> {
+		let mut length: usize = 0;
+		let mut shift = 0;
+		loop {
+			let byte = self.read_byte()?;
+			let bits = (byte & 0x7F) as usize;
+			//Bits shifted out of the length would silently make it smaller:
+			if shift >= usize::BITS || (bits << shift) >> shift != bits {
+				return Err(cache_error("Length does not fit into memory"));
+			}
+			length |= bits << shift;
+			if byte & 0x80 == 0 {
+				return Ok(length);
+			}
+			shift += 7;
+		}
+	}
+	
+	fn read_byte(&mut self) -> Result<u8, JecsCacheError> {
+		Ok(self.read_bytes(1)?[0])
+	}
+	
+	fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], JecsCacheError> {
+		if length > self.remaining() {
+			return Err(cache_error("Unexpected end of the cache"));
+		}
+		let bytes = &self.bytes[self.position..self.position + length];
+		self.position += length;
+		Ok(bytes)
+	}
+	
+	fn remaining(&self) -> usize {
+		self.bytes.len() - self.position
+	}
+}
+
+fn cache_error(description: &str) -> JecsCacheError {
+	JecsCacheError {
+		description: description.to_string(),
+	}
+}
@@ -0,0 +1,78 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::errors::JecsWriteError;
+use crate::path::JecsPath;
+use crate::types::JecsType;
+use crate::writer::{write_error, write_jecs_string_with_options, KeyOrder, WriterOptions};
+
+impl JecsType {
+	//JECS text with sorted keys and the writer's spacing and without comments. Files with the same content get the same text.
+	pub fn to_canonical_string(&self) -> Result<String, JecsWriteError> {
+		let map = match self {
+			JecsType::Map(map) => map,
+			_ => return Err(write_error(&JecsPath::root(), "Only maps can be written as JECS text")),
+		};
+		let options = WriterOptions {
+			key_order: KeyOrder::Alphabetical,
+			..WriterOptions::default()
+		};
+		write_jecs_string_with_options(map, &options)
+	}
+	
+	//64 bit FNV-1a hash of the content, independent of the map order. It is the same on every platform and version,
+	// thus it can be stored to detect changed configs. Not meant to protect against deliberate collisions.
+	pub fn canonical_hash(&self) -> u64 {
+		let mut hasher = FnvHasher(FNV_OFFSET_BASIS);
+		hash_entry(&mut hasher, self);
+		return hasher.0;
+		
+		fn hash_entry(hasher: &mut FnvHasher, entry: &JecsType) {
+			//Every type gets a tag and every collection its length, so that different trees cannot produce the same bytes:
+			match entry {
+				JecsType::Any() => hasher.write(&[0]),
+				JecsType::Null() => hasher.write(&[4]),
+				JecsType::Value(value) => {
+					hasher.write(&[1]);
+					hasher.write_str(value);
+				}
+				JecsType::Map(map) => {
+					hasher.write(&[2]);
+					hasher.write(&(map.len() as u64).to_le_bytes());
+					let mut entries: Vec<_> = map.iter().collect();
+					entries.sort_by_key(|(key, _)| *key);
+					for (key, child) in entries {
+						hasher.write_str(key);
+						hash_entry(hasher, child);
+					}
+				}
+				JecsType::List(list) => {
+					hasher.write(&[3]);
+					hasher.write(&(list.len() as u64).to_le_bytes());
+					for child in list {
+						hash_entry(hasher, child);
+					}
+				}
+			}
+		}
+	}
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct FnvHasher(u64);
+
+impl FnvHasher {
+	fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes {
+			self.0 ^= *byte as u64;
+			self.0 = self.0.wrapping_mul(FNV_PRIME);
+		}
+	}
+	
+	fn write_str(&mut self, text: &str) {
+		self.write(&(text.len() as u64).to_le_bytes());
+		self.write(text.as_bytes());
+	}
+}
@@ -0,0 +1,115 @@
+use std::string::String;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::path::{JecsPath, JecsPathSegment};
+use crate::shared::SharedJecs;
+use crate::types::{HashMap, JecsHasher, JecsMap, JecsType};
+
+//One version of the tree of a JecsCell. The root entries are shared with the other versions, an edit only copies the root entries it changes.
+#[derive(Clone, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub struct JecsSnapshot {
+	entries: HashMap<String, SharedJecs, JecsHasher>,
+}
+
+impl JecsSnapshot {
+	pub fn get(&self, key: &str) -> Option<&JecsType> {
+		self.entries.get(key).map(|entry| &**entry)
+	}
+	
+	pub fn get_path(&self, path: impl Into<JecsPath>) -> Option<&JecsType> {
+		let path = path.into();
+		let (first, rest) = path.segments().split_first()?;
+		let mut entry = match first {
+			JecsPathSegment::Key(key) => self.get(key)?,
+			JecsPathSegment::Index(_) => return None,
+		};
+		for segment in rest {
+			entry = entry.get_child(segment)?;
+		}
+		Some(entry)
+	}
+	
+	pub fn get_shared(&self, key: &str) -> Option<&SharedJecs> {
+		self.entries.get(key)
+	}
+	
+	//The entry is copied, if older snapshots still use it.
+	pub fn get_mut(&mut self, key: &str) -> Option<&mut JecsType> {
+		self.entries.get_mut(key).map(SharedJecs::make_mut)
+	}
+	
+	pub fn insert(&mut self, key: impl Into<String>, entry: impl Into<SharedJecs>) -> Option<SharedJecs> {
+		self.entries.insert(key.into(), entry.into())
+	}
+	
+	pub fn remove(&mut self, key: &str) -> Option<SharedJecs> {
+		self.entries.remove(key)
+	}
+	
+	pub fn keys(&self) -> impl Iterator<Item = &str> {
+		self.entries.keys().map(String::as_str)
+	}
+	
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+	
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+	
+	//Copies the whole tree.
+	pub fn to_jecs_map(&self) -> JecsMap {
+		self.entries.iter().map(|(key, entry)| (key.clone(), JecsType::clone(entry))).collect()
+	}
+}
+
+impl From<JecsMap> for JecsSnapshot {
+	fn from(map: JecsMap) -> Self {
+		Self {
+			entries: map.into_iter().map(|(key, entry)| (key, SharedJecs::new(entry))).collect(),
+		}
+	}
+}
+
+//Holds the current version of a tree for many reading threads, which get consistent snapshots of it.
+//Edits are done on a copy of the current snapshot, which shares all root entries that were not edited. Once done, the copy becomes the current snapshot.
+//Snapshots taken before stay unchanged. Edits are applied one after another, readers never wait for an edit to finish.
+#[derive(Default)]
+pub struct JecsCell {
+	current: RwLock<Arc<JecsSnapshot>>,
+	writer: Mutex<()>,
+}
+
+impl JecsCell {
+	pub fn new(snapshot: JecsSnapshot) -> Self {
+		Self {
+			current: RwLock::new(Arc::new(snapshot)),
+			writer: Mutex::new(()),
+		}
+	}
+	
+	pub fn snapshot(&self) -> Arc<JecsSnapshot> {
+		self.current.read().unwrap_or_else(|error| error.into_inner()).clone()
+	}
+	
+	//Replaces the whole tree, like after reloading a file.
+	pub fn store(&self, snapshot: impl Into<JecsSnapshot>) {
+		let _writer = self.writer.lock().unwrap_or_else(|error| error.into_inner());
+		self.swap(snapshot.into());
+	}
+	
+	//If the edit panics, the current snapshot stays as it is.
+	pub fn update<R>(&self, edit: impl FnOnce(&mut JecsSnapshot) -> R) -> R {
+		let _writer = self.writer.lock().unwrap_or_else(|error| error.into_inner());
+		let mut snapshot = JecsSnapshot::clone(&self.snapshot());
+		let result = edit(&mut snapshot);
+		self.swap(snapshot);
+		result
+	}
+	
+	fn swap(&self, snapshot: JecsSnapshot) {
+		*self.current.write().unwrap_or_else(|error| error.into_inner()) = Arc::new(snapshot);
+	}
+}
@@ -0,0 +1,137 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use crate::errors::{JecsDecodeError, JecsIncompatibleOrMalformedError, JecsOutOfRangeError};
+use crate::types::{malformed, parse_color, parse_component_address, JecsType};
+
+//Checks the text of values in an application specific format. Closures taking the value text can be registered directly.
+pub trait JecsValueCodec: Send + Sync {
+	fn check(&self, value: &str) -> Result<(), JecsDecodeError>;
+}
+
+impl<F: Fn(&str) -> Result<(), JecsDecodeError> + Send + Sync> JecsValueCodec for F {
+	fn check(&self, value: &str) -> Result<(), JecsDecodeError> {
+		self(value)
+	}
+}
+
+//Value formats by name, used by expect_custom() and by schemas with a type that is no built-in type.
+//The default registry knows:
+// color: 'RRGGBB' in upper case hex digits
+// address: Component address like 'C-123'
+// duration: Numbers with unit, like '1h30m' or '500ms'. Units are 'd', 'h', 'm', 's' and 'ms'
+#[derive(Clone)]
+pub struct JecsCodecRegistry {
+	codecs: BTreeMap<String, Arc<dyn JecsValueCodec>>,
+}
+
+impl JecsCodecRegistry {
+	//A registry without the default codecs.
+	pub fn empty() -> Self {
+		Self {
+			codecs: BTreeMap::new(),
+		}
+	}
+	
+	//Adds the codec, replacing an existing one with the same name.
+	pub fn register(&mut self, name: &str, codec: impl JecsValueCodec + 'static) {
+		self.codecs.insert(name.to_string(), Arc::new(codec));
+	}
+	
+	pub fn with_codec(mut self, name: &str, codec: impl JecsValueCodec + 'static) -> Self {
+		self.register(name, codec);
+		self
+	}
+	
+	pub fn get(&self, name: &str) -> Option<&dyn JecsValueCodec> {
+		self.codecs.get(name).map(|codec| &**codec)
+	}
+	
+	pub fn contains(&self, name: &str) -> bool {
+		self.codecs.contains_key(name)
+	}
+	
+	pub fn names(&self) -> impl Iterator<Item = &str> {
+		self.codecs.keys().map(String::as_str)
+	}
+	
+	//Values of unknown formats are rejected, as nothing can tell whether they are valid.
+	pub fn check(&self, name: &str, value: &str) -> Result<(), JecsDecodeError> {
+		match self.get(name) {
+			Some(codec) => codec.check(value),
+			None => Err(JecsIncompatibleOrMalformedError {
+				data_type: format!("{} (unknown value format)", name),
+				value: value.to_string(),
+				path: None,
+			}.into()),
+		}
+	}
+}
+
+impl Default for JecsCodecRegistry {
+	fn default() -> Self {
+		Self::empty()
+			.with_codec("color", |value: &str| parse_color(value).map(drop))
+			.with_codec("address", |value: &str| parse_component_address(value).map(drop))
+			.with_codec("duration", |value: &str| parse_duration(value).map(drop))
+	}
+}
+
+impl core::fmt::Debug for JecsCodecRegistry {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_set().entries(self.codecs.keys()).finish()
+	}
+}
+
+impl JecsType {
+	//Returns the value, after checking it with the codec registered under the name:
+	// entry.expect_custom(&registry, "duration")
+	pub fn expect_custom(&self, registry: &JecsCodecRegistry, name: &str) -> Result<&str, JecsDecodeError> {
+		let value = match self {
+			JecsType::Value(value) => value,
+			_ => return Err(self.wrong_type(name)),
+		};
+		registry.check(name, value)?;
+		Ok(value)
+	}
+}
+
+//A sequence of numbers with unit, like '1h30m'. Every unit may only appear once and in descending order.
+fn parse_duration(value: &str) -> Result<Duration, JecsDecodeError> {
+	const UNITS: [(&str, u64); 5] = [("d", 86_400_000), ("h", 3_600_000), ("ms", 1), ("m", 60_000), ("s", 1000)];
+	let mut milliseconds: u64 = 0;
+	let mut previous_factor = u64::MAX;
+	if value.is_empty() {
+		return Err(malformed("duration", value));
+	}
+	let mut rest = value;
+	while !rest.is_empty() {
+		let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+		if digits == 0 {
+			return Err(malformed("duration", value));
+		}
+		let (number, unit_and_rest) = rest.split_at(digits);
+		//'ms' is listed before 'm', so that it is not read as minutes:
+		let (unit, factor) = match UNITS.iter().find(|(unit, _)| unit_and_rest.starts_with(unit)) {
+			None => return Err(malformed("duration", value)),
+			Some(unit) => *unit,
+		};
+		if factor >= previous_factor {
+			return Err(malformed("duration", value));
+		}
+		previous_factor = factor;
+		milliseconds = number.parse::<u64>().ok()
+			.and_then(|number| number.checked_mul(factor))
+			.and_then(|part| part.checked_add(milliseconds))
+			.ok_or_else(|| JecsDecodeError::from(JecsOutOfRangeError {
+				data_type: "duration".to_string(),
+				value: value.to_string(),
+				path: None,
+			}))?;
+		rest = &unit_and_rest[unit.len()..];
+	}
+	Ok(Duration::from_millis(milliseconds))
+}
@@ -0,0 +1,87 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::decode::FromJecs;
+use crate::encode::IntoJecs;
+use crate::errors::JecsDecodeError;
+use crate::path::JecsPath;
+use crate::types::{parse_color, JecsType};
+
+//A color as written in JECS files, 'RRGGBB' in upper case hex digits. Display writes it that way.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[derive(Debug)]
+pub struct Color {
+	pub r: u8,
+	pub g: u8,
+	pub b: u8,
+}
+
+impl Color {
+	pub const fn new(r: u8, g: u8, b: u8) -> Self {
+		Self {
+			r,
+			g,
+			b,
+		}
+	}
+}
+
+impl From<(u8, u8, u8)> for Color {
+	fn from((r, g, b): (u8, u8, u8)) -> Self {
+		Self::new(r, g, b)
+	}
+}
+
+impl From<Color> for (u8, u8, u8) {
+	fn from(color: Color) -> Self {
+		(color.r, color.g, color.b)
+	}
+}
+
+impl Display for Color {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+	}
+}
+
+impl FromJecs for Color {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		entry.expect_color().map(Color::from)
+	}
+}
+
+impl IntoJecs for Color {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::value(self.to_string())
+	}
+}
+
+impl JecsType {
+	//Palettes are either a list of colors, or a single value with comma separated colors: "FF0000, 00FF00, 0000FF"
+	//Entries without value or children are empty palettes.
+	pub fn expect_color_list(&self) -> Result<Vec<Color>, JecsDecodeError> {
+		match self {
+			JecsType::Any() => Ok(Vec::new()),
+			JecsType::Value(value) => value.split(',').map(|color| parse_color(color.trim()).map(Color::from)).collect(),
+			_ => self.expect_list()?.iter().enumerate().map(|(index, entry)| {
+				entry.expect_color().map(Color::from).map_err(|error| error.at(JecsPath::root().join_index(index)))
+			}).collect(),
+		}
+	}
+	
+	//A palette in the compact form read by expect_color_list(). Use to_jecs() of the list to write one color per list entry instead.
+	pub fn color_list(colors: &[Color]) -> JecsType {
+		if colors.is_empty() {
+			return JecsType::Any();
+		}
+		let mut value = String::with_capacity(colors.len() * 8);
+		for (index, color) in colors.iter().enumerate() {
+			if index != 0 {
+				value.push_str(", ");
+			}
+			value.push_str(&color.to_string());
+		}
+		JecsType::value(value)
+	}
+}
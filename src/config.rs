@@ -0,0 +1,134 @@
+use std::boxed::Box;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::string::String;
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use crate::decode::FromJecs;
+use crate::errors::JecsLoadError;
+use crate::parser::parse_file_into;
+
+pub type ConfigValidator<T> = dyn Fn(&T) -> Result<(), String> + Send + Sync;
+pub type ConfigErrorCallback = dyn Fn(&JecsLoadError) + Send + Sync;
+
+//Keeps a typed config up to date with its file. Readers always get the last config, which got loaded and validated without problems.
+//Changes are found by polling the modification time and size of the file, on a thread started with watch().
+//Failed reloads keep the previous config and are passed to the error callback. Each change of the file is only tried once.
+pub struct ConfigManager<T> {
+	state: Arc<ConfigState<T>>,
+	watcher: Option<JoinHandle<()>>,
+}
+
+struct ConfigState<T> {
+	path: PathBuf,
+	current: RwLock<Arc<T>>,
+	file_stamp: Mutex<Option<(SystemTime, u64)>>, //Of the last loaded file
+	validator: Box<ConfigValidator<T>>,
+	on_error: Mutex<Option<Box<ConfigErrorCallback>>>,
+	stopped: Mutex<bool>,
+	stop_signal: Condvar,
+}
+
+impl<T: FromJecs + Send + Sync + 'static> ConfigManager<T> {
+	//The first load has to succeed, there is no previous config to fall back to.
+	pub fn load(path: impl Into<PathBuf>) -> Result<Self, JecsLoadError> {
+		Self::load_validated(path, |_| Ok(()))
+	}
+	
+	//The validator gets every decoded config, before it replaces the current one.
+	pub fn load_validated(path: impl Into<PathBuf>, validator: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static) -> Result<Self, JecsLoadError> {
+		let path = path.into();
+		let file_stamp = file_stamp(&path)?;
+		let config = load_config(&path, &validator)?;
+		Ok(Self {
+			state: Arc::new(ConfigState {
+				path,
+				current: RwLock::new(Arc::new(config)),
+				file_stamp: Mutex::new(Some(file_stamp)),
+				validator: Box::new(validator),
+				on_error: Mutex::new(None),
+				stopped: Mutex::new(false),
+				stop_signal: Condvar::new(),
+			}),
+			watcher: None,
+		})
+	}
+	
+	pub fn path(&self) -> &Path {
+		&self.state.path
+	}
+	
+	pub fn get(&self) -> Arc<T> {
+		self.state.current.read().unwrap_or_else(|error| error.into_inner()).clone()
+	}
+	
+	//Gets called with the errors of reloads done by the watching thread.
+	pub fn set_error_callback(&self, callback: impl Fn(&JecsLoadError) + Send + Sync + 'static) {
+		*self.state.on_error.lock().unwrap_or_else(|error| error.into_inner()) = Some(Box::new(callback));
+	}
+	
+	//Loads the file, if it changed since the last load. Returns if the config got replaced.
+	pub fn reload(&self) -> Result<bool, JecsLoadError> {
+		self.state.reload()
+	}
+	
+	//Starts checking the file for changes in the interval, until the manager is dropped.
+	pub fn watch(&mut self, interval: Duration) {
+		if self.watcher.is_some() {
+			return;
+		}
+		let state = self.state.clone();
+		self.watcher = Some(thread::spawn(move || {
+			let mut stopped = state.stopped.lock().unwrap_or_else(|error| error.into_inner());
+			while !*stopped {
+				stopped = state.stop_signal.wait_timeout(stopped, interval).unwrap_or_else(|error| error.into_inner()).0;
+				if *stopped {
+					break;
+				}
+				if let Err(error) = state.reload() {
+					if let Some(callback) = &*state.on_error.lock().unwrap_or_else(|error| error.into_inner()) {
+						callback(&error);
+					}
+				}
+			}
+		}));
+	}
+}
+
+impl<T: FromJecs> ConfigState<T> {
+	fn reload(&self) -> Result<bool, JecsLoadError> {
+		let new_stamp = file_stamp(&self.path)?;
+		let mut file_stamp = self.file_stamp.lock().unwrap_or_else(|error| error.into_inner());
+		if *file_stamp == Some(new_stamp) {
+			return Ok(false);
+		}
+		//Stored before loading, so that a broken file is not reported again until it changes:
+		*file_stamp = Some(new_stamp);
+		let config = load_config(&self.path, &self.validator)?;
+		*self.current.write().unwrap_or_else(|error| error.into_inner()) = Arc::new(config);
+		Ok(true)
+	}
+}
+
+impl<T> Drop for ConfigManager<T> {
+	fn drop(&mut self) {
+		if let Some(watcher) = self.watcher.take() {
+			*self.state.stopped.lock().unwrap_or_else(|error| error.into_inner()) = true;
+			self.state.stop_signal.notify_all();
+			let _ = watcher.join();
+		}
+	}
+}
+
+fn load_config<T: FromJecs>(path: &Path, validator: &impl Fn(&T) -> Result<(), String>) -> Result<T, JecsLoadError> {
+	let config = parse_file_into(path)?;
+	validator(&config).map_err(JecsLoadError::Invalid)?;
+	Ok(config)
+}
+
+fn file_stamp(path: &Path) -> Result<(SystemTime, u64), JecsLoadError> {
+	let metadata = fs::metadata(path)?;
+	Ok((metadata.modified()?, metadata.len()))
+}
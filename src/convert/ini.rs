@@ -0,0 +1,195 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::errors::{JecsConvertError, JecsWriteError};
+use crate::path::{JecsPath, JecsPathSegment};
+use crate::redact::JecsRedaction;
+use crate::types::JecsType;
+use crate::writer::{validate_key, write_error};
+
+//Maps at the root become sections, everything below them is flattened into keys written as path:
+//Name = Wire
+//
+//[Settings]
+//Inputs[0] = 0
+//Nested.Deep = 1
+//Entries of the root which are no maps are written before the first section.
+//Any entries and empty maps or lists get an empty value, values with spaces around them are quoted.
+//Keys and sections are written in alphabetical order.
+pub fn to_ini_string(tree: &JecsType) -> Result<String, JecsWriteError> {
+	let root = match tree {
+		JecsType::Map(map) => map,
+		JecsType::Any() => return Ok(String::new()),
+		_ => return Err(write_error(&JecsPath::root(), "The root of INI files has to be a map")),
+	};
+	let mut keys: Vec<_> = root.keys().collect();
+	keys.sort();
+	let is_section = |entry: &JecsType| matches!(entry, JecsType::Map(map) if !map.is_empty());
+	
+	let mut output = String::new();
+	let mut path = JecsPath::root();
+	for key in keys.iter().filter(|key| !is_section(&root[**key])) {
+		path.push_key(key);
+		write_entries(&mut output, &mut path, 0, &root[*key])?;
+		path.pop();
+	}
+	for key in keys.iter().filter(|key| is_section(&root[**key])) {
+		path.push_key(key);
+		if !output.is_empty() {
+			output.push('\n');
+		}
+		output.push_str(&format!("[{}]\n", key));
+		write_entries(&mut output, &mut path, 1, &root[*key])?;
+		path.pop();
+	}
+	return Ok(output);
+	
+	//The key of each line is the path below the section, which starts at 'section_length'.
+	fn write_entries(output: &mut String, path: &mut JecsPath, section_length: usize, entry: &JecsType) -> Result<(), JecsWriteError> {
+		if let Some(JecsPathSegment::Key(key)) = path.last() {
+			validate_ini_key(key).map_err(|description| write_error(path, description))?;
+		}
+		match entry {
+			JecsType::Map(map) if !map.is_empty() => {
+				let mut keys: Vec<_> = map.keys().collect();
+				keys.sort();
+				for key in keys {
+					path.push_key(key);
+					write_entries(output, path, section_length, &map[key])?;
+					path.pop();
+				}
+			}
+			JecsType::List(list) if !list.is_empty() => {
+				for (index, child) in list.iter().enumerate() {
+					path.push_index(index);
+					write_entries(output, path, section_length, child)?;
+					path.pop();
+				}
+			}
+			_ => {
+				let mut key = JecsPath::root();
+				for segment in &path.segments()[section_length..] {
+					match segment {
+						JecsPathSegment::Key(segment_key) => key.push_key(segment_key),
+						JecsPathSegment::Index(index) => key.push_index(*index),
+					}
+				}
+				output.push_str(&key.to_string());
+				output.push_str(" =");
+				if let JecsType::Value(value) = entry {
+					if value.contains(['\n', '\r']) {
+						return Err(write_error(path, "Values with line breaks are not supported by INI files"));
+					}
+					let needs_quotes = value.trim() != value.as_str() || value.is_empty() || (value.len() >= 2 && value.starts_with('"') && value.ends_with('"'));
+					output.push(' ');
+					if needs_quotes {
+						output.push_str(&format!("\"{}\"", value));
+					} else {
+						output.push_str(value);
+					}
+				}
+				output.push('\n');
+			}
+		}
+		Ok(())
+	}
+	
+	//Keys are read back as path, thus may not contain its separators, and may not look like a comment or section.
+	fn validate_ini_key(key: &str) -> Result<(), &'static str> {
+		validate_key(key)?;
+		if key.contains(['.', '[', ']', '=']) {
+			return Err("Keys of INI files may not contain '.', '[', ']' or '='");
+		}
+		if key.starts_with(';') {
+			return Err("Keys of INI files may not start with ';', as that marks comments");
+		}
+		Ok(())
+	}
+}
+
+//Same as to_ini_string(), but replaces sensitive values, so that the output can be shared.
+pub fn to_ini_string_redacted(tree: &JecsType, redaction: &JecsRedaction) -> Result<String, JecsWriteError> {
+	to_ini_string(&redaction.redacted(tree))
+}
+
+//Reads INI and properties files. Keys and section names are paths like "Settings.Inputs[0]", which are created as needed.
+//Keys are separated from values by '=' or ':', lines starting with ';' or '#' are comments.
+//Values are read as text with the surrounding quotes removed, keys without value become Any entries.
+//A key which is set again replaces the earlier value.
+pub fn from_ini_str(text: &str) -> Result<JecsType, JecsConvertError> {
+	let mut tree = JecsType::new_map();
+	let mut section = JecsPath::root();
+	for (line_index, line) in text.lines().enumerate() {
+		let row = line_index + 1;
+		let line = line.trim();
+		if line.is_empty() || line.starts_with([';', '#']) {
+			continue;
+		}
+		if let Some(header) = line.strip_prefix('[') {
+			let name = match header.strip_suffix(']') {
+				None => return Err(convert_error(row, "Section header is missing its closing ']'")),
+				Some(name) => name.trim(),
+			};
+			section = JecsPath::parse(name);
+			let entry = entry_or_insert(&mut tree, &section).ok_or_else(|| convert_error(row, &format!("Section '{}' conflicts with an earlier entry", name)))?;
+			if entry.is_any() {
+				*entry = JecsType::new_map();
+			}
+			continue;
+		}
+		let separator = match line.find(['=', ':']) {
+			None => return Err(convert_error(row, "Line is neither a section, a comment nor a 'key = value' pair")),
+			Some(separator) => separator,
+		};
+		let key = line[..separator].trim();
+		if key.is_empty() {
+			return Err(convert_error(row, "Key is missing before the separator"));
+		}
+		let value = line[separator + 1..].trim();
+		let value = match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+			Some(quoted) => JecsType::value(quoted),
+			None if value.is_empty() => JecsType::Any(),
+			None => JecsType::value(value),
+		};
+		let mut path = section.clone();
+		path.extend(JecsPath::parse(key));
+		match entry_or_insert(&mut tree, &path) {
+			None => return Err(convert_error(row, &format!("Key '{}' conflicts with an earlier entry", path))),
+			Some(entry) => *entry = value,
+		}
+	}
+	return Ok(tree);
+	
+	//Any entries on the way turn into maps or lists. Lists can only grow by one entry at their end, as there is nothing to fill gaps with.
+	fn entry_or_insert<'a>(tree: &'a mut JecsType, path: &JecsPath) -> Option<&'a mut JecsType> {
+		let mut entry = tree;
+		for segment in path.segments() {
+			if entry.is_any() {
+				*entry = match segment {
+					JecsPathSegment::Key(_) => JecsType::new_map(),
+					JecsPathSegment::Index(_) => JecsType::new_list(),
+				};
+			}
+			entry = match (entry, segment) {
+				(JecsType::Map(map), JecsPathSegment::Key(key)) => map.entry(key.clone()).or_insert_with(JecsType::Any),
+				(JecsType::List(list), JecsPathSegment::Index(index)) => {
+					if *index == list.len() {
+						list.push(JecsType::Any());
+					}
+					list.get_mut(*index)?
+				}
+				_ => return None,
+			};
+		}
+		Some(entry)
+	}
+}
+
+fn convert_error(row: usize, description: &str) -> JecsConvertError {
+	JecsConvertError {
+		format: "INI",
+		row,
+		description: description.to_string(),
+	}
+}
@@ -0,0 +1,388 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::document::JecsDocument;
+use crate::errors::JecsConvertError;
+use crate::path::JecsPath;
+use crate::types::{JecsMap, JecsType};
+use crate::writer::{write_line, WriterOptions, INDENTATION_STEP};
+
+//Reads JSON, JSONC and JSON5 as tree. Strings, numbers and booleans become values with their text, numbers as written.
+//Null, empty objects and empty arrays become Any entries. The root has to be an object.
+pub fn from_json5_str(text: &str) -> Result<JecsType, JecsConvertError> {
+	let (_, root, _) = Parser::new(text).parse_document()?;
+	return Ok(to_jecs_type(root));
+	
+	fn to_jecs_type(node: Node) -> JecsType {
+		match node {
+			Node::Value(value) => JecsType::value(value),
+			Node::Null => JecsType::Any(),
+			Node::Object(members, _) if members.is_empty() => JecsType::Any(),
+			Node::Object(members, _) => {
+				let mut map = JecsMap::default();
+				for member in members {
+					map.insert(member.key.unwrap(), to_jecs_type(member.node));
+				}
+				JecsType::Map(map)
+			}
+			Node::Array(members, _) if members.is_empty() => JecsType::Any(),
+			Node::Array(members, _) => JecsType::List(members.into_iter().map(|member| to_jecs_type(member.node)).collect()),
+		}
+	}
+}
+
+//Same as from_json5_str(), but keeps the order of the entries and their comments, as document for a one-time migration to JECS.
+//Comments above an entry or behind its value on the same line become the comment of the JECS entry, see JecsDocument::get_comment().
+//Comments at the end of an object or array stay below its last entry, comments around the root object at the start and end of the file.
+//Values are written with the options, the key order of the options is not used.
+pub fn from_json5_document(text: &str, options: &WriterOptions) -> Result<JecsDocument, JecsConvertError> {
+	let (header, root, footer) = Parser::new(text).parse_document()?;
+	let mut output = String::new();
+	if !header.is_empty() {
+		push_comment(&mut output, 0, &header);
+		//The empty line keeps the header from becoming the comment of the first entry:
+		output.push('\n');
+	}
+	if let Node::Object(members, dangling) = &root {
+		write_members(&mut output, &mut JecsPath::root(), 0, members, options)?;
+		push_comment(&mut output, 0, dangling);
+	}
+	push_comment(&mut output, 0, &footer);
+	return JecsDocument::parse(&output).map_err(|error| convert_error(0, &format!("Converted text cannot be parsed again: {}", error.description)));
+	
+	fn write_members(output: &mut String, path: &mut JecsPath, depth: usize, members: &[Member], options: &WriterOptions) -> Result<(), JecsConvertError> {
+		for (index, member) in members.iter().enumerate() {
+			match &member.key {
+				Some(key) => path.push_key(key),
+				None => path.push_index(index),
+			}
+			push_comment(output, depth, &member.comment);
+			let value = match &member.node {
+				Node::Value(value) => Some(value.as_str()),
+				_ => None,
+			};
+			write_line(output, path, depth, member.key.as_deref(), value, options)
+				.map_err(|error| convert_error(member.row, &format!("Entry '{}' cannot be written as JECS: {}", error.path, error.description)))?;
+			if let Node::Object(children, dangling) | Node::Array(children, dangling) = &member.node {
+				write_members(output, path, depth + 1, children, options)?;
+				push_comment(output, depth + 1, dangling);
+			}
+			path.pop();
+		}
+		Ok(())
+	}
+	
+	fn push_comment(output: &mut String, depth: usize, lines: &[String]) {
+		for line in lines {
+			output.push_str(&" ".repeat(depth * INDENTATION_STEP));
+			output.push('#');
+			if !line.is_empty() {
+				output.push(' ');
+				output.push_str(line);
+			}
+			output.push('\n');
+		}
+	}
+}
+
+enum Node {
+	Value(String),
+	Null,
+	Object(Vec<Member>, Vec<String>), //With the comment lines after the last member
+	Array(Vec<Member>, Vec<String>),
+}
+
+struct Member {
+	key: Option<String>, //None for array entries
+	comment: Vec<String>,
+	row: usize,
+	node: Node,
+}
+
+struct Comment {
+	row: usize, //Of the end of the comment
+	lines: Vec<String>,
+}
+
+struct Parser<'a> {
+	text: &'a str,
+	position: usize,
+	row: usize,
+	comments: Vec<Comment>, //Comments which are not assigned to a member yet
+}
+
+impl<'a> Parser<'a> {
+	fn new(text: &'a str) -> Self {
+		Self {
+			text: text.strip_prefix('\u{feff}').unwrap_or(text),
+			position: 0,
+			row: 1,
+			comments: Vec::new(),
+		}
+	}
+	
+	//The comment lines before the root object, the root object and the comment lines after it.
+	fn parse_document(mut self) -> Result<(Vec<String>, Node, Vec<String>), JecsConvertError> {
+		self.skip_trivia()?;
+		let header = self.take_comments();
+		if self.peek() != Some('{') {
+			return Err(self.error("The root has to be an object"));
+		}
+		let root = self.parse_value()?;
+		self.skip_trivia()?;
+		if self.peek().is_some() {
+			return Err(self.error("Unexpected content after the root object"));
+		}
+		let footer = self.take_comments();
+		Ok((header, root, footer))
+	}
+	
+	fn parse_value(&mut self) -> Result<Node, JecsConvertError> {
+		match self.peek() {
+			None => Err(self.error("Unexpected end of the file, expected a value")),
+			Some('{') => self.parse_container('}'),
+			Some('[') => self.parse_container(']'),
+			Some(quote @ ('"' | '\'')) => Ok(Node::Value(self.parse_string(quote)?)),
+			Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => self.parse_number(),
+			Some(_) => {
+				let word = self.parse_identifier();
+				match word {
+					"null" => Ok(Node::Null),
+					"true" | "false" | "Infinity" | "NaN" => Ok(Node::Value(word.to_string())),
+					"" => Err(self.error(&format!("Unexpected character '{}'", self.peek().unwrap()))),
+					_ => Err(self.error(&format!("Unknown literal '{}'", word))),
+				}
+			}
+		}
+	}
+	
+	//Objects and arrays, members of arrays have no key.
+	fn parse_container(&mut self, end: char) -> Result<Node, JecsConvertError> {
+		self.advance(); //Opening bracket
+		let mut members = Vec::new();
+		loop {
+			self.skip_trivia()?;
+			if self.peek() == Some(end) {
+				break;
+			}
+			let row = self.row;
+			let key = if end == '}' {
+				let key = match self.peek() {
+					Some(quote @ ('"' | '\'')) => self.parse_string(quote)?,
+					Some(c) if c.is_alphabetic() || matches!(c, '_' | '$') => self.parse_identifier().to_string(),
+					_ => return Err(self.error("Expected a key or '}'")),
+				};
+				self.skip_trivia()?;
+				if self.peek() != Some(':') {
+					return Err(self.error("Expected ':' after the key"));
+				}
+				self.advance();
+				self.skip_trivia()?;
+				Some(key)
+			} else {
+				None
+			};
+			//Comments between the key and the value are kept with the comments above the member:
+			let mut comment = self.take_comments();
+			let node = self.parse_value()?;
+			let value_row = self.row;
+			self.skip_trivia()?;
+			let separated = self.peek() == Some(',');
+			if separated {
+				self.advance();
+				self.skip_trivia()?;
+			}
+			//Comments behind the value belong to this member, the following ones to the next member:
+			let (behind, below): (Vec<_>, Vec<_>) = core::mem::take(&mut self.comments).into_iter().partition(|comment| comment.row == value_row);
+			self.comments = below;
+			comment.extend(behind.into_iter().flat_map(|comment| comment.lines));
+			members.push(Member {
+				key,
+				comment,
+				row,
+				node,
+			});
+			if !separated && self.peek() != Some(end) {
+				return Err(self.error(&format!("Expected ',' or '{}'", end)));
+			}
+		}
+		self.advance(); //Closing bracket
+		let dangling = self.take_comments();
+		Ok(if end == '}' { Node::Object(members, dangling) } else { Node::Array(members, dangling) })
+	}
+	
+	fn parse_string(&mut self, quote: char) -> Result<String, JecsConvertError> {
+		self.advance();
+		let mut value = String::new();
+		loop {
+			let c = match self.peek() {
+				None => return Err(self.error("Unterminated string")),
+				Some('\n' | '\r') => return Err(self.error("Line break inside of a string, escape it as '\\n'")),
+				Some(c) => c,
+			};
+			self.advance();
+			match c {
+				_ if c == quote => return Ok(value),
+				'\\' => {
+					let escaped = match self.advance() {
+						None => return Err(self.error("Unterminated string")),
+						Some(escaped) => escaped,
+					};
+					match escaped {
+						'n' => value.push('\n'),
+						'r' => value.push('\r'),
+						't' => value.push('\t'),
+						'b' => value.push('\u{8}'),
+						'f' => value.push('\u{c}'),
+						'v' => value.push('\u{b}'),
+						'0' => value.push('\0'),
+						'x' => {
+							let code = self.parse_hex(2)?;
+							value.push(char::from_u32(code).unwrap()); //Two digits are always a valid char
+						}
+						'u' => {
+							let mut code = self.parse_hex(4)?;
+							//Characters outside of the basic plane are escaped as UTF-16 surrogate pair:
+							if (0xD800..0xDC00).contains(&code) && self.text[self.position..].starts_with("\\u") {
+								self.position += 2;
+								let low = self.parse_hex(4)?;
+								code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+							}
+							match char::from_u32(code) {
+								None => return Err(self.error("Escape sequence is not a valid character")),
+								Some(c) => value.push(c),
+							}
+						}
+						'\r' => {
+							//Line continuation, the line break is not part of the value:
+							if self.peek() == Some('\n') {
+								self.advance();
+							}
+						}
+						'\n' | '\u{2028}' | '\u{2029}' => {}
+						_ => value.push(escaped),
+					}
+				}
+				_ => value.push(c),
+			}
+		}
+	}
+	
+	fn parse_hex(&mut self, digits: usize) -> Result<u32, JecsConvertError> {
+		let hex = self.text[self.position..].get(..digits).filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()));
+		match hex {
+			None => Err(self.error(&format!("Expected {} hexadecimal digits", digits))),
+			Some(hex) => {
+				self.position += digits;
+				Ok(u32::from_str_radix(hex, 16).unwrap())
+			}
+		}
+	}
+	
+	//Numbers are kept as written, including JSON5 hexadecimal numbers and signs.
+	fn parse_number(&mut self) -> Result<Node, JecsConvertError> {
+		let start = self.position;
+		while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-')) {
+			self.advance();
+		}
+		let number = &self.text[start..self.position];
+		let unsigned = number.strip_prefix(['+', '-']).unwrap_or(number);
+		let hex = unsigned.strip_prefix("0x").or_else(|| unsigned.strip_prefix("0X"));
+		let valid = match hex {
+			Some(digits) => !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()),
+			None => matches!(unsigned, "Infinity" | "NaN") || (unsigned.starts_with(|c: char| c.is_ascii_digit() || c == '.') && unsigned.parse::<f64>().is_ok()),
+		};
+		if !valid {
+			return Err(self.error(&format!("Invalid number '{}'", number)));
+		}
+		Ok(Node::Value(number.to_string()))
+	}
+	
+	fn parse_identifier(&mut self) -> &'a str {
+		let start = self.position;
+		while self.peek().is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '$')) {
+			self.advance();
+		}
+		&self.text[start..self.position]
+	}
+	
+	//Skips whitespace and collects the comments on the way.
+	fn skip_trivia(&mut self) -> Result<(), JecsConvertError> {
+		loop {
+			match self.peek() {
+				Some(c) if c.is_whitespace() => {
+					self.advance();
+				}
+				Some('/') if self.text[self.position..].starts_with("//") => {
+					let rest = &self.text[self.position + 2..];
+					let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+					self.position += 2 + line.len();
+					self.comments.push(Comment {
+						row: self.row,
+						lines: vec![comment_line(line.trim_end())],
+					});
+				}
+				Some('/') if self.text[self.position..].starts_with("/*") => {
+					let rest = &self.text[self.position + 2..];
+					let content = match rest.find("*/") {
+						None => return Err(self.error("Unterminated block comment")),
+						Some(end) => &rest[..end],
+					};
+					self.position += 2 + content.len() + 2;
+					self.row += content.matches('\n').count();
+					//Decorations of multi-line block comments like ' * ' at the start of lines are removed:
+					let mut lines: Vec<String> = content.lines().map(|line| {
+						let line = line.trim();
+						let line = line.strip_prefix('*').map_or(line, |line| line.strip_prefix(' ').unwrap_or(line));
+						line.to_string()
+					}).collect();
+					while lines.last().is_some_and(|line| line.is_empty()) {
+						lines.pop();
+					}
+					let first_content = lines.iter().position(|line| !line.is_empty()).unwrap_or(lines.len());
+					lines.drain(..first_content);
+					self.comments.push(Comment {
+						row: self.row,
+						lines,
+					});
+				}
+				_ => return Ok(()),
+			}
+		}
+		
+		fn comment_line(line: &str) -> String {
+			line.strip_prefix(' ').unwrap_or(line).to_string()
+		}
+	}
+	
+	fn take_comments(&mut self) -> Vec<String> {
+		self.comments.drain(..).flat_map(|comment| comment.lines).collect()
+	}
+	
+	fn peek(&self) -> Option<char> {
+		self.text[self.position..].chars().next()
+	}
+	
+	fn advance(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.position += c.len_utf8();
+		if c == '\n' {
+			self.row += 1;
+		}
+		Some(c)
+	}
+	
+	fn error(&self, description: &str) -> JecsConvertError {
+		convert_error(self.row, description)
+	}
+}
+
+fn convert_error(row: usize, description: &str) -> JecsConvertError {
+	JecsConvertError {
+		format: "JSON5",
+		row,
+		description: description.to_string(),
+	}
+}
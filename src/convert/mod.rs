@@ -0,0 +1,16 @@
+//Conversion between JECS trees and other formats. JECS has no data types, thus values of other formats are read as their text.
+//Writing has to pick one representation for values, which is a string in every format.
+
+mod ini;
+mod json5;
+#[cfg(feature = "ron")]
+mod ron;
+#[cfg(feature = "xml")]
+mod xml;
+
+pub use self::ini::{from_ini_str, to_ini_string, to_ini_string_redacted};
+pub use self::json5::{from_json5_document, from_json5_str};
+#[cfg(feature = "ron")]
+pub use self::ron::{from_ron_str, to_ron_string, to_ron_string_redacted};
+#[cfg(feature = "xml")]
+pub use self::xml::{from_xml_str, to_xml_string, to_xml_string_redacted};
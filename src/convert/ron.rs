@@ -0,0 +1,25 @@
+use ::ron::ser::PrettyConfig;
+
+use crate::errors::JecsSerdeError;
+use crate::redact::JecsRedaction;
+use crate::types::JecsType;
+
+//Any entries become '()', values are always written as strings.
+pub fn to_ron_string(tree: &JecsType) -> Result<String, JecsSerdeError> {
+	::ron::ser::to_string_pretty(tree, PrettyConfig::default()).map_err(|error| JecsSerdeError {
+		description: error.to_string(),
+	})
+}
+
+//Same as to_ron_string(), but replaces sensitive values, so that the output can be shared.
+pub fn to_ron_string_redacted(tree: &JecsType, redaction: &JecsRedaction) -> Result<String, JecsSerdeError> {
+	to_ron_string(&redaction.redacted(tree))
+}
+
+//Numbers, booleans and chars become values with their text, '()' and 'None' become Any entries.
+//Structs are read as maps, map keys have to be strings.
+pub fn from_ron_str(text: &str) -> Result<JecsType, JecsSerdeError> {
+	::ron::from_str(text).map_err(|error| JecsSerdeError {
+		description: error.to_string(),
+	})
+}
@@ -0,0 +1,195 @@
+use ::xml::common::Position;
+use ::xml::reader::{ParserConfig, XmlEvent};
+
+use crate::errors::{JecsConvertError, JecsWriteError};
+use crate::path::JecsPath;
+use crate::redact::JecsRedaction;
+use crate::types::{JecsMap, JecsType};
+use crate::writer::write_error;
+
+const ROOT_ELEMENT: &str = "jecs";
+const LIST_ENTRY_ELEMENT: &str = "item";
+
+//Keys become elements and values their text, list entries become repeated 'item' elements:
+//<jecs>
+//	<Name>Wire</Name>
+//	<Inputs>
+//		<item>0</item>
+//		<item>1</item>
+//	</Inputs>
+//	<Settings/>
+//</jecs>
+//Any entries and empty maps or lists become empty elements. Map keys are written in alphabetical order.
+//Empty values and maps with only the key 'item' are rejected, as they would be read back as Any entry and list.
+pub fn to_xml_string(tree: &JecsType) -> Result<String, JecsWriteError> {
+	let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+	write_element(&mut output, &mut JecsPath::root(), 0, ROOT_ELEMENT, tree)?;
+	return Ok(output);
+	
+	fn write_element(output: &mut String, path: &mut JecsPath, depth: usize, name: &str, entry: &JecsType) -> Result<(), JecsWriteError> {
+		if !is_element_name(name) {
+			return Err(write_error(path, "Key is not a valid XML element name"));
+		}
+		push_indentation(output, depth);
+		match entry {
+			JecsType::Value(value) => {
+				//The reader ignores whitespace between elements:
+				if value.trim().is_empty() {
+					return Err(write_error(path, "Empty values would be read as Any entry"));
+				}
+				if value.chars().any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r')) {
+					return Err(write_error(path, "Value contains control characters, which XML cannot represent"));
+				}
+				output.push_str(&format!("<{}>", name));
+				push_escaped_text(output, value);
+				output.push_str(&format!("</{}>\n", name));
+			}
+			JecsType::Map(map) if !map.is_empty() => {
+				if map.len() == 1 && map.contains_key(LIST_ENTRY_ELEMENT) {
+					return Err(write_error(path, "Map with only the key 'item' would be read as list"));
+				}
+				output.push_str(&format!("<{}>\n", name));
+				let mut keys: Vec<_> = map.keys().collect();
+				keys.sort();
+				for key in keys {
+					path.push_key(key);
+					write_element(output, path, depth + 1, key, &map[key])?;
+					path.pop();
+				}
+				push_indentation(output, depth);
+				output.push_str(&format!("</{}>\n", name));
+			}
+			JecsType::List(list) if !list.is_empty() => {
+				output.push_str(&format!("<{}>\n", name));
+				for (index, child) in list.iter().enumerate() {
+					path.push_index(index);
+					write_element(output, path, depth + 1, LIST_ENTRY_ELEMENT, child)?;
+					path.pop();
+				}
+				push_indentation(output, depth);
+				output.push_str(&format!("</{}>\n", name));
+			}
+			_ => output.push_str(&format!("<{}/>\n", name)),
+		}
+		Ok(())
+	}
+	
+	fn push_indentation(output: &mut String, depth: usize) {
+		for _ in 0..depth {
+			output.push('\t');
+		}
+	}
+	
+	fn push_escaped_text(output: &mut String, text: &str) {
+		for c in text.chars() {
+			match c {
+				'&' => output.push_str("&amp;"),
+				'<' => output.push_str("&lt;"),
+				'>' => output.push_str("&gt;"),
+				'\r' => output.push_str("&#13;"), //Parsers turn raw '\r\n' into '\n'
+				_ => output.push(c),
+			}
+		}
+	}
+}
+
+//Same as to_xml_string(), but replaces sensitive values, so that the output can be shared.
+pub fn to_xml_string_redacted(tree: &JecsType, redaction: &JecsRedaction) -> Result<String, JecsWriteError> {
+	to_xml_string(&redaction.redacted(tree))
+}
+
+//Reads the mapping of 'to_xml_string', the name of the root element does not matter.
+//Elements with text become values, empty ones Any entries and elements with only 'item' children lists.
+//Other elements become maps, with their attributes as values. Repeated child elements become a list under their name.
+//Whitespace between elements, comments and namespaces are ignored.
+pub fn from_xml_str(text: &str) -> Result<JecsType, JecsConvertError> {
+	let mut reader = ParserConfig::new()
+		.cdata_to_characters(true)
+		.ignore_comments(true)
+		.create_reader(text.as_bytes());
+	//Elements which are not closed yet, the root element is first:
+	let mut open: Vec<Element> = Vec::new();
+	loop {
+		let event = reader.next().map_err(|error| convert_error(error.position().row as usize + 1, error.msg()))?;
+		match event {
+			XmlEvent::StartElement { name, attributes, .. } => open.push(Element {
+				name: name.local_name,
+				row: reader.position().row as usize + 1,
+				attributes: attributes.into_iter().map(|attribute| (attribute.name.local_name, attribute.value)).collect(),
+				children: Vec::new(),
+				text: String::new(),
+			}),
+			XmlEvent::Characters(characters) => {
+				//Text outside the root element is rejected by the reader.
+				open.last_mut().unwrap().text.push_str(&characters);
+			}
+			XmlEvent::EndElement { .. } => {
+				let element = open.pop().unwrap();
+				let name = element.name.clone();
+				let entry = element.into_entry()?;
+				match open.last_mut() {
+					Some(parent) => parent.children.push((name, entry)),
+					None => return Ok(entry),
+				}
+			}
+			XmlEvent::EndDocument => return Err(convert_error(0, "Document has no root element")),
+			_ => {}
+		}
+	}
+}
+
+struct Element {
+	name: String,
+	row: usize,
+	attributes: Vec<(String, String)>,
+	children: Vec<(String, JecsType)>,
+	text: String,
+}
+
+impl Element {
+	fn into_entry(self) -> Result<JecsType, JecsConvertError> {
+		let has_text = !self.text.trim().is_empty();
+		if self.attributes.is_empty() && self.children.is_empty() {
+			return Ok(if has_text { JecsType::value(self.text) } else { JecsType::Any() });
+		}
+		if has_text {
+			return Err(convert_error(self.row, &format!("Element '{}' has text next to attributes or child elements", self.name)));
+		}
+		if self.attributes.is_empty() && self.children.iter().all(|(name, _)| name == LIST_ENTRY_ELEMENT) {
+			return Ok(JecsType::List(self.children.into_iter().map(|(_, entry)| entry).collect()));
+		}
+		let mut map = JecsMap::default();
+		let mut lists = Vec::new(); //Keys which already got turned into lists
+		let attributes = self.attributes.into_iter().map(|(name, value)| (name, JecsType::value(value)));
+		for (name, entry) in attributes.chain(self.children) {
+			match map.get_mut(&name) {
+				Some(JecsType::List(list)) if lists.contains(&name) => list.push(entry),
+				Some(existing) => {
+					let first = core::mem::replace(existing, JecsType::Any());
+					*existing = JecsType::List(vec![first, entry]);
+					lists.push(name);
+				}
+				None => {
+					map.insert(name, entry);
+				}
+			}
+		}
+		Ok(JecsType::Map(map))
+	}
+}
+
+//XML 1.0 names, limited to ASCII and without ':' as that separates namespaces.
+fn is_element_name(name: &str) -> bool {
+	let mut chars = name.chars();
+	chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+		&& !name.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("xml"))
+}
+
+fn convert_error(row: usize, description: &str) -> JecsConvertError {
+	JecsConvertError {
+		format: "XML",
+		row,
+		description: description.to_string(),
+	}
+}
@@ -0,0 +1,354 @@
+use std::boxed::Box;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::from_utf8;
+use std::string::String;
+use std::time::{Duration, Instant};
+
+use crate::decode::FromJecs;
+use crate::document::{replace_file, strip_bom, JecsDocument};
+use crate::encode::IntoJecs;
+use crate::errors::{JecsCorruptedDataError, JecsDecodeError, JecsLoadError, JecsSaveError, JecsWriteError};
+use crate::merge::JecsMergeOptions;
+use crate::parser::parse_jecs_string;
+use crate::path::{JecsPath, JecsPathSegment};
+use crate::types::{JecsMap, JecsType};
+use crate::writer::{write_error, KeyOrder, WriterOptions};
+
+//A JECS file to read and write values by path, like the DataFile of the C# SUCC library.
+//Values missing in the file are read from the defaults, if there are any. Changes stay in memory until save() is called, or auto-save writes them.
+//Saving only changes the lines of changed entries, comments and formatting written by users stay as they are.
+//With the 'file_lock' feature, reading and writing the file is guarded by a lock, which other processes using DataFile respect.
+pub struct DataFile {
+	data: MemoryDataFile,
+	path: PathBuf,
+	auto_save: Option<Duration>,
+	has_unsaved_changes: bool,
+	last_save: Option<Instant>,
+}
+
+impl DataFile {
+	//A missing file is created empty.
+	pub fn open(path: impl Into<PathBuf>) -> Result<Self, JecsLoadError> {
+		Self::open_with_defaults(path, "")
+	}
+	
+	//The defaults are JECS text, usually included into the program with include_str!().
+	//A missing file is created with exactly that text, thus the comments of the defaults end up in the file too.
+	pub fn open_with_defaults(path: impl Into<PathBuf>, defaults: &str) -> Result<Self, JecsLoadError> {
+		let defaults = strip_bom(defaults);
+		let mut data_file = Self {
+			data: MemoryDataFile::parse_with_defaults("", defaults)?,
+			path: path.into(),
+			auto_save: None,
+			has_unsaved_changes: false,
+			last_save: None,
+		};
+		data_file.load_or_create(defaults)?;
+		Ok(data_file)
+	}
+	
+	//For the first start of a program: A missing file is created with all values of T::default() and their comments (see IntoJecs::jecs_comments()).
+	//These values are the defaults for entries missing in the file too.
+	pub fn open_or_create_with_defaults<T: Default + IntoJecs>(path: impl Into<PathBuf>) -> Result<Self, Box<dyn Error>> {
+		let defaults = default_file_text(&T::default(), &T::jecs_comments())?;
+		Ok(Self::open_with_defaults(path, &defaults)?)
+	}
+	
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+	
+	//The content of the file as it currently is in memory.
+	pub fn data(&self) -> &MemoryDataFile {
+		&self.data
+	}
+	
+	pub fn root(&self) -> &JecsMap {
+		self.data.root()
+	}
+	
+	pub fn contains(&self, path: impl Into<JecsPath>) -> bool {
+		self.data.contains(path)
+	}
+	
+	pub fn get<T: FromJecs>(&self, path: impl Into<JecsPath>) -> Result<T, JecsDecodeError> {
+		self.data.get(path)
+	}
+	
+	pub fn get_or<T: FromJecs>(&self, path: impl Into<JecsPath>, default: T) -> Result<T, JecsDecodeError> {
+		self.data.get_or(path, default)
+	}
+	
+	pub fn get_struct<T: FromJecs>(&self) -> Result<T, JecsDecodeError> {
+		self.data.get_struct()
+	}
+	
+	//Like the AutoSave of SUCC: Every change gets written right away, but at most once per interval.
+	//Changes within the interval after a save are written by the next change after it, by flush() or when the DataFile is dropped.
+	//None turns it off, Some(Duration::ZERO) writes on every change.
+	pub fn set_auto_save(&mut self, interval: Option<Duration>) {
+		self.auto_save = interval;
+	}
+	
+	pub fn has_unsaved_changes(&self) -> bool {
+		self.has_unsaved_changes
+	}
+	
+	//See MemoryDataFile::set(). Errors of auto-saving are returned too, the change is kept in that case.
+	pub fn set<T: IntoJecs + ?Sized>(&mut self, path: impl Into<JecsPath>, value: &T) -> Result<(), JecsSaveError> {
+		self.data.set(path, value)?;
+		self.changed()
+	}
+	
+	//See MemoryDataFile::set_struct(). Errors of auto-saving are returned too, the change is kept in that case.
+	pub fn set_struct<T: IntoJecs>(&mut self, value: &T) -> Result<(), JecsSaveError> {
+		self.data.set_struct(value)?;
+		self.changed()
+	}
+	
+	//Errors of auto-saving are returned instead of the removed entry, which is gone in any case.
+	pub fn remove(&mut self, path: impl Into<JecsPath>) -> Result<Option<JecsType>, JecsSaveError> {
+		let removed = self.data.remove(path);
+		if removed.is_some() {
+			self.changed()?;
+		}
+		Ok(removed)
+	}
+	
+	//The file is replaced in one go.
+	pub fn save(&mut self) -> Result<(), JecsSaveError> {
+		let document = self.data.synced_document()?;
+		#[cfg(feature = "file_lock")]
+		let _lock = lock_file(&self.path, true)?;
+		replace_file(&self.path, document.as_str())?;
+		self.data.document = document;
+		self.has_unsaved_changes = false;
+		self.last_save = Some(Instant::now());
+		Ok(())
+	}
+	
+	//Saves, if there are unsaved changes.
+	pub fn flush(&mut self) -> Result<(), JecsSaveError> {
+		if self.has_unsaved_changes {
+			self.save()?;
+		}
+		Ok(())
+	}
+	
+	//Discards all changes that were not saved. On error the previous values are kept.
+	pub fn reload(&mut self) -> Result<(), JecsLoadError> {
+		#[cfg(feature = "file_lock")]
+		let lock = lock_file(&self.path, false)?;
+		let bytes = fs::read(&self.path)?;
+		#[cfg(feature = "file_lock")]
+		drop(lock);
+		let document = JecsDocument::parse(from_utf8(&bytes)?)?;
+		self.data.tree = JecsType::Map(document.to_jecs_map());
+		self.data.document = document;
+		self.has_unsaved_changes = false;
+		Ok(())
+	}
+	
+	fn changed(&mut self) -> Result<(), JecsSaveError> {
+		self.has_unsaved_changes = true;
+		let interval = match self.auto_save {
+			None => return Ok(()),
+			Some(interval) => interval,
+		};
+		match self.last_save {
+			Some(last_save) if last_save.elapsed() < interval => Ok(()),
+			_ => self.save(),
+		}
+	}
+	
+	fn load_or_create(&mut self, defaults: &str) -> Result<(), JecsLoadError> {
+		match self.reload() {
+			Err(JecsLoadError::Io(error)) if error.kind() == ErrorKind::NotFound => {
+				#[cfg(feature = "file_lock")]
+				let _lock = lock_file(&self.path, true)?;
+				replace_file(&self.path, defaults)?;
+				self.data.document = JecsDocument::parse(defaults)?;
+				self.data.tree = self.data.defaults.clone();
+				Ok(())
+			}
+			result => result,
+		}
+	}
+}
+
+//Writes pending changes of auto-save. Errors cannot be reported here, call flush() before to get them.
+impl Drop for DataFile {
+	fn drop(&mut self) {
+		if self.auto_save.is_some() {
+			let _ = self.flush();
+		}
+	}
+}
+
+//The same as a DataFile, but without a file. For tests and documents that only exist in memory.
+#[derive(Clone, Default)]
+#[derive(Debug)]
+pub struct MemoryDataFile {
+	document: JecsDocument, //The text as parsed or last saved
+	tree: JecsType,
+	defaults: JecsType,
+}
+
+impl MemoryDataFile {
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	pub fn parse(text: &str) -> Result<Self, JecsCorruptedDataError> {
+		Self::parse_with_defaults(text, "")
+	}
+	
+	//Entries missing in the text are read from the defaults.
+	pub fn parse_with_defaults(text: &str, defaults: &str) -> Result<Self, JecsCorruptedDataError> {
+		let document = JecsDocument::parse(text)?;
+		Ok(Self {
+			tree: JecsType::Map(document.to_jecs_map()),
+			document,
+			defaults: JecsType::Map(parse_jecs_string(strip_bom(defaults))?),
+		})
+	}
+	
+	//The values of the file, without the defaults.
+	pub fn root(&self) -> &JecsMap {
+		self.tree.get_map().unwrap()
+	}
+	
+	//Only looks at the file, not at the defaults.
+	pub fn contains(&self, path: impl Into<JecsPath>) -> bool {
+		self.tree.get_path(path).is_some()
+	}
+	
+	//Reads the entry from the file, or if missing there from the defaults.
+	pub fn get<T: FromJecs>(&self, path: impl Into<JecsPath>) -> Result<T, JecsDecodeError> {
+		let path = path.into();
+		if self.tree.get_path(&path).is_none() && self.defaults.get_path(&path).is_some() {
+			return self.defaults.expect_at(path, T::from_jecs);
+		}
+		self.tree.read_path(path)
+	}
+	
+	//Same as get(), but entries missing in the file and the defaults are the given value.
+	pub fn get_or<T: FromJecs>(&self, path: impl Into<JecsPath>, default: T) -> Result<T, JecsDecodeError> {
+		let path = path.into();
+		if self.tree.get_path(&path).is_none() && self.defaults.get_path(&path).is_none() {
+			return Ok(default);
+		}
+		self.get(path)
+	}
+	
+	//Decodes the whole file, entries missing in it are taken from the defaults.
+	pub fn get_struct<T: FromJecs>(&self) -> Result<T, JecsDecodeError> {
+		let mut tree = self.defaults.clone();
+		tree.merge(self.tree.clone(), &JecsMergeOptions::default());
+		T::from_jecs(&tree)
+	}
+	
+	//Replaces the entries the value consists of. Maps are updated key by key, thus keys unknown to the type stay in the file.
+	pub fn set_struct<T: IntoJecs>(&mut self, value: &T) -> Result<(), JecsWriteError> {
+		let tree = value.to_jecs();
+		if !tree.is_strict_map() {
+			return Err(write_error(&JecsPath::root(), "Value has to be a map"));
+		}
+		self.tree.merge(tree, &JecsMergeOptions::default());
+		Ok(())
+	}
+	
+	//Replaces the entry or adds it, missing maps on the way are added too. Lists can only be extended by one entry at their end.
+	pub fn set<T: IntoJecs + ?Sized>(&mut self, path: impl Into<JecsPath>, value: &T) -> Result<(), JecsWriteError> {
+		let path = path.into();
+		let (last, parents) = match path.segments().split_last() {
+			None => return Err(write_error(&path, "The root cannot be replaced")),
+			Some(segments) => segments,
+		};
+		let mut entry = &mut self.tree;
+		for segment in parents {
+			entry = match child_or_insert(entry, segment, JecsType::new_map) {
+				None => return Err(write_error(&path, "Parent entry cannot have this child")),
+				Some(child) => child,
+			};
+		}
+		match child_or_insert(entry, last, JecsType::Any) {
+			None => return Err(write_error(&path, "Parent entry cannot have this child")),
+			Some(child) => *child = value.to_jecs(),
+		}
+		Ok(())
+	}
+	
+	pub fn remove(&mut self, path: impl Into<JecsPath>) -> Option<JecsType> {
+		self.tree.take_path(path)
+	}
+	
+	//The text a DataFile would save: The parsed text with the changes applied, new entries in alphabetical order.
+	pub fn to_jecs_string(&self) -> Result<String, JecsWriteError> {
+		Ok(self.synced_document()?.as_str().into())
+	}
+	
+	fn synced_document(&self) -> Result<JecsDocument, JecsWriteError> {
+		let options = WriterOptions {
+			key_order: KeyOrder::Alphabetical,
+			..WriterOptions::default()
+		};
+		let mut document = self.document.clone();
+		document.sync_with(self.root(), &options)?;
+		Ok(document)
+	}
+}
+
+fn default_file_text<T: IntoJecs>(defaults: &T, comments: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+	let tree = defaults.to_jecs();
+	if !tree.is_strict_map() {
+		return Err(write_error(&JecsPath::root(), "Defaults have to be a map").into());
+	}
+	let data = MemoryDataFile {
+		tree,
+		..MemoryDataFile::default()
+	};
+	let mut document = JecsDocument::parse(&data.to_jecs_string()?)?;
+	for (path, comment) in comments {
+		document.set_comment(*path, comment)?;
+	}
+	Ok(document.as_str().into())
+}
+
+//Locks '<file>.lock' instead of the file itself, as saving replaces the file. The lock file is kept, removing it could break the lock of another process.
+//Shared locks for reading, an exclusive lock for writing. The lock is released when the returned file is dropped.
+#[cfg(feature = "file_lock")]
+fn lock_file(path: &Path, exclusive: bool) -> std::io::Result<fs::File> {
+	let mut lock_path = path.as_os_str().to_owned();
+	lock_path.push(".lock");
+	let file = fs::OpenOptions::new().write(true).create(true).truncate(false).open(lock_path)?;
+	if exclusive {
+		fs2::FileExt::lock_exclusive(&file)?;
+	} else {
+		fs2::FileExt::lock_shared(&file)?;
+	}
+	Ok(file)
+}
+
+//Entries without value become maps, if a key is added to them.
+fn child_or_insert<'a>(entry: &'a mut JecsType, segment: &JecsPathSegment, new_entry: fn() -> JecsType) -> Option<&'a mut JecsType> {
+	if entry.is_any() && matches!(segment, JecsPathSegment::Key(_)) {
+		*entry = JecsType::new_map();
+	}
+	match (entry, segment) {
+		(JecsType::Map(map), JecsPathSegment::Key(key)) => Some(map.entry(key.clone()).or_insert_with(new_entry)),
+		(JecsType::List(list), JecsPathSegment::Index(index)) => list_entry_or_push(list, *index, new_entry),
+		(JecsType::List(list), JecsPathSegment::Key(key)) => list_entry_or_push(list, key.parse().ok()?, new_entry),
+		_ => None,
+	}
+}
+
+fn list_entry_or_push(list: &mut Vec<JecsType>, index: usize, new_entry: fn() -> JecsType) -> Option<&mut JecsType> {
+	if index == list.len() {
+		list.push(new_entry());
+	}
+	list.get_mut(index)
+}
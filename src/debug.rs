@@ -1,47 +1,77 @@
+use std::fmt::Write;
+
 use ecc_ansi_lib::ansi;
 
+use crate::redact::JecsRedaction;
 use crate::types::JecsType;
 
 pub fn debug_print(entry: &JecsType) {
-	print_inner(entry,
+	print!("{}", debug_string(entry, None));
+}
+
+//Same as debug_print(), but replaces sensitive values, so that the output can be shared.
+pub fn debug_print_redacted(entry: &JecsType, redaction: &JecsRedaction) {
+	debug_print(&redaction.redacted(entry));
+}
+
+//The text printed by debug_print(), including the color codes. Maps and lists deeper than 'max_depth' are collapsed into one line with their child count.
+pub fn debug_string(entry: &JecsType, max_depth: Option<usize>) -> String {
+	let mut output = String::new();
+	print_inner(&mut output, entry,
 		ansi!("«gr»└ ").to_owned(),
 		ansi!("«gr»  ").to_owned(),
+		max_depth,
 	);
+	output
 }
 
-fn print_inner(entry: &JecsType, entry_prefix: String, prefix: String) {
+fn print_inner(output: &mut String, entry: &JecsType, entry_prefix: String, prefix: String, remaining_depth: Option<usize>) {
+	let collapsed = remaining_depth == Some(0);
+	let remaining_depth = remaining_depth.map(|depth| depth.saturating_sub(1));
+	//Writing into a String does not fail.
 	match entry {
 		JecsType::Any() => {
-			println!(ansi!("«y»{}«r»{}«»"), entry_prefix, "---");
+			writeln!(output, ansi!("«y»{}«r»{}«»"), entry_prefix, "---").unwrap();
+		}
+		JecsType::Null() => {
+			writeln!(output, ansi!("«y»{}«r»{}«»"), entry_prefix, "null").unwrap();
 		}
 		JecsType::Value(value) => {
-			println!(ansi!("{}'«w»{}«gr»'«»"), entry_prefix, value);
+			writeln!(output, ansi!("{}'«w»{}«gr»'«»"), entry_prefix, value).unwrap();
+		}
+		JecsType::Map(map) if collapsed && !map.is_empty() => {
+			writeln!(output, ansi!("{}<map> «y»({} entries)«»"), entry_prefix, map.len()).unwrap();
 		}
 		JecsType::Map(map) => {
-			println!(ansi!("{}<map>«»"), entry_prefix);
+			writeln!(output, ansi!("{}<map>«»"), entry_prefix).unwrap();
 			for (index, (key, value)) in map.iter().enumerate() {
-				print_inner(value,
+				print_inner(output, value,
 					format!(ansi!("{}{} «w»{}«gr»: "),
 						prefix, if index == (map.len() - 1) { '└' } else { '├' }, key
 					),
 					format!("{}{} ",
 						prefix, if index == (map.len() - 1) { ' ' } else { '│' }
 					),
+					remaining_depth,
 				);
 			}
 		}
+		JecsType::List(list) if collapsed && !list.is_empty() => {
+			writeln!(output, ansi!("«y»{}<list> ({} entries)«»"), entry_prefix, list.len()).unwrap();
+		}
 		JecsType::List(list) => {
-			println!(ansi!("«y»{}<list>«»"), entry_prefix);
+			writeln!(output, ansi!("«y»{}<list>«»"), entry_prefix).unwrap();
 			for (index, value) in list.iter().enumerate() {
-				print_inner(value,
+				print_inner(output, value,
 					format!("{}{} ",
 						prefix, if index == (list.len() - 1) { '└' } else { '├' }
 					),
 					format!("{}{} ",
 						prefix, if index == (list.len() - 1) { ' ' } else { '│' }
 					),
+					remaining_depth,
 				);
 			}
 		}
 	}
-}
\ No newline at end of file
+}
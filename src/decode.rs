@@ -0,0 +1,271 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+
+use crate::errors::{JecsDecodeError, JecsWrongLengthError};
+use crate::path::JecsPath;
+use crate::types::{integer_error, malformed, HashMap, JecsHasher, JecsType};
+
+//Types which can be read from an entry of a JECS tree.
+pub trait FromJecs: Sized {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError>;
+	
+	//Value to use when the entry does not exist at all, by default a missing entry is an error.
+	fn from_missing() -> Option<Self> {
+		None
+	}
+}
+
+impl FromJecs for JecsType {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		Ok(entry.clone())
+	}
+}
+
+impl FromJecs for String {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		entry.expect_string().map(str::to_string)
+	}
+}
+
+impl FromJecs for bool {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		entry.expect_bool()
+	}
+}
+
+impl FromJecs for f64 {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		entry.expect_double()
+	}
+}
+
+impl FromJecs for f32 {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		let value = entry.expect_string()?;
+		value.parse().map_err(|_| malformed("f32", value))
+	}
+}
+
+macro_rules! integer_from_jecs {
+	($($type:ty),*) => {
+		$(
+			impl FromJecs for $type {
+				fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+					let value = entry.expect_string()?;
+					value.parse().map_err(|error| integer_error(stringify!($type), value, &error))
+				}
+			}
+		)*
+	};
+}
+
+integer_from_jecs!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+//Any, Null and missing entries are None, everything else has to be a valid T.
+impl<T: FromJecs> FromJecs for Option<T> {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		match entry {
+			JecsType::Any() | JecsType::Null() => Ok(None),
+			_ => T::from_jecs(entry).map(Some),
+		}
+	}
+	
+	fn from_missing() -> Option<Self> {
+		Some(None)
+	}
+}
+
+//Tells missing entries apart from Null ones, which Option<T> treats the same.
+//Null entries are written as 'key: null', with WriterOptions::write_null and ParserOptions::read_null. Any entries ('key:') are
+// no Null entry, but an empty value, map or list, which T has to accept.
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub enum JecsNullable<T> {
+	Missing,
+	Null,
+	Present(T),
+}
+
+impl<T> JecsNullable<T> {
+	pub fn is_missing(&self) -> bool {
+		matches!(self, JecsNullable::Missing)
+	}
+	
+	pub fn is_null(&self) -> bool {
+		matches!(self, JecsNullable::Null)
+	}
+	
+	//Missing and null entries are both None.
+	pub fn into_option(self) -> Option<T> {
+		match self {
+			JecsNullable::Present(value) => Some(value),
+			JecsNullable::Missing | JecsNullable::Null => None,
+		}
+	}
+}
+
+impl<T: FromJecs> FromJecs for JecsNullable<T> {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		match entry {
+			JecsType::Null() => Ok(JecsNullable::Null),
+			_ => T::from_jecs(entry).map(JecsNullable::Present),
+		}
+	}
+	
+	fn from_missing() -> Option<Self> {
+		Some(JecsNullable::Missing)
+	}
+}
+
+//Empty lists are Any entries, as they are written as key without value.
+impl<T: FromJecs> FromJecs for Vec<T> {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		if entry.is_any() {
+			return Ok(Vec::new());
+		}
+		entry.expect_list()?.iter().enumerate().map(|(index, child)| {
+			T::from_jecs(child).map_err(|error| error.at(JecsPath::root().join_index(index)))
+		}).collect()
+	}
+}
+
+impl<T: FromJecs, S: BuildHasher + Default> FromJecs for HashMap<String, T, S> {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		decode_entries(entry)
+	}
+}
+
+impl<T: FromJecs> FromJecs for BTreeMap<String, T> {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		decode_entries(entry)
+	}
+}
+
+//Empty sections are Any entries, as they are written as key without value.
+fn decode_entries<T: FromJecs, C: FromIterator<(String, T)>>(entry: &JecsType) -> Result<C, JecsDecodeError> {
+	if entry.is_any() {
+		return Ok(core::iter::empty().collect());
+	}
+	entry.expect_map()?.iter().map(|(key, child)| {
+		T::from_jecs(child).map(|value| (key.clone(), value)).map_err(|error| error.at(JecsPath::root().join_key(key)))
+	}).collect()
+}
+
+//Functions to read homogeneous maps and lists, like a section with one entry per component. Any entries are empty.
+impl JecsType {
+	pub fn decode_map<T: FromJecs>(&self) -> Result<HashMap<String, T, JecsHasher>, JecsDecodeError> {
+		HashMap::from_jecs(self)
+	}
+	
+	pub fn decode_list<T: FromJecs>(&self) -> Result<Vec<T>, JecsDecodeError> {
+		Vec::from_jecs(self)
+	}
+}
+
+//Tuples are read from lists with exactly one entry per element, like coordinates '- 1', '- 2', '- 3'.
+//The accessors name the types at the call: entry.expect_tuple3::<f32, f32, f32>()
+macro_rules! tuple_from_jecs {
+	($($function:ident, $length:literal: $($type:ident $index:tt),+;)*) => {
+		$(
+			impl<$($type: FromJecs),+> FromJecs for ($($type,)+) {
+				fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+					let list = entry.expect_list()?;
+					if list.len() != $length {
+						return Err(JecsWrongLengthError {
+							expected_length: $length,
+							encountered_length: list.len(),
+							path: None,
+						}.into());
+					}
+					Ok(($(
+						$type::from_jecs(&list[$index]).map_err(|error| error.at(JecsPath::root().join_index($index)))?,
+					)+))
+				}
+			}
+			
+			impl JecsType {
+				pub fn $function<$($type: FromJecs),+>(&self) -> Result<($($type,)+), JecsDecodeError> {
+					<($($type,)+)>::from_jecs(self)
+				}
+			}
+		)*
+	};
+}
+
+tuple_from_jecs! {
+	expect_tuple2, 2: A 0, B 1;
+	expect_tuple3, 3: A 0, B 1, C 2;
+	expect_tuple4, 4: A 0, B 1, C 2, D 3;
+}
+
+//A path together with the type of the entry it points to. Create them with the jecs_keys! macro:
+// jecs_keys! {
+// 	pub PRIORITY: u32 = "MainInfo.Priority";
+// 	AUTHOR: Option<String> = "MainInfo.Author";
+// }
+// let priority = tree.read(PRIORITY)?;
+//Typed keys are read with read() instead of get(), as get() already returns the untyped child of a key or index.
+pub struct JecsKey<T> {
+	path: &'static str,
+	data_type: PhantomData<fn() -> T>,
+}
+
+impl<T> JecsKey<T> {
+	pub const fn new(path: &'static str) -> Self {
+		Self {
+			path,
+			data_type: PhantomData,
+		}
+	}
+	
+	pub fn as_str(&self) -> &'static str {
+		self.path
+	}
+	
+	pub fn path(&self) -> JecsPath {
+		JecsPath::parse(self.path)
+	}
+}
+
+//Deriving would require T to implement these too:
+impl<T> Clone for JecsKey<T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for JecsKey<T> {}
+
+impl<T> core::fmt::Debug for JecsKey<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "JecsKey({})", self.path)
+	}
+}
+
+impl JecsType {
+	//Reads and converts the entry a typed key points to.
+	pub fn read<T: FromJecs>(&self, key: JecsKey<T>) -> Result<T, JecsDecodeError> {
+		self.read_path(key.path())
+	}
+	
+	pub(crate) fn read_path<T: FromJecs>(&self, path: JecsPath) -> Result<T, JecsDecodeError> {
+		if let Some(default) = T::from_missing() {
+			if self.get_path(&path).is_none() {
+				return Ok(default);
+			}
+		}
+		self.expect_at(path, T::from_jecs)
+	}
+}
+
+#[macro_export]
+macro_rules! jecs_keys {
+	($($visibility:vis $name:ident: $type:ty = $path:expr;)*) => {
+		$(
+			$visibility const $name: $crate::decode::JecsKey<$type> = $crate::decode::JecsKey::new($path);
+		)*
+	};
+}
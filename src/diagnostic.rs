@@ -0,0 +1,80 @@
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+use crate::errors::{JecsCorruptedDataError, JecsErrorCode, JecsWarning};
+
+//Renders parse errors and warnings together with the offending source line, a caret under the column and a hint:
+//
+//error[JECS0001]: Cannot mix list and dict collection entries with the same parent
+// --> line 3, column 3
+//  |
+//3 |   b: 2
+//  |   ^
+//  = hint: Entries of one parent are either all list entries ('- value') or all have a key ('key: value')
+pub fn render_error(source: &str, error: &JecsCorruptedDataError) -> String {
+	render("error", source, error.code, error.row, error.column, &error.description)
+}
+
+pub fn render_warning(source: &str, warning: &JecsWarning) -> String {
+	render("warning", source, warning.code, warning.row, warning.column, &warning.description)
+}
+
+pub fn hint(code: JecsErrorCode) -> Option<&'static str> {
+	Some(match code {
+		JecsErrorCode::MixedListAndMap => "Entries of one parent are either all list entries ('- value') or all have a key ('key: value')",
+		JecsErrorCode::MissingKey => "Every entry needs a key in front of its ':', or has to be a list entry starting with '-'",
+		JecsErrorCode::UnterminatedKey => "Keys end with ':', add it or turn the line into a comment with '#'",
+		JecsErrorCode::InvalidKeyCharacter => "'#' starts a comment, it cannot be used within keys",
+		JecsErrorCode::UnterminatedMultiLineString => "Multi-line strings end with a line only containing '\"\"\"'",
+		JecsErrorCode::MultiLineStringNotIndented => "Indent the lines of a multi-line string deeper than the line with its opening '\"\"\"'",
+		JecsErrorCode::MultiLineStringInconsistentIndentation => "All lines of a multi-line string need the same indentation, including the terminating '\"\"\"'",
+		JecsErrorCode::IndentedRootEntry => "The first entry of a file is a root entry, it may not be indented",
+		JecsErrorCode::RootListEntry => "The root of a JECS file is a map, give the entry a key",
+		JecsErrorCode::ChildOfValue => "Either remove the value of the parent entry, or indent this line like its sibling entries",
+		JecsErrorCode::WrongIndentation => "Indent the entry exactly like its siblings or like one of its parents",
+		JecsErrorCode::AdjustedIndentation => "Indent the entry exactly like its siblings",
+		JecsErrorCode::TrailingWhitespace => "Remove the whitespace at the end of the line, in multi-line strings it gets lost",
+		JecsErrorCode::TabCharacter => "Use spaces instead of tabs",
+		JecsErrorCode::InconsistentIndentationStep => "Use the same amount of spaces for every indentation level",
+		JecsErrorCode::CommentAfterValue => "Comments are usually separated from values by a space: 'key: value # comment'",
+		JecsErrorCode::CommentInPlaceOfValue => "Comments are usually separated from the '#' by a space: 'key: # comment'",
+		JecsErrorCode::UnknownKey => "Check the key for typos, or remove it if it is no longer used",
+		JecsErrorCode::IndentationJump => "Indent child entries exactly one level deeper than their parent",
+		JecsErrorCode::DuplicateKey | JecsErrorCode::ReplacedDuplicateKey => "Remove one of the entries or rename its key, only the last one is used when duplicates are tolerated",
+		JecsErrorCode::KeyCase => "Rename the key as suggested, the code reading the file has to use the new name too",
+		//Tree errors do not come from the parser and thus have no source to show:
+		JecsErrorCode::WrongEntryType | JecsErrorCode::IncompatibleOrMalformed | JecsErrorCode::KeyNotFound | JecsErrorCode::OutOfRange | JecsErrorCode::WrongLength => return None,
+	})
+}
+
+fn render(severity: &str, source: &str, code: JecsErrorCode, row: usize, column: usize, description: &str) -> String {
+	let mut output = String::new();
+	//Writing to a String cannot fail:
+	writeln!(output, "{}[{}]: {}", severity, code.code(), description).unwrap();
+	
+	//The parser skips the BOM, the rows and columns are relative to the text after it:
+	let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+	let number = row.to_string();
+	let gutter = " ".repeat(number.len());
+	match source.lines().nth(row.saturating_sub(1)) {
+		None => {
+			writeln!(output, "{}--> line {}", gutter, row).unwrap();
+		}
+		Some(line) => {
+			let mut column = column.min(line.len());
+			while !line.is_char_boundary(column) {
+				column -= 1;
+			}
+			//Keep tabs in front of the caret, so that it lines up with the source line:
+			let marker: String = line[..column].chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+			writeln!(output, "{}--> line {}, column {}", gutter, row, line[..column].chars().count() + 1).unwrap();
+			writeln!(output, "{} |", gutter).unwrap();
+			writeln!(output, "{} | {}", number, line).unwrap();
+			writeln!(output, "{} | {}^", gutter, marker).unwrap();
+		}
+	}
+	if let Some(hint) = hint(code) {
+		writeln!(output, "{} = hint: {}", gutter, hint).unwrap();
+	}
+	output
+}
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+use crate::path::JecsPath;
+use crate::types::JecsType;
+
+//A difference between two trees. Maps are compared key by key and lists index by index,
+// thus inserting into the middle of a list shows up as changes of all following entries.
+#[derive(Clone, PartialEq)]
+#[derive(Debug)]
+pub enum JecsChange {
+	Added {
+		path: JecsPath,
+		entry: JecsType,
+	},
+	Removed {
+		path: JecsPath,
+		entry: JecsType,
+	},
+	//Values which differ, or entries whose type changed.
+	Changed {
+		path: JecsPath,
+		old: JecsType,
+		new: JecsType,
+	},
+}
+
+impl JecsChange {
+	pub fn path(&self) -> &JecsPath {
+		match self {
+			JecsChange::Added { path, .. } | JecsChange::Removed { path, .. } | JecsChange::Changed { path, .. } => path,
+		}
+	}
+}
+
+impl JecsType {
+	//All changes needed to turn this tree into the other one, map keys are compared in alphabetical order.
+	pub fn diff(&self, other: &JecsType) -> Vec<JecsChange> {
+		let mut changes = Vec::new();
+		diff_inner(self, other, &mut JecsPath::root(), &mut changes);
+		return changes;
+		
+		fn diff_inner(old: &JecsType, new: &JecsType, path: &mut JecsPath, changes: &mut Vec<JecsChange>) {
+			match (old, new) {
+				(JecsType::Map(old_map), JecsType::Map(new_map)) => {
+					let mut keys: Vec<_> = old_map.keys().chain(new_map.keys().filter(|key| !old_map.contains_key(*key))).collect();
+					keys.sort();
+					for key in keys {
+						path.push_key(key);
+						match (old_map.get(key), new_map.get(key)) {
+							(Some(old_child), Some(new_child)) => diff_inner(old_child, new_child, path, changes),
+							(Some(old_child), None) => changes.push(JecsChange::Removed {
+								path: path.clone(),
+								entry: old_child.clone(),
+							}),
+							(None, Some(new_child)) => changes.push(JecsChange::Added {
+								path: path.clone(),
+								entry: new_child.clone(),
+							}),
+							(None, None) => unreachable!(), //Keys are taken from the maps
+						}
+						path.pop();
+					}
+				}
+				(JecsType::List(old_list), JecsType::List(new_list)) => {
+					for index in 0..old_list.len().max(new_list.len()) {
+						path.push_index(index);
+						match (old_list.get(index), new_list.get(index)) {
+							(Some(old_child), Some(new_child)) => diff_inner(old_child, new_child, path, changes),
+							(Some(old_child), None) => changes.push(JecsChange::Removed {
+								path: path.clone(),
+								entry: old_child.clone(),
+							}),
+							(None, Some(new_child)) => changes.push(JecsChange::Added {
+								path: path.clone(),
+								entry: new_child.clone(),
+							}),
+							(None, None) => unreachable!(), //Index is below the length of one of the lists
+						}
+						path.pop();
+					}
+				}
+				(old, new) => {
+					if old != new {
+						changes.push(JecsChange::Changed {
+							path: path.clone(),
+							old: old.clone(),
+							new: new.clone(),
+						});
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,491 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+use crate::errors::{JecsCorruptedDataError, JecsWriteError};
+use crate::parser::parse_jecs_string_with_spans;
+use crate::path::{JecsPath, JecsPathSegment};
+use crate::redact::JecsRedaction;
+use crate::spanned::{self, JecsSpannedEntry, JecsSpannedType};
+use crate::types::{JecsMap, JecsType};
+use crate::writer::{push_escaped_value, validate_value, write_entry, write_error, WriterOptions, INDENTATION_STEP};
+
+//A JECS file kept as its original text. Edits only replace the bytes of the changed value or comment,
+// thus comments, spacing, line endings and the order of entries stay exactly as they were. Display writes the text.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct JecsDocument {
+	text: String,
+	root: JecsSpannedEntry,
+}
+
+impl JecsDocument {
+	pub fn parse(text: &str) -> Result<Self, JecsCorruptedDataError> {
+		Ok(Self {
+			root: JecsSpannedEntry::root(parse_jecs_string_with_spans(strip_bom(text))?),
+			text: text.into(),
+		})
+	}
+	
+	pub fn as_str(&self) -> &str {
+		&self.text
+	}
+	
+	pub fn root(&self) -> &JecsSpannedEntry {
+		&self.root
+	}
+	
+	pub fn to_jecs_map(&self) -> JecsMap {
+		match &self.root.value {
+			JecsSpannedType::Map(map) => spanned::into_jecs_map(map.clone()),
+			_ => unreachable!("Impossible to reach code: The root of a document is always a map."),
+		}
+	}
+	
+	pub fn get_entry(&self, path: impl Into<JecsPath>) -> Option<&JecsSpannedEntry> {
+		self.root.get_path(path)
+	}
+	
+	pub fn get_value(&self, path: impl Into<JecsPath>) -> Option<&str> {
+		match &self.get_entry(path)?.value {
+			JecsSpannedType::Value(value) => Some(value),
+			_ => None,
+		}
+	}
+	
+	//The deepest entry covering a position (row starting at 1, column as byte offset), together with its path.
+	//Entries cover all rows up to their last child, but on their first row only from their key on.
+	pub fn node_at(&self, row: usize, column: usize) -> Option<(JecsPath, &JecsSpannedEntry)> {
+		let mut path = JecsPath::root();
+		let mut entry = &self.root;
+		loop {
+			let covers = |child: &JecsSpannedEntry| {
+				child.span.row <= row && row <= child.span.last_row && (row != child.span.row || column >= child.span.key_columns.start)
+			};
+			let child = match &entry.value {
+				JecsSpannedType::Map(map) => map.iter().find(|(_, child)| covers(child)).map(|(key, child)| {
+					path.push_key(key);
+					child
+				}),
+				JecsSpannedType::List(list) => list.iter().position(covers).map(|index| {
+					path.push_index(index);
+					&list[index]
+				}),
+				_ => None,
+			};
+			match child {
+				None => break,
+				Some(child) => entry = child,
+			}
+		}
+		if path.is_root() {
+			return None;
+		}
+		Some((path, entry))
+	}
+	
+	//Replaces the value of an existing entry. An empty value turns the entry into one without value.
+	//Multi-line strings are replaced up to including their terminator, entries with children cannot get a value.
+	pub fn set_value(&mut self, path: impl Into<JecsPath>, value: &str) -> Result<(), JecsWriteError> {
+		let path = path.into();
+		if path.is_root() {
+			return Err(write_error(&path, "The root cannot have a value"));
+		}
+		let entry = match self.get_entry(&path) {
+			None => return Err(write_error(&path, "Entry does not exist")),
+			Some(entry) => entry,
+		};
+		if let JecsSpannedType::Map(_) | JecsSpannedType::List(_) = entry.value {
+			return Err(write_error(&path, "Entries with children cannot have a value"));
+		}
+		validate_value(value).map_err(|description| write_error(&path, description))?;
+		
+		let span = &entry.span;
+		let line_start = self.row_offset(span.row);
+		//Keys are followed by ':', which has to stay. Spaces may be in front of it, as in 'Key :'. The '-' of list entries is not followed by anything:
+		let mut separator_end = line_start + span.key_columns.end;
+		if let Some(JecsPathSegment::Key(_)) = path.last() {
+			let after_key = &self.text[separator_end..];
+			let spaces = after_key.len() - after_key.trim_start_matches(' ').len();
+			if after_key[spaces..].starts_with(':') {
+				separator_end += spaces + 1;
+			}
+		}
+		let mut replacement = String::new();
+		let range = match &span.value_columns {
+			None if value.is_empty() => return Ok(()), //Nothing to change
+			None => {
+				replacement.push(' ');
+				separator_end..separator_end
+			}
+			Some(value_columns) => {
+				let value_end = if span.last_row != span.row {
+					self.terminator_end(span.last_row)
+				} else {
+					line_start + value_columns.end
+				};
+				if value.is_empty() {
+					separator_end..value_end //Also remove the spaces in front of the value
+				} else {
+					line_start + value_columns.start..value_end
+				}
+			}
+		};
+		push_escaped_value(&mut replacement, value);
+		self.replace(&path, range, &replacement)
+	}
+	
+	//Replaces a byte range of the text, like an editor does on every keystroke. On error the document stays unchanged.
+	//Only the root entries touched by the edit are parsed again, the entries after them just get their rows moved.
+	//Panics like String::replace_range(), if the range is out of bounds or not on a char boundary.
+	pub fn apply_edit(&mut self, range: Range<usize>, replacement: &str) -> Result<(), JecsCorruptedDataError> {
+		let bom_length = self.text.len() - strip_bom(&self.text).len();
+		let removed_rows = self.text[range.clone()].matches('\n').count();
+		let edit_row = 1 + self.text[..range.start].matches('\n').count();
+		let mut text = self.text.clone();
+		text.replace_range(range.clone(), replacement);
+		if range.start < bom_length || strip_bom(&text).len() + bom_length != text.len() {
+			return self.reparse(text);
+		}
+		let added_rows = replacement.matches('\n').count();
+		let delta = added_rows as isize - removed_rows as isize;
+		
+		//Lines without indentation always start a root entry, as multi-line strings have to be indented.
+		//The lines in front of and after the edit did not change, thus root entries there start at the same line in the old text:
+		let body = strip_bom(&text);
+		let mut root_count = 0;
+		let mut start = (1, 0); //Row and byte offset of the first line to parse again
+		let mut end = None;
+		let mut offset = 0;
+		for (index, line) in body.split_inclusive('\n').enumerate() {
+			let row = index + 1;
+			if line.starts_with(|c: char| !matches!(c, ' ' | '#' | '\r' | '\n')) {
+				root_count += 1;
+				if row < edit_row {
+					start = (row, offset);
+				} else if row > edit_row + added_rows && end.is_none() {
+					end = Some((row, offset));
+				}
+			}
+			offset += line.len();
+		}
+		let (start_row, start_offset) = start;
+		let (old_end_row, end_offset) = match end {
+			None => (usize::MAX, body.len()),
+			Some((row, offset)) => (row.saturating_add_signed(-delta), offset),
+		};
+		let section = match parse_jecs_string_with_spans(&body[start_offset..end_offset]) {
+			//The error might differ from the one of the whole file, as the section ends in the middle of it:
+			Err(_) => return self.reparse(text),
+			Ok(section) => section,
+		};
+		
+		let map = match &mut self.root.value {
+			JecsSpannedType::Map(map) => map,
+			_ => unreachable!("Impossible to reach code: The root of a document is always a map."),
+		};
+		let is_replaced = |entry: &JecsSpannedEntry| entry.span.row >= start_row && entry.span.row < old_end_row;
+		//With duplicate keys only the last entry is kept, which is only known when parsing everything:
+		let removed_count = map.values().filter(|entry| is_replaced(entry)).count();
+		let kept_count = section.keys().filter(|key| map.get(*key).is_some_and(|entry| !is_replaced(entry))).count();
+		if map.len() - removed_count + section.len() - kept_count != root_count {
+			return self.reparse(text);
+		}
+		map.retain(|_, entry| !is_replaced(entry));
+		for entry in map.values_mut() {
+			if entry.span.row >= old_end_row {
+				entry.shift_rows(delta);
+			}
+		}
+		for (key, mut entry) in section {
+			entry.shift_rows(start_row as isize - 1);
+			map.insert(key, entry);
+		}
+		self.text = text;
+		Ok(())
+	}
+	
+	fn reparse(&mut self, text: String) -> Result<(), JecsCorruptedDataError> {
+		self.root = JecsSpannedEntry::root(parse_jecs_string_with_spans(strip_bom(&text))?);
+		self.text = text;
+		Ok(())
+	}
+	
+	//The comment lines directly above an entry with the same indentation, without the '#' and one space after it.
+	pub fn get_comment(&self, path: impl Into<JecsPath>) -> Option<String> {
+		let entry = self.get_entry(path)?;
+		let rows = self.comment_rows(entry);
+		if rows.is_empty() {
+			return None;
+		}
+		let lines: Vec<&str> = rows.map(|row| {
+			let comment = &self.line(row).trim_start_matches(' ')[1..];
+			comment.strip_prefix(' ').unwrap_or(comment)
+		}).collect();
+		Some(lines.join("\n"))
+	}
+	
+	//Replaces the comment lines above an entry, see get_comment(). Every line of the comment becomes a '# ' line, an empty comment removes them.
+	//The rest of the document, including other comments and empty lines, stays as it is.
+	pub fn set_comment(&mut self, path: impl Into<JecsPath>, comment: &str) -> Result<(), JecsWriteError> {
+		let path = path.into();
+		if path.is_root() {
+			return Err(write_error(&path, "The root cannot have a comment"));
+		}
+		let entry = match self.get_entry(&path) {
+			None => return Err(write_error(&path, "Entry does not exist")),
+			Some(entry) => entry,
+		};
+		let rows = self.comment_rows(entry);
+		let indentation = " ".repeat(entry.span.key_columns.start);
+		let line_ending = self.line_ending();
+		let mut replacement = String::new();
+		if !comment.is_empty() {
+			for line in comment.lines() {
+				replacement.push_str(&indentation);
+				replacement.push('#');
+				if !line.is_empty() {
+					replacement.push(' ');
+					replacement.push_str(line);
+				}
+				replacement.push_str(line_ending);
+			}
+		}
+		let range = self.row_offset(rows.start)..self.row_offset(rows.end);
+		self.apply_edit(range, &replacement).map_err(|error| write_error(&path, error.description.as_str()))
+	}
+	
+	//Copy of the document with the values of sensitive entries replaced, so that its text can be shared. Comments stay as they are.
+	pub fn redacted(&self, redaction: &JecsRedaction) -> JecsDocument {
+		let mut paths = Vec::new();
+		collect_sensitive_values(&mut paths, &mut JecsPath::root(), &self.root, redaction);
+		let mut copy = self.clone();
+		for path in paths {
+			//The entries exist and have a value, which can always be replaced by a single line one:
+			copy.set_value(path, JecsRedaction::REPLACEMENT).unwrap();
+		}
+		return copy;
+		
+		fn collect_sensitive_values(paths: &mut Vec<JecsPath>, path: &mut JecsPath, entry: &JecsSpannedEntry, redaction: &JecsRedaction) {
+			match &entry.value {
+				JecsSpannedType::Value(_) if redaction.is_sensitive(path) => paths.push(path.clone()),
+				JecsSpannedType::Map(map) => {
+					for (key, child) in map {
+						path.push_key(key);
+						collect_sensitive_values(paths, path, child, redaction);
+						path.pop();
+					}
+				}
+				JecsSpannedType::List(list) => {
+					for (index, child) in list.iter().enumerate() {
+						path.push_index(index);
+						collect_sensitive_values(paths, path, child, redaction);
+						path.pop();
+					}
+				}
+				_ => {}
+			}
+		}
+	}
+	
+	//Changes the document to contain exactly the entries of the tree, while keeping as much of its text as possible:
+	//Changed values are replaced in place, removed entries are deleted together with their comment and new entries are added after the last entry of their map.
+	//Maps are compared key by key. Lists and entries that changed their type are written again as a whole, keeping the comment above them.
+	pub fn sync_with(&mut self, root: &JecsMap, options: &WriterOptions) -> Result<(), JecsWriteError> {
+		self.sync_map(&JecsPath::root(), root, options)
+	}
+	
+	fn sync_map(&mut self, path: &JecsPath, target: &JecsMap, options: &WriterOptions) -> Result<(), JecsWriteError> {
+		let removed_keys: Vec<String> = match &self.get_entry(path).unwrap().value {
+			JecsSpannedType::Map(map) => map.keys().filter(|key| !target.contains_key(*key)).cloned().collect(),
+			_ => unreachable!("Impossible to reach code: Only maps of the document are synchronized."),
+		};
+		for key in removed_keys {
+			self.remove_entry(&path.join_key(&key))?;
+		}
+		let mut added_entries = Vec::new();
+		for (key, entry) in options.key_order.sorted(path, target) {
+			let child_path = path.join_key(key);
+			if self.get_entry(&child_path).is_none() {
+				added_entries.push((key, entry));
+			} else {
+				self.sync_entry(&child_path, entry, options)?;
+			}
+		}
+		for (key, entry) in added_entries {
+			self.append_entry(path, key, entry, options)?;
+		}
+		Ok(())
+	}
+	
+	fn sync_entry(&mut self, path: &JecsPath, target: &JecsType, options: &WriterOptions) -> Result<(), JecsWriteError> {
+		let existing = self.get_entry(path).unwrap();
+		match (&existing.value, target) {
+			(JecsSpannedType::Map(_), JecsType::Map(map)) => self.sync_map(path, map, options),
+			(JecsSpannedType::Any(), JecsType::Any()) => Ok(()),
+			(JecsSpannedType::Value(old), JecsType::Value(new)) if old.as_str() == new.as_str() => Ok(()),
+			//The writer might quote the value or turn it into a multi-line string. Empty values would turn into Any entries in place:
+			(JecsSpannedType::Value(_) | JecsSpannedType::Any(), JecsType::Value(value)) if !options.quote_values && !value.is_empty() && validate_value(value).is_ok() => self.set_value(path, value),
+			(JecsSpannedType::Value(_), JecsType::Any()) => self.set_value(path, ""),
+			(JecsSpannedType::List(_), JecsType::List(_)) if existing.clone().into_jecs_type() == *target => Ok(()),
+			_ => self.replace_entry(path, target, options),
+		}
+	}
+	
+	fn remove_entry(&mut self, path: &JecsPath) -> Result<(), JecsWriteError> {
+		let entry = self.get_entry(path).unwrap();
+		let rows = self.comment_rows(entry);
+		let range = self.row_offset(rows.start)..self.row_offset(entry.span.last_row + 1);
+		self.apply_edit(range, "").map_err(|error| write_error(path, error.description.as_str()))
+	}
+	
+	fn replace_entry(&mut self, path: &JecsPath, target: &JecsType, options: &WriterOptions) -> Result<(), JecsWriteError> {
+		let entry = self.get_entry(path).unwrap();
+		let indentation = entry.span.key_columns.start;
+		let range = self.row_offset(entry.span.row)..self.row_offset(entry.span.last_row + 1);
+		let replacement = self.render_entry(path, target, indentation, options)?;
+		self.apply_edit(range, &replacement).map_err(|error| write_error(path, error.description.as_str()))
+	}
+	
+	//Adds the entry after the last entry of the map, with the same indentation as the other entries of the map.
+	fn append_entry(&mut self, map_path: &JecsPath, key: &str, target: &JecsType, options: &WriterOptions) -> Result<(), JecsWriteError> {
+		let path = map_path.join_key(key);
+		let (indentation, offset) = if map_path.is_root() {
+			(0, self.text.len())
+		} else {
+			let map_entry = self.get_entry(map_path).unwrap();
+			let indentation = match &map_entry.value {
+				JecsSpannedType::Map(map) => map.values().map(|child| child.span.key_columns.start).min(),
+				_ => None,
+			};
+			let indentation = indentation.unwrap_or(map_entry.span.key_columns.start + INDENTATION_STEP);
+			(indentation, self.row_offset(map_entry.span.last_row + 1))
+		};
+		let mut replacement = String::new();
+		if offset == self.text.len() && !self.text.is_empty() && !self.text.ends_with('\n') {
+			replacement.push_str(self.line_ending());
+		}
+		replacement.push_str(&self.render_entry(&path, target, indentation, options)?);
+		self.apply_edit(offset..offset, &replacement).map_err(|error| write_error(&path, error.description.as_str()))
+	}
+	
+	//The lines of an entry as the writer produces them, moved to the indentation and with the line endings of the document.
+	fn render_entry(&self, path: &JecsPath, target: &JecsType, indentation: usize, options: &WriterOptions) -> Result<String, JecsWriteError> {
+		let mut output = String::new();
+		write_entry(&mut output, &mut path.clone(), 0, path.last_key(), target, options)?;
+		let indentation = " ".repeat(indentation);
+		let line_ending = self.line_ending();
+		let mut replacement = String::new();
+		for line in output.lines() {
+			if !line.is_empty() {
+				replacement.push_str(&indentation);
+				replacement.push_str(line);
+			}
+			replacement.push_str(line_ending);
+		}
+		Ok(replacement)
+	}
+	
+	fn line_ending(&self) -> &'static str {
+		if self.text.contains("\r\n") { "\r\n" } else { "\n" }
+	}
+	
+	fn comment_rows(&self, entry: &JecsSpannedEntry) -> Range<usize> {
+		let indentation = entry.span.key_columns.start;
+		let mut first_row = entry.span.row;
+		while first_row > 1 {
+			let line = self.line(first_row - 1);
+			let content = line.trim_start_matches(' ');
+			if line.len() - content.len() != indentation || !content.starts_with('#') {
+				break;
+			}
+			first_row -= 1;
+		}
+		first_row..entry.span.row
+	}
+	
+	//A row (starting at 1) of the text, without its line ending.
+	fn line(&self, row: usize) -> &str {
+		let rest = &self.text[self.row_offset(row)..];
+		let line = rest.split('\n').next().unwrap();
+		line.strip_suffix('\r').unwrap_or(line)
+	}
+	
+	fn replace(&mut self, path: &JecsPath, range: Range<usize>, replacement: &str) -> Result<(), JecsWriteError> {
+		let mut text = self.text.clone();
+		text.replace_range(range, replacement);
+		//Parsing again updates all spans after the edit, the validation above ensures that this succeeds:
+		match parse_jecs_string_with_spans(strip_bom(&text)) {
+			Ok(map) => {
+				self.root = JecsSpannedEntry::root(map);
+				self.text = text;
+				Ok(())
+			}
+			Err(error) => Err(write_error(path, error.description.as_str())),
+		}
+	}
+	
+	//Byte offset of the start of a row (starting at 1) within the text.
+	fn row_offset(&self, row: usize) -> usize {
+		let bom_length = self.text.len() - strip_bom(&self.text).len();
+		bom_length + strip_bom(&self.text).split_inclusive('\n').take(row - 1).map(str::len).sum::<usize>()
+	}
+	
+	//Byte offset after the '"""' terminating a multi-line string on that row.
+	fn terminator_end(&self, row: usize) -> usize {
+		let line_start = self.row_offset(row);
+		let line = &self.text[line_start..];
+		line_start + line.find("\"\"\"").unwrap() + 3
+	}
+}
+
+impl Default for JecsDocument {
+	fn default() -> Self {
+		Self {
+			text: String::new(),
+			root: JecsSpannedEntry::root(Default::default()),
+		}
+	}
+}
+
+impl Display for JecsDocument {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		f.write_str(&self.text)
+	}
+}
+
+//Reads a file as document, lets the function edit it and writes it back, if the text changed.
+//Untouched bytes stay as they are. The new text is written to a temporary file next to it first, which then replaces the file.
+//Returns if the file got written.
+#[cfg(feature = "std")]
+pub fn update_jecs_file<E: Into<Box<dyn Error>>>(path: &Path, edit: impl FnOnce(&mut JecsDocument) -> Result<(), E>) -> Result<bool, Box<dyn Error>> {
+	let bytes = fs::read(path)?;
+	let text = core::str::from_utf8(&bytes)?;
+	let mut document = JecsDocument::parse(text)?;
+	edit(&mut document).map_err(Into::into)?;
+	if document.as_str() == text {
+		return Ok(false);
+	}
+	replace_file(path, document.as_str())?;
+	Ok(true)
+}
+
+//Writes the text to a temporary file next to the file first, which then replaces the file. Readers never see a half written file.
+#[cfg(feature = "std")]
+pub(crate) fn replace_file(path: &Path, text: &str) -> std::io::Result<()> {
+	let mut temporary_path = path.as_os_str().to_owned();
+	temporary_path.push(".tmp");
+	fs::write(&temporary_path, text)?;
+	fs::rename(&temporary_path, path)
+}
+
+pub(crate) fn strip_bom(text: &str) -> &str {
+	text.strip_prefix('\u{feff}').unwrap_or(text)
+}
@@ -0,0 +1,81 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::types::JecsType;
+
+//Types which can be written as an entry of a JECS tree, the counterpart of decode::FromJecs.
+//The produced entry has to be readable with FromJecs of the same type again.
+pub trait IntoJecs {
+	fn to_jecs(&self) -> JecsType;
+	
+	//Comments for the entries of the produced tree, as path (relative to it) and comment text. Written into generated default files.
+	fn jecs_comments() -> Vec<(&'static str, &'static str)> where Self: Sized {
+		Vec::new()
+	}
+}
+
+impl IntoJecs for JecsType {
+	fn to_jecs(&self) -> JecsType {
+		self.clone()
+	}
+}
+
+impl IntoJecs for str {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::value(self)
+	}
+}
+
+impl IntoJecs for String {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::value(self.as_str())
+	}
+}
+
+impl IntoJecs for bool {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::value(if *self { "true" } else { "false" })
+	}
+}
+
+macro_rules! display_into_jecs {
+	($($type:ty),*) => {
+		$(
+			impl IntoJecs for $type {
+				fn to_jecs(&self) -> JecsType {
+					JecsType::value(self.to_string())
+				}
+			}
+		)*
+	};
+}
+
+display_into_jecs!(f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+//None becomes an Any entry, which FromJecs of Option reads as None again.
+impl<T: IntoJecs> IntoJecs for Option<T> {
+	fn to_jecs(&self) -> JecsType {
+		match self {
+			None => JecsType::Any(),
+			Some(value) => value.to_jecs(),
+		}
+	}
+}
+
+impl<T: IntoJecs> IntoJecs for [T] {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::List(self.iter().map(IntoJecs::to_jecs).collect())
+	}
+}
+
+impl<T: IntoJecs> IntoJecs for Vec<T> {
+	fn to_jecs(&self) -> JecsType {
+		self.as_slice().to_jecs()
+	}
+}
+
+impl<T: IntoJecs + ?Sized> IntoJecs for &T {
+	fn to_jecs(&self) -> JecsType {
+		(**self).to_jecs()
+	}
+}
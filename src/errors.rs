@@ -1,20 +1,246 @@
-use std::error::Error;
-use std::fmt::{Debug, Display, Formatter};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use alloc::{format, string::ToString};
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::path::JecsPath;
+
+// ###### Error Codes ######
+
+//Stable codes of all parse and tree errors and of the parser warnings. Codes never change their meaning, new ones get appended.
+//Parse errors use JECS00xx, tree errors JECS01xx and warnings JECS10xx.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum JecsErrorCode {
+	MixedListAndMap,
+	MissingKey,
+	UnterminatedKey,
+	InvalidKeyCharacter,
+	UnterminatedMultiLineString,
+	MultiLineStringNotIndented,
+	MultiLineStringInconsistentIndentation,
+	IndentedRootEntry,
+	RootListEntry,
+	ChildOfValue,
+	WrongIndentation,
+	WrongEntryType,
+	IncompatibleOrMalformed,
+	KeyNotFound,
+	OutOfRange,
+	AdjustedIndentation,
+	TrailingWhitespace,
+	TabCharacter,
+	InconsistentIndentationStep,
+	CommentAfterValue,
+	CommentInPlaceOfValue,
+	UnknownKey,
+	IndentationJump,
+	DuplicateKey,
+	ReplacedDuplicateKey,
+	KeyCase,
+	WrongLength,
+}
+
+impl JecsErrorCode {
+	pub fn code(&self) -> &'static str {
+		match self {
+			JecsErrorCode::MixedListAndMap => "JECS0001",
+			JecsErrorCode::MissingKey => "JECS0002",
+			JecsErrorCode::UnterminatedKey => "JECS0003",
+			JecsErrorCode::InvalidKeyCharacter => "JECS0004",
+			JecsErrorCode::UnterminatedMultiLineString => "JECS0005",
+			JecsErrorCode::MultiLineStringNotIndented => "JECS0006",
+			JecsErrorCode::MultiLineStringInconsistentIndentation => "JECS0007",
+			JecsErrorCode::IndentedRootEntry => "JECS0008",
+			JecsErrorCode::RootListEntry => "JECS0009",
+			JecsErrorCode::ChildOfValue => "JECS0010",
+			JecsErrorCode::WrongIndentation => "JECS0011",
+			JecsErrorCode::WrongEntryType => "JECS0101",
+			JecsErrorCode::IncompatibleOrMalformed => "JECS0102",
+			JecsErrorCode::KeyNotFound => "JECS0103",
+			JecsErrorCode::OutOfRange => "JECS0104",
+			JecsErrorCode::AdjustedIndentation => "JECS1001",
+			JecsErrorCode::TrailingWhitespace => "JECS1002",
+			JecsErrorCode::TabCharacter => "JECS1003",
+			JecsErrorCode::InconsistentIndentationStep => "JECS1004",
+			JecsErrorCode::CommentAfterValue => "JECS1005",
+			JecsErrorCode::CommentInPlaceOfValue => "JECS1006",
+			JecsErrorCode::UnknownKey => "JECS1007",
+			JecsErrorCode::IndentationJump => "JECS1008",
+			JecsErrorCode::DuplicateKey => "JECS0012",
+			JecsErrorCode::ReplacedDuplicateKey => "JECS1009",
+			JecsErrorCode::KeyCase => "JECS1010",
+			JecsErrorCode::WrongLength => "JECS0105",
+		}
+	}
+	
+	pub fn summary(&self) -> &'static str {
+		match self {
+			JecsErrorCode::MixedListAndMap => "mixed list/map siblings",
+			JecsErrorCode::MissingKey => "missing key",
+			JecsErrorCode::UnterminatedKey => "key without ':'",
+			JecsErrorCode::InvalidKeyCharacter => "invalid character in key",
+			JecsErrorCode::UnterminatedMultiLineString => "unterminated multi-line string",
+			JecsErrorCode::MultiLineStringNotIndented => "multi-line string not indented",
+			JecsErrorCode::MultiLineStringInconsistentIndentation => "inconsistent multi-line string indentation",
+			JecsErrorCode::IndentedRootEntry => "indented root entry",
+			JecsErrorCode::RootListEntry => "list entry at root level",
+			JecsErrorCode::ChildOfValue => "child of entry with value",
+			JecsErrorCode::WrongIndentation => "wrong indentation",
+			JecsErrorCode::WrongEntryType => "wrong entry type",
+			JecsErrorCode::IncompatibleOrMalformed => "incompatible or malformed value",
+			JecsErrorCode::KeyNotFound => "key not found",
+			JecsErrorCode::OutOfRange => "value out of range",
+			JecsErrorCode::AdjustedIndentation => "adjusted indentation",
+			JecsErrorCode::TrailingWhitespace => "trailing whitespace",
+			JecsErrorCode::TabCharacter => "tab character",
+			JecsErrorCode::InconsistentIndentationStep => "inconsistent indentation step",
+			JecsErrorCode::CommentAfterValue => "comment directly after value",
+			JecsErrorCode::CommentInPlaceOfValue => "comment in place of value",
+			JecsErrorCode::UnknownKey => "key unknown to the schema",
+			JecsErrorCode::IndentationJump => "indentation skips levels",
+			JecsErrorCode::DuplicateKey => "duplicate key",
+			JecsErrorCode::ReplacedDuplicateKey => "duplicate key replaces earlier entry",
+			JecsErrorCode::KeyCase => "key against naming convention",
+			JecsErrorCode::WrongLength => "wrong list length",
+		}
+	}
+}
+
+impl Display for JecsErrorCode {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}: {}", self.code(), self.summary())
+	}
+}
 
 // ###### Tree Errors ######
 
+fn write_path_prefix(f: &mut Formatter<'_>, path: &Option<JecsPath>) -> core::fmt::Result {
+	if let Some(path) = path {
+		write!(f, "At '{}': ", path)?;
+	}
+	Ok(())
+}
+
+// ### Decode Error ###
+
+//Returned by all accessors which read entries or values of a tree, so that they can be handled the same way.
+#[derive(Debug)]
+pub enum JecsDecodeError {
+	WrongType(JecsWrongEntryTypeError),
+	Malformed(JecsIncompatibleOrMalformedError),
+	MissingKey(JecsMissingKeyError),
+	OutOfRange(JecsOutOfRangeError),
+	WrongLength(JecsWrongLengthError),
+}
+
+impl JecsDecodeError {
+	pub fn path(&self) -> Option<&JecsPath> {
+		match self {
+			JecsDecodeError::WrongType(error) => error.path.as_ref(),
+			JecsDecodeError::Malformed(error) => error.path.as_ref(),
+			JecsDecodeError::MissingKey(error) => error.path.as_ref(),
+			JecsDecodeError::OutOfRange(error) => error.path.as_ref(),
+			JecsDecodeError::WrongLength(error) => error.path.as_ref(),
+		}
+	}
+	
+	//Sets the path of the entry which caused the error.
+	//If the error already has a path, it is relative to the given one, this way nested decoders only know their part.
+	pub fn at(mut self, path: impl Into<JecsPath>) -> Self {
+		let target = match &mut self {
+			JecsDecodeError::WrongType(error) => &mut error.path,
+			JecsDecodeError::Malformed(error) => &mut error.path,
+			JecsDecodeError::MissingKey(error) => &mut error.path,
+			JecsDecodeError::OutOfRange(error) => &mut error.path,
+			JecsDecodeError::WrongLength(error) => &mut error.path,
+		};
+		let mut path = path.into();
+		if let Some(inner) = target.take() {
+			path.extend(inner);
+		}
+		*target = Some(path);
+		self
+	}
+	
+	pub fn error_code(&self) -> JecsErrorCode {
+		match self {
+			JecsDecodeError::WrongType(error) => error.error_code(),
+			JecsDecodeError::Malformed(error) => error.error_code(),
+			JecsDecodeError::MissingKey(error) => error.error_code(),
+			JecsDecodeError::OutOfRange(error) => error.error_code(),
+			JecsDecodeError::WrongLength(error) => error.error_code(),
+		}
+	}
+}
+
+impl Error for JecsDecodeError {}
+
+impl Display for JecsDecodeError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			JecsDecodeError::WrongType(error) => Display::fmt(error, f),
+			JecsDecodeError::Malformed(error) => Display::fmt(error, f),
+			JecsDecodeError::MissingKey(error) => Display::fmt(error, f),
+			JecsDecodeError::OutOfRange(error) => Display::fmt(error, f),
+			JecsDecodeError::WrongLength(error) => Display::fmt(error, f),
+		}
+	}
+}
+
+impl From<JecsWrongEntryTypeError> for JecsDecodeError {
+	fn from(error: JecsWrongEntryTypeError) -> Self {
+		JecsDecodeError::WrongType(error)
+	}
+}
+
+impl From<JecsIncompatibleOrMalformedError> for JecsDecodeError {
+	fn from(error: JecsIncompatibleOrMalformedError) -> Self {
+		JecsDecodeError::Malformed(error)
+	}
+}
+
+impl From<JecsMissingKeyError> for JecsDecodeError {
+	fn from(error: JecsMissingKeyError) -> Self {
+		JecsDecodeError::MissingKey(error)
+	}
+}
+
+impl From<JecsOutOfRangeError> for JecsDecodeError {
+	fn from(error: JecsOutOfRangeError) -> Self {
+		JecsDecodeError::OutOfRange(error)
+	}
+}
+
+impl From<JecsWrongLengthError> for JecsDecodeError {
+	fn from(error: JecsWrongLengthError) -> Self {
+		JecsDecodeError::WrongLength(error)
+	}
+}
+
 // ### Wrong Entry Type ###
 
 #[derive(Debug)]
 pub struct JecsWrongEntryTypeError {
 	pub expected_type: String,
 	pub encountered_type: String,
+	pub path: Option<JecsPath>, //Entry which caused the error, if known
+}
+
+impl JecsWrongEntryTypeError {
+	pub fn error_code(&self) -> JecsErrorCode {
+		JecsErrorCode::WrongEntryType
+	}
 }
 
 impl Error for JecsWrongEntryTypeError {}
 
 impl Display for JecsWrongEntryTypeError {
-	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "[{}] ", self.error_code().code())?;
+		write_path_prefix(f, &self.path)?;
 		writeln!(f, "Expected {} JECS data type, got {}", self.expected_type, self.encountered_type)?;
 		Ok(())
 	}
@@ -26,30 +252,413 @@ impl Display for JecsWrongEntryTypeError {
 pub struct JecsIncompatibleOrMalformedError {
 	pub data_type: String,
 	pub value: String,
+	pub path: Option<JecsPath>, //Entry which caused the error, if known
+}
+
+impl JecsIncompatibleOrMalformedError {
+	pub fn error_code(&self) -> JecsErrorCode {
+		JecsErrorCode::IncompatibleOrMalformed
+	}
 }
 
 impl Error for JecsIncompatibleOrMalformedError {}
 
 impl Display for JecsIncompatibleOrMalformedError {
-	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "[{}] ", self.error_code().code())?;
+		write_path_prefix(f, &self.path)?;
 		writeln!(f, "Failed to parse {} data with value '{}'", self.data_type, self.value)?;
 		Ok(())
 	}
 }
 
+// ### Missing Key ###
+
+#[derive(Debug)]
+pub struct JecsMissingKeyError {
+	pub key: String,
+	pub available_keys: Vec<String>, //Keys of the map, sorted
+	pub suggestion: Option<String>, //Existing key which is similar to the missing one, likely a typo
+	pub path: Option<JecsPath>, //Entry which caused the error, if known
+}
+
+impl JecsMissingKeyError {
+	pub fn error_code(&self) -> JecsErrorCode {
+		JecsErrorCode::KeyNotFound
+	}
+}
+
+impl Error for JecsMissingKeyError {}
+
+impl Display for JecsMissingKeyError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "[{}] ", self.error_code().code())?;
+		write_path_prefix(f, &self.path)?;
+		write!(f, "Missing key '{}'", self.key)?;
+		if let Some(suggestion) = &self.suggestion {
+			write!(f, ", did you mean '{}'?", suggestion)?;
+		} else if !self.available_keys.is_empty() && self.available_keys.len() <= 8 {
+			write!(f, ", available keys: {}", self.available_keys.join(", "))?;
+		}
+		writeln!(f)?;
+		Ok(())
+	}
+}
+
+// ### Out Of Range ###
+
+#[derive(Debug)]
+pub struct JecsOutOfRangeError {
+	pub data_type: String,
+	pub value: String,
+	pub path: Option<JecsPath>, //Entry which caused the error, if known
+}
+
+impl JecsOutOfRangeError {
+	pub fn error_code(&self) -> JecsErrorCode {
+		JecsErrorCode::OutOfRange
+	}
+}
+
+impl Error for JecsOutOfRangeError {}
+
+impl Display for JecsOutOfRangeError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "[{}] ", self.error_code().code())?;
+		write_path_prefix(f, &self.path)?;
+		writeln!(f, "Value '{}' is out of range for {} data", self.value, self.data_type)?;
+		Ok(())
+	}
+}
+
+// ### Wrong Length ###
+
+//Lists which are read as tuple need an exact amount of entries.
+#[derive(Debug)]
+pub struct JecsWrongLengthError {
+	pub expected_length: usize,
+	pub encountered_length: usize,
+	pub path: Option<JecsPath>, //Entry which caused the error, if known
+}
+
+impl JecsWrongLengthError {
+	pub fn error_code(&self) -> JecsErrorCode {
+		JecsErrorCode::WrongLength
+	}
+}
+
+impl Error for JecsWrongLengthError {}
+
+impl Display for JecsWrongLengthError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "[{}] ", self.error_code().code())?;
+		write_path_prefix(f, &self.path)?;
+		writeln!(f, "Expected list with {} entries, got {}", self.expected_length, self.encountered_length)?;
+		Ok(())
+	}
+}
+
 // ###### Parsing Errors ######
 
 #[derive(Debug)]
 pub struct JecsCorruptedDataError {
 	pub row: usize,
+	pub column: usize, //Byte offset within the row
+	pub code: JecsErrorCode,
 	pub description: String,
 }
 
+impl JecsCorruptedDataError {
+	pub fn error_code(&self) -> JecsErrorCode {
+		self.code
+	}
+}
+
 impl Error for JecsCorruptedDataError {}
 
 impl Display for JecsCorruptedDataError {
-	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		writeln!(f, "JECS file is corrupted. Line {}: {}", self.row, self.description)?;
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "[{}] JECS file is corrupted. Line {}: {}", self.code.code(), self.row, self.description)?;
+		Ok(())
+	}
+}
+
+// ###### Parsing Warnings ######
+
+//Problems the parser could work around, the file was still parsed.
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub struct JecsWarning {
+	pub row: usize,
+	pub column: usize, //Byte offset within the row
+	pub code: JecsErrorCode,
+	pub description: String,
+}
+
+impl JecsWarning {
+	pub fn error_code(&self) -> JecsErrorCode {
+		self.code
+	}
+}
+
+impl Display for JecsWarning {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "[{}] JECS file warning. Line {}: {}", self.code.code(), self.row, self.description)?;
 		Ok(())
 	}
 }
+
+// ###### Writing Errors ######
+
+#[derive(Debug)]
+pub struct JecsWriteError {
+	pub path: JecsPath,
+	pub description: String,
+}
+
+impl Error for JecsWriteError {}
+
+impl Display for JecsWriteError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "Cannot write JECS entry '{}': {}", self.path, self.description)?;
+		Ok(())
+	}
+}
+
+// ###### Formatting Errors ######
+
+//Everything that can go wrong when formatting JECS text.
+#[derive(Debug)]
+pub enum JecsFormatError {
+	Corrupted(JecsCorruptedDataError),
+	InvalidOptions(String),
+}
+
+impl Error for JecsFormatError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			JecsFormatError::Corrupted(error) => Some(error),
+			JecsFormatError::InvalidOptions(_) => None,
+		}
+	}
+}
+
+impl Display for JecsFormatError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			JecsFormatError::Corrupted(error) => Display::fmt(error, f),
+			JecsFormatError::InvalidOptions(description) => writeln!(f, "Cannot format JECS text: {}", description),
+		}
+	}
+}
+
+impl From<JecsCorruptedDataError> for JecsFormatError {
+	fn from(error: JecsCorruptedDataError) -> Self {
+		JecsFormatError::Corrupted(error)
+	}
+}
+
+// ###### Cache Errors ######
+
+#[derive(Debug)]
+pub struct JecsCacheError {
+	pub description: String,
+}
+
+impl Error for JecsCacheError {}
+
+impl Display for JecsCacheError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "Cannot load JECS cache: {}", self.description)?;
+		Ok(())
+	}
+}
+
+// ###### Loading Errors ######
+
+//Everything that can go wrong when reading a file into a typed value.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum JecsLoadError {
+	Io(std::io::Error),
+	Encoding(core::str::Utf8Error),
+	Corrupted(JecsCorruptedDataError),
+	Decode(JecsDecodeError),
+	Invalid(String), //Rejected by a validation of the decoded value
+}
+
+#[cfg(feature = "std")]
+impl Error for JecsLoadError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			JecsLoadError::Io(error) => Some(error),
+			JecsLoadError::Encoding(error) => Some(error),
+			JecsLoadError::Corrupted(error) => Some(error),
+			JecsLoadError::Decode(error) => Some(error),
+			JecsLoadError::Invalid(_) => None,
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl Display for JecsLoadError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			JecsLoadError::Io(error) => writeln!(f, "Cannot read JECS file: {}", error),
+			JecsLoadError::Encoding(error) => writeln!(f, "JECS file is not valid UTF-8: {}", error),
+			JecsLoadError::Corrupted(error) => Display::fmt(error, f),
+			JecsLoadError::Decode(error) => Display::fmt(error, f),
+			JecsLoadError::Invalid(description) => writeln!(f, "JECS file content is invalid: {}", description),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for JecsLoadError {
+	fn from(error: std::io::Error) -> Self {
+		JecsLoadError::Io(error)
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<core::str::Utf8Error> for JecsLoadError {
+	fn from(error: core::str::Utf8Error) -> Self {
+		JecsLoadError::Encoding(error)
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<JecsCorruptedDataError> for JecsLoadError {
+	fn from(error: JecsCorruptedDataError) -> Self {
+		JecsLoadError::Corrupted(error)
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<JecsDecodeError> for JecsLoadError {
+	fn from(error: JecsDecodeError) -> Self {
+		JecsLoadError::Decode(error)
+	}
+}
+
+// ###### Saving Errors ######
+
+//Everything that can go wrong when writing a tree into a file.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum JecsSaveError {
+	Io(std::io::Error),
+	Write(JecsWriteError),
+}
+
+#[cfg(feature = "std")]
+impl Error for JecsSaveError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			JecsSaveError::Io(error) => Some(error),
+			JecsSaveError::Write(error) => Some(error),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl Display for JecsSaveError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			JecsSaveError::Io(error) => writeln!(f, "Cannot write JECS file: {}", error),
+			JecsSaveError::Write(error) => Display::fmt(error, f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for JecsSaveError {
+	fn from(error: std::io::Error) -> Self {
+		JecsSaveError::Io(error)
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<JecsWriteError> for JecsSaveError {
+	fn from(error: JecsWriteError) -> Self {
+		JecsSaveError::Write(error)
+	}
+}
+
+// ###### Conversion Errors ######
+
+//Content of another format, which cannot be turned into a JECS tree.
+#[derive(Debug)]
+pub struct JecsConvertError {
+	pub format: &'static str,
+	pub row: usize, //Starts at 1, 0 if the problem is not on a specific line
+	pub description: String,
+}
+
+impl Error for JecsConvertError {}
+
+impl Display for JecsConvertError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		if self.row == 0 {
+			writeln!(f, "Cannot convert {} to JECS: {}", self.format, self.description)?;
+		} else {
+			writeln!(f, "Cannot convert {} to JECS. Line {}: {}", self.format, self.row, self.description)?;
+		}
+		Ok(())
+	}
+}
+
+// ###### Serde Errors ######
+
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct JecsSerdeError {
+	pub description: String,
+}
+
+#[cfg(feature = "serde")]
+impl Error for JecsSerdeError {}
+
+#[cfg(feature = "serde")]
+impl Display for JecsSerdeError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "JECS (de)serialization failed: {}", self.description)?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for JecsSerdeError {
+	fn custom<T: Display>(message: T) -> Self {
+		Self {
+			description: message.to_string(),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for JecsSerdeError {
+	fn custom<T: Display>(message: T) -> Self {
+		Self {
+			description: message.to_string(),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl From<JecsCorruptedDataError> for JecsSerdeError {
+	fn from(error: JecsCorruptedDataError) -> Self {
+		Self {
+			description: format!("[{}] Line {}: {}", error.code.code(), error.row, error.description),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl From<JecsWriteError> for JecsSerdeError {
+	fn from(error: JecsWriteError) -> Self {
+		Self {
+			description: format!("Entry '{}': {}", error.path, error.description),
+		}
+	}
+}
@@ -0,0 +1,189 @@
+//C interface, declared in 'include/ecc_jecs.h'. Build the library with '--crate-type cdylib' or 'staticlib' to link against it.
+//Trees and strings handed out by this module are owned by the caller and must be released with the matching free function.
+
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+use crate::parser;
+use crate::types::JecsType;
+use crate::writer;
+
+//Node kinds as returned by jecs_node_kind(), must match the header.
+pub const JECS_KIND_ANY: i32 = 0;
+pub const JECS_KIND_VALUE: i32 = 1;
+pub const JECS_KIND_MAP: i32 = 2;
+pub const JECS_KIND_LIST: i32 = 3;
+pub const JECS_KIND_NULL: i32 = 4;
+
+/// Parses JECS text into a tree. Returns null on failure, in that case `error_out` (if not null) receives a message, which must be released with `jecs_free_string`.
+///
+/// # Safety
+/// `text` must be a valid null-terminated string, `error_out` must be null or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_parse(text: *const c_char, error_out: *mut *mut c_char) -> *mut JecsType {
+	if text.is_null() {
+		set_error(error_out, "Text is null");
+		return ptr::null_mut();
+	}
+	let text = match CStr::from_ptr(text).to_str() {
+		Ok(text) => text,
+		Err(error) => {
+			set_error(error_out, &error.to_string());
+			return ptr::null_mut();
+		}
+	};
+	match parser::parse_jecs_string(text) {
+		Ok(map) => Box::into_raw(Box::new(JecsType::Map(map))),
+		Err(error) => {
+			set_error(error_out, error.to_string().trim_end());
+			ptr::null_mut()
+		}
+	}
+}
+
+/// Releases a tree returned by `jecs_parse`. Nodes obtained from it become invalid.
+///
+/// # Safety
+/// `tree` must be null or a pointer returned by `jecs_parse`, which was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_free(tree: *mut JecsType) {
+	if !tree.is_null() {
+		drop(Box::from_raw(tree));
+	}
+}
+
+/// Returns the node at a path like "MainInfo.Components[3]" or null if there is none. The node is borrowed from its tree.
+///
+/// # Safety
+/// `node` must be null or a valid node, `path` must be null or a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_get_path(node: *const JecsType, path: *const c_char) -> *const JecsType {
+	if node.is_null() || path.is_null() {
+		return ptr::null();
+	}
+	let path = match CStr::from_ptr(path).to_str() {
+		Ok(path) => path,
+		Err(_) => return ptr::null(),
+	};
+	match (*node).get_path(path) {
+		Some(child) => child,
+		None => ptr::null(),
+	}
+}
+
+/// Returns the kind of the node (`JECS_KIND_*`) or -1 for null.
+///
+/// # Safety
+/// `node` must be null or a valid node.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_node_kind(node: *const JecsType) -> i32 {
+	if node.is_null() {
+		return -1;
+	}
+	match &*node {
+		JecsType::Any() => JECS_KIND_ANY,
+		JecsType::Value(_) => JECS_KIND_VALUE,
+		JecsType::Map(_) => JECS_KIND_MAP,
+		JecsType::List(_) => JECS_KIND_LIST,
+		JecsType::Null() => JECS_KIND_NULL,
+	}
+}
+
+/// Returns the amount of children of a map or list node, 0 otherwise.
+///
+/// # Safety
+/// `node` must be null or a valid node.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_child_count(node: *const JecsType) -> usize {
+	if node.is_null() {
+		return 0;
+	}
+	match &*node {
+		JecsType::Map(map) => map.len(),
+		JecsType::List(list) => list.len(),
+		_ => 0,
+	}
+}
+
+/// Returns the list entry at the index, or null if the node is no list or the index is out of bounds. The entry is borrowed from its tree.
+///
+/// # Safety
+/// `node` must be null or a valid node.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_list_get(node: *const JecsType, index: usize) -> *const JecsType {
+	if node.is_null() {
+		return ptr::null();
+	}
+	match (*node).get_list().and_then(|list| list.get(index)) {
+		Some(entry) => entry,
+		None => ptr::null(),
+	}
+}
+
+/// Returns a copy of the key of the n-th map entry, for iterating maps together with `jecs_child_count`. Must be released with `jecs_free_string`.
+/// The order is arbitrary, but stays the same as long as the map is not modified.
+///
+/// # Safety
+/// `node` must be null or a valid node.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_map_key(node: *const JecsType, index: usize) -> *mut c_char {
+	if node.is_null() {
+		return ptr::null_mut();
+	}
+	match (*node).get_map().and_then(|map| map.keys().nth(index)) {
+		Some(key) => to_c_string(key),
+		None => ptr::null_mut(),
+	}
+}
+
+/// Returns a copy of the value of a value node, or null for other nodes. Must be released with `jecs_free_string`.
+///
+/// # Safety
+/// `node` must be null or a valid node.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_get_value(node: *const JecsType) -> *mut c_char {
+	if node.is_null() {
+		return ptr::null_mut();
+	}
+	match (*node).get_value() {
+		Some(value) => to_c_string(value),
+		None => ptr::null_mut(),
+	}
+}
+
+/// Writes the tree as JECS text, returns null if it cannot be written. Must be released with `jecs_free_string`.
+///
+/// # Safety
+/// `tree` must be null or a valid node.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_write(tree: *const JecsType) -> *mut c_char {
+	if tree.is_null() {
+		return ptr::null_mut();
+	}
+	match (*tree).get_map().map(writer::write_jecs_string) {
+		Some(Ok(text)) => to_c_string(&text),
+		_ => ptr::null_mut(),
+	}
+}
+
+/// Releases a string returned by this library.
+///
+/// # Safety
+/// `text` must be null or a string returned by this library, which was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn jecs_free_string(text: *mut c_char) {
+	if !text.is_null() {
+		drop(CString::from_raw(text));
+	}
+}
+
+fn to_c_string(text: &str) -> *mut c_char {
+	//JECS text cannot contain null characters in a meaningful way, replace them instead of failing:
+	CString::new(text.replace('\0', "\u{FFFD}")).unwrap().into_raw()
+}
+
+unsafe fn set_error(error_out: *mut *mut c_char, message: &str) {
+	if !error_out.is_null() {
+		*error_out = to_c_string(message);
+	}
+}
@@ -0,0 +1,151 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::errors::JecsFormatError;
+use crate::lexer::{JecsLexer, JecsToken, JecsTokenKind};
+use crate::parser::validate_jecs_string;
+use crate::writer::INDENTATION_STEP;
+
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct FormatOptions {
+	//Spaces per indentation level, at least 1 as children would otherwise become siblings.
+	pub indentation: usize,
+	//Lines with a comment after their entry get their comments aligned with the surrounding lines.
+	pub align_comments: bool,
+}
+
+impl Default for FormatOptions {
+	fn default() -> Self {
+		Self {
+			indentation: INDENTATION_STEP,
+			align_comments: false,
+		}
+	}
+}
+
+//Rewrites valid JECS text with consistent indentation and 'key: value # comment' spacing. Keys, values (as written),
+// comments, empty lines and the order of lines stay the same. Comment lines get the indentation of the entry after them.
+pub fn format_jecs_string(text: &str, options: &FormatOptions) -> Result<String, JecsFormatError> {
+	if options.indentation == 0 {
+		return Err(JecsFormatError::InvalidOptions("Indentation has to be at least 1 space".to_string()));
+	}
+	let body = text.strip_prefix('\u{feff}').unwrap_or(text);
+	if let Err(errors) = validate_jecs_string(body) {
+		return Err(errors.into_iter().next().unwrap().into());
+	}
+	
+	let mut lines: Vec<FormattedLine> = Vec::new();
+	let mut pending_lines = Vec::new(); //Comment and empty lines, waiting for the indentation of the next entry
+	let mut levels: Vec<usize> = Vec::new(); //Original indentation of the parents and the last entry
+	let mut string_indentation = None; //Set while in a multi-line string
+	let mut tokens = JecsLexer::new(body).peekable();
+	for (index, original_line) in body.lines().enumerate() {
+		let mut line_tokens = Vec::new();
+		while let Some(token) = tokens.next_if(|token| token.row == index + 1) {
+			if token.kind != JecsTokenKind::Indent {
+				line_tokens.push(token);
+			}
+		}
+		let indentation = original_line.len() - original_line.trim_start_matches(' ').len();
+		
+		if let Some(new_indentation) = string_indentation {
+			//Lines of multi-line strings, only their indentation changes:
+			if line_tokens.first().is_some_and(|token| token.kind == JecsTokenKind::MultilineEnd) {
+				string_indentation = None;
+			}
+			let mut line = FormattedLine::default();
+			if !line_tokens.is_empty() {
+				line.code.push_str(&" ".repeat(new_indentation));
+				push_tokens(&mut line, &line_tokens);
+			}
+			lines.push(line);
+			continue;
+		}
+		if line_tokens.iter().all(|token| token.kind == JecsTokenKind::Comment) {
+			pending_lines.push(line_tokens.first().map(|token| token.text));
+			continue;
+		}
+		
+		//Entry line, find its level like the parser does:
+		while levels.last().is_some_and(|level| *level > indentation) {
+			levels.pop();
+		}
+		if levels.last() != Some(&indentation) {
+			levels.push(indentation);
+		}
+		let new_indentation = (levels.len() - 1) * options.indentation;
+		flush_pending_lines(&mut lines, &mut pending_lines, new_indentation);
+		let mut line = FormattedLine::default();
+		line.code.push_str(&" ".repeat(new_indentation));
+		push_tokens(&mut line, &line_tokens);
+		if line_tokens.iter().any(|token| token.kind == JecsTokenKind::MultilineStart) {
+			string_indentation = Some(new_indentation + options.indentation);
+		}
+		lines.push(line);
+	}
+	flush_pending_lines(&mut lines, &mut pending_lines, 0);
+	
+	if options.align_comments {
+		align_comments(&mut lines);
+	}
+	let mut output = String::with_capacity(text.len());
+	if body.len() != text.len() {
+		output.push('\u{feff}');
+	}
+	for line in lines {
+		output.push_str(&line.code);
+		if let Some(comment) = line.comment {
+			output.push_str(&" ".repeat(line.comment_padding));
+			output.push_str(comment);
+		}
+		output.push('\n');
+	}
+	Ok(output)
+}
+
+#[derive(Default)]
+struct FormattedLine<'a> {
+	code: String,
+	//Comment after the code, comment lines have it as code instead:
+	comment: Option<&'a str>,
+	comment_padding: usize,
+}
+
+fn push_tokens<'a>(line: &mut FormattedLine<'a>, tokens: &[JecsToken<'a>]) {
+	for token in tokens {
+		match token.kind {
+			JecsTokenKind::Colon => line.code.push(':'),
+			JecsTokenKind::Value | JecsTokenKind::MultilineStart if !line.code.trim_start().is_empty() => {
+				line.code.push(' ');
+				line.code.push_str(token.text);
+			}
+			JecsTokenKind::Comment if !line.code.trim_start().is_empty() => {
+				line.comment = Some(token.text);
+				line.comment_padding = 1;
+			}
+			_ => line.code.push_str(token.text),
+		}
+	}
+}
+
+fn flush_pending_lines<'a>(lines: &mut Vec<FormattedLine<'a>>, pending_lines: &mut Vec<Option<&'a str>>, indentation: usize) {
+	for comment in pending_lines.drain(..) {
+		let mut line = FormattedLine::default();
+		if let Some(comment) = comment {
+			line.code.push_str(&" ".repeat(indentation));
+			line.code.push_str(comment);
+		}
+		lines.push(line);
+	}
+}
+
+//Consecutive lines with comments after their code get the same comment column.
+fn align_comments(lines: &mut [FormattedLine]) {
+	for group in lines.chunk_by_mut(|a, b| a.comment.is_some() && b.comment.is_some()) {
+		let width = group.iter().map(|line| line.code.chars().count()).max().unwrap_or(0);
+		for line in group.iter_mut().filter(|line| line.comment.is_some()) {
+			line.comment_padding = width - line.code.chars().count() + 1;
+		}
+	}
+}
@@ -0,0 +1,127 @@
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use crate::decode::FromJecs;
+use crate::encode::IntoJecs;
+use crate::errors::JecsDecodeError;
+use crate::types::{malformed, JecsType};
+
+//A key binding, written as modifiers and the input joined by '+': "Ctrl+Shift+K", "Alt+Mouse1", "Gamepad.LeftTrigger"
+//Modifiers are 'Ctrl' (or 'Control'), 'Shift' and 'Alt', in any order and case. Display writes them in that order.
+#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub struct KeyBind {
+	pub ctrl: bool,
+	pub shift: bool,
+	pub alt: bool,
+	pub input: KeyBindInput,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum KeyBindInput {
+	Key(String), //Name of a keyboard key, single letters are upper case: "K", "Space", "F5"
+	Mouse(u8), //"Mouse0" to "Mouse9", "MouseLeft", "MouseRight" and "MouseMiddle" are 0, 1 and 2
+	Gamepad(String), //Name of the button or axis after "Gamepad.": "Gamepad.A"
+}
+
+impl KeyBind {
+	pub fn key(name: &str) -> Self {
+		Self {
+			ctrl: false,
+			shift: false,
+			alt: false,
+			input: KeyBindInput::Key(name.to_string()),
+		}
+	}
+}
+
+impl FromStr for KeyBind {
+	type Err = JecsDecodeError;
+	
+	fn from_str(text: &str) -> Result<Self, JecsDecodeError> {
+		let error = || malformed("key binding", text);
+		let mut parts = text.split('+');
+		let input = parts.next_back().unwrap(); //Splitting always results in at least one part
+		let mut bind = KeyBind {
+			ctrl: false,
+			shift: false,
+			alt: false,
+			input: parse_input(input.trim()).ok_or_else(error)?,
+		};
+		for modifier in parts {
+			let flag = match &modifier.trim().to_lowercase()[..] {
+				"ctrl" | "control" => &mut bind.ctrl,
+				"shift" => &mut bind.shift,
+				"alt" => &mut bind.alt,
+				_ => return Err(error()),
+			};
+			if *flag {
+				//Same modifier twice
+				return Err(error());
+			}
+			*flag = true;
+		}
+		Ok(bind)
+	}
+}
+
+impl Display for KeyBind {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		if self.ctrl {
+			f.write_str("Ctrl+")?;
+		}
+		if self.shift {
+			f.write_str("Shift+")?;
+		}
+		if self.alt {
+			f.write_str("Alt+")?;
+		}
+		match &self.input {
+			KeyBindInput::Key(name) => f.write_str(name),
+			KeyBindInput::Mouse(button) => write!(f, "Mouse{}", button),
+			KeyBindInput::Gamepad(name) => write!(f, "Gamepad.{}", name),
+		}
+	}
+}
+
+impl FromJecs for KeyBind {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		entry.expect_keybind()
+	}
+}
+
+impl IntoJecs for KeyBind {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::value(self.to_string())
+	}
+}
+
+impl JecsType {
+	pub fn expect_keybind(&self) -> Result<KeyBind, JecsDecodeError> {
+		self.expect_string()?.parse()
+	}
+}
+
+fn parse_input(text: &str) -> Option<KeyBindInput> {
+	if text.is_empty() || text.contains(char::is_whitespace) {
+		return None;
+	}
+	if let Some(name) = text.strip_prefix("Gamepad.") {
+		return (!name.is_empty()).then(|| KeyBindInput::Gamepad(name.to_string()));
+	}
+	if let Some(button) = text.strip_prefix("Mouse") {
+		return match button {
+			"Left" => Some(KeyBindInput::Mouse(0)),
+			"Right" => Some(KeyBindInput::Mouse(1)),
+			"Middle" => Some(KeyBindInput::Mouse(2)),
+			_ if button.len() == 1 => button.parse().ok().map(KeyBindInput::Mouse),
+			_ => None,
+		};
+	}
+	if text.chars().count() == 1 {
+		return Some(KeyBindInput::Key(text.to_uppercase()));
+	}
+	Some(KeyBindInput::Key(text.to_string()))
+}
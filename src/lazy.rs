@@ -0,0 +1,142 @@
+use alloc::string::{String, ToString};
+use core::cell::OnceCell;
+use core::ops::Range;
+
+use crate::errors::JecsCorruptedDataError;
+use crate::parser::{parse_jecs_string_with_options, ParserOptions};
+use crate::types::{HashMap, JecsMap, JecsType};
+
+//A JECS file, of which only the root keys are known. The entries below a root key get parsed when they are accessed first.
+//Lines without indentation always start a root entry, thus finding them only needs a scan over the line starts.
+//Most errors are only found when the broken entry is accessed, their rows are still relative to the whole text.
+pub struct JecsLazyDocument {
+	text: String,
+	options: ParserOptions,
+	sections: HashMap<String, LazySection>,
+}
+
+struct LazySection {
+	bytes: Range<usize>,
+	first_row: usize,
+	parsed: OnceCell<Result<JecsType, JecsCorruptedDataError>>,
+}
+
+impl JecsLazyDocument {
+	pub fn parse(text: &str) -> Result<Self, JecsCorruptedDataError> {
+		Self::parse_with_options(text, &ParserOptions::default())
+	}
+	
+	pub fn parse_with_options(text: &str, options: &ParserOptions) -> Result<Self, JecsCorruptedDataError> {
+		let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+		let mut document = Self {
+			text: text.to_string(),
+			options: options.clone(),
+			sections: HashMap::default(),
+		};
+		//Root line of the current section: Row, byte offset and key.
+		let mut current: Option<(usize, usize, Option<&str>)> = None;
+		let mut offset = 0;
+		for (index, line) in text.split_inclusive('\n').enumerate() {
+			if line.starts_with(|c: char| !matches!(c, ' ' | '#' | '\r' | '\n')) {
+				if let Some(section) = current.take() {
+					document.add_section(section, offset)?;
+				}
+				current = Some((index + 1, offset, root_key(line)));
+			} else if current.is_none() && !line.trim_start_matches(' ').trim_end().is_empty() && !line.trim_start().starts_with('#') {
+				//Indented entries in front of the first root entry are only accepted by lenient parsing:
+				current = Some((index + 1, offset, None));
+			}
+			offset += line.len();
+		}
+		if let Some(section) = current {
+			document.add_section(section, text.len())?;
+		}
+		Ok(document)
+	}
+	
+	fn add_section(&mut self, (first_row, start, key): (usize, usize, Option<&str>), end: usize) -> Result<(), JecsCorruptedDataError> {
+		let section = LazySection {
+			bytes: start..end,
+			first_row,
+			parsed: OnceCell::new(),
+		};
+		match key {
+			//Like in a parsed map, the last entry with the same key wins:
+			Some(key) => {
+				self.sections.insert(key.to_string(), section);
+			}
+			None => {
+				//The root line is broken or indented, parse now to find out. Lenient parsing might even turn it into multiple root entries:
+				for (key, entry) in section.parse(&self.text, &self.options)? {
+					self.sections.insert(key, LazySection {
+						bytes: section.bytes.clone(),
+						first_row,
+						parsed: OnceCell::from(Ok(entry)),
+					});
+				}
+			}
+		}
+		Ok(())
+	}
+	
+	pub fn keys(&self) -> impl Iterator<Item = &str> {
+		self.sections.keys().map(String::as_str)
+	}
+	
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.sections.contains_key(key)
+	}
+	
+	//If the entries of the section were not parsed before, they get parsed now.
+	pub fn get(&self, key: &str) -> Result<Option<&JecsType>, &JecsCorruptedDataError> {
+		let section = match self.sections.get(key) {
+			None => return Ok(None),
+			Some(section) => section,
+		};
+		let parsed = section.parsed.get_or_init(|| section.parse(&self.text, &self.options).map(section_entry));
+		parsed.as_ref().map(Some)
+	}
+	
+	pub fn is_parsed(&self, key: &str) -> bool {
+		self.sections.get(key).is_some_and(|section| section.parsed.get().is_some())
+	}
+	
+	//Parses all remaining sections.
+	pub fn into_jecs_map(self) -> Result<JecsMap, JecsCorruptedDataError> {
+		let mut map = JecsMap::with_capacity_and_hasher(self.sections.len(), Default::default());
+		for (key, section) in self.sections {
+			let parsed = match section.parsed.get() {
+				Some(_) => section.parsed.into_inner().unwrap(),
+				None => section.parse(&self.text, &self.options).map(section_entry),
+			};
+			map.insert(key, parsed?);
+		}
+		Ok(map)
+	}
+}
+
+impl LazySection {
+	fn parse(&self, text: &str, options: &ParserOptions) -> Result<JecsMap, JecsCorruptedDataError> {
+		parse_jecs_string_with_options(&text[self.bytes.clone()], options).map_err(|mut error| {
+			error.row += self.first_row - 1;
+			error
+		})
+	}
+}
+
+//A section contains exactly one root entry.
+fn section_entry(map: JecsMap) -> JecsType {
+	map.into_values().next().unwrap_or_default()
+}
+
+//The key of a root line, if the line is a valid one.
+fn root_key(line: &str) -> Option<&str> {
+	if line.starts_with('-') {
+		return None;
+	}
+	let end = line.find([':', '#'])?;
+	if end == 0 || !line[end..].starts_with(':') {
+		return None;
+	}
+	Some(line[..end].trim_end_matches(' '))
+}
@@ -0,0 +1,165 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::str::SplitInclusive;
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum JecsTokenKind {
+	Indent, //Leading spaces of a line
+	Key, //Without the spaces in front of the ':'
+	Colon,
+	ListDash,
+	Value, //As written, thus with escapes like '\#'. Also every line of a multi-line string
+	Comment, //From the '#' to the end of the line
+	MultilineStart, //The '"""' in place of a value
+	MultilineEnd, //The '"""' terminating a multi-line string
+	Invalid, //Rest of a line, which the parser would reject
+}
+
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub struct JecsToken<'a> {
+	pub kind: JecsTokenKind,
+	pub row: usize, //Starting at 1, like in parser errors
+	pub columns: Range<usize>, //Byte offsets within the row
+	pub offsets: Range<usize>, //Byte offsets within the whole text
+	pub text: &'a str,
+}
+
+//Splits JECS text into tokens, following the same rules as the parser. Spaces between tokens are skipped.
+//The lexer never fails: Lines the parser would reject end with an Invalid token, the structure (indentation levels) is not checked.
+pub struct JecsLexer<'a> {
+	lines: SplitInclusive<'a, char>,
+	row: usize,
+	line_offset: usize,
+	tokens: VecDeque<JecsToken<'a>>,
+	in_multi_line_string: bool,
+}
+
+impl<'a> JecsLexer<'a> {
+	pub fn new(text: &'a str) -> Self {
+		Self {
+			lines: text.split_inclusive('\n'),
+			row: 0,
+			line_offset: 0,
+			tokens: VecDeque::new(),
+			in_multi_line_string: false,
+		}
+	}
+	
+	fn lex_line(&mut self, row: usize, line: &'a str) {
+		let indentation = line.len() - line.trim_start_matches(' ').len();
+		if indentation != 0 {
+			self.push(JecsTokenKind::Indent, row, line, 0..indentation);
+		}
+		let mut position = indentation;
+		if self.in_multi_line_string {
+			//Comments are also removed from the lines of multi-line strings:
+			let end = value_end(line, position);
+			if &line[position..end] == "\"\"\"" {
+				self.push(JecsTokenKind::MultilineEnd, row, line, position..end);
+				self.in_multi_line_string = false;
+			} else if end != position {
+				self.push(JecsTokenKind::Value, row, line, position..end);
+			}
+			position = end;
+		} else if !line[position..].starts_with('#') && position != line.len() {
+			let rest = &line[position..];
+			if rest.starts_with('-') {
+				self.push(JecsTokenKind::ListDash, row, line, position..position + 1);
+				position += 1;
+			} else {
+				match rest.find([':', '#']) {
+					Some(end) if end != 0 && rest[end..].starts_with(':') => {
+						let key_end = position + rest[..end].trim_end_matches(' ').len();
+						self.push(JecsTokenKind::Key, row, line, position..key_end);
+						self.push(JecsTokenKind::Colon, row, line, position + end..position + end + 1);
+						position += end + 1;
+					}
+					_ => {
+						//Missing key, missing ':' or '#' within the key:
+						return self.push(JecsTokenKind::Invalid, row, line, position..line.len());
+					}
+				}
+			}
+			position = skip_spaces(line, position);
+			let end = value_end(line, position);
+			if end != position {
+				let kind = if &line[position..end] == "\"\"\"" {
+					self.in_multi_line_string = true;
+					JecsTokenKind::MultilineStart
+				} else {
+					JecsTokenKind::Value
+				};
+				self.push(kind, row, line, position..end);
+			}
+			position = end;
+		}
+		position = skip_spaces(line, position);
+		if position != line.len() {
+			self.push(JecsTokenKind::Comment, row, line, position..line.len());
+		}
+	}
+	
+	fn push(&mut self, kind: JecsTokenKind, row: usize, line: &'a str, columns: Range<usize>) {
+		self.tokens.push_back(JecsToken {
+			kind,
+			row,
+			text: &line[columns.clone()],
+			offsets: self.line_offset + columns.start..self.line_offset + columns.end,
+			columns,
+		});
+	}
+}
+
+impl<'a> Iterator for JecsLexer<'a> {
+	type Item = JecsToken<'a>;
+	
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(token) = self.tokens.pop_front() {
+				return Some(token);
+			}
+			let line = self.lines.next()?;
+			//Same line endings as str::lines():
+			let content = match line.strip_suffix('\n') {
+				None => line,
+				Some(content) => content.strip_suffix('\r').unwrap_or(content),
+			};
+			self.row += 1;
+			self.lex_line(self.row, content);
+			self.line_offset += line.len();
+		}
+	}
+}
+
+pub fn tokenize(text: &str) -> Vec<JecsToken<'_>> {
+	JecsLexer::new(text).collect()
+}
+
+//Byte ranges within the text to color, the indentation and spaces between tokens are left out.
+pub fn highlight(text: &str) -> Vec<(Range<usize>, JecsTokenKind)> {
+	JecsLexer::new(text)
+		.filter(|token| token.kind != JecsTokenKind::Indent)
+		.map(|token| (token.offsets, token.kind))
+		.collect()
+}
+
+fn skip_spaces(line: &str, position: usize) -> usize {
+	line.len() - line[position..].trim_start_matches(' ').len()
+}
+
+//End of the value starting at the position: In front of the first unescaped '#' and without trailing spaces.
+fn value_end(line: &str, position: usize) -> usize {
+	let mut end = line.len();
+	let mut previous = None;
+	for (index, c) in line[position..].char_indices() {
+		if c == '#' && previous != Some('\\') {
+			end = position + index;
+			break;
+		}
+		previous = Some(c);
+	}
+	position + line[position..end].trim_end_matches(' ').len()
+}
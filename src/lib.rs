@@ -1,4 +1,56 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "hashbrown")))]
+compile_error!("Without the 'std' feature, the 'hashbrown' feature is required, as it provides the map type.");
+
 pub mod errors;
 pub mod types;
 pub mod parser;
+#[cfg(feature = "std")]
 pub mod debug;
+pub mod path;
+pub mod query;
+pub mod merge;
+pub mod diff;
+pub mod schema;
+pub mod codec;
+pub mod writer;
+pub mod stats;
+pub mod redact;
+pub mod spanned;
+pub mod document;
+pub mod diagnostic;
+pub mod lint;
+pub mod decode;
+pub mod encode;
+pub mod convert;
+pub mod color;
+pub mod keybind;
+pub mod lexer;
+pub mod format;
+pub mod canonical;
+pub mod cache;
+pub mod lazy;
+pub mod borrowed;
+pub mod shared;
+pub mod logicworld;
+#[cfg(feature = "std")]
+pub mod cell;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod data_file;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_support;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "arena")]
+pub mod arena;
@@ -0,0 +1,175 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::document::JecsDocument;
+use crate::errors::{JecsDecodeError, JecsErrorCode, JecsWarning};
+use crate::spanned::{JecsSpannedEntry, JecsSpannedType};
+use crate::types::malformed;
+
+//Checks the indentation of all parents against the step most of the file uses, it is the parsed text that is checked, not the tree.
+//Parents whose children use another step, or which skip levels by indenting their children by a multiple of the step, get one warning at their first child.
+//The parser only compares with the first step it encounters, which reports every entry of a well indented file after a single odd parent.
+pub fn lint_indentation(document: &JecsDocument) -> Vec<JecsWarning> {
+	//Row and column of the first child and the step of each parent with children:
+	let mut groups = Vec::new();
+	collect_groups(document.root(), &mut groups);
+	let mut step_counts: BTreeMap<usize, usize> = BTreeMap::new();
+	for (_, _, step) in &groups {
+		*step_counts.entry(*step).or_default() += 1;
+	}
+	//On equal counts the smaller step wins, as the map is ordered:
+	let common_step = match step_counts.iter().rev().max_by_key(|(_, count)| **count) {
+		None => return Vec::new(),
+		Some((step, _)) => *step,
+	};
+	let mut warnings: Vec<JecsWarning> = groups.into_iter()
+		.filter(|(_, _, step)| *step != common_step)
+		.map(|(row, column, step)| match step % common_step {
+			0 => JecsWarning {
+				row,
+				column,
+				code: JecsErrorCode::IndentationJump,
+				description: format!("Entries are indented {} levels deeper than their parent, instead of one level of {} spaces", step / common_step, common_step),
+			},
+			_ => JecsWarning {
+				row,
+				column,
+				code: JecsErrorCode::InconsistentIndentationStep,
+				description: format!("Indentation step of {} differs from the step of {} used by most of the file", step, common_step),
+			},
+		})
+		.collect();
+	warnings.sort_by_key(|warning| warning.row);
+	return warnings;
+	
+	//The root is not indented and thus has no step of its own.
+	fn collect_groups(entry: &JecsSpannedEntry, groups: &mut Vec<(usize, usize, usize)>) {
+		let children: Vec<&JecsSpannedEntry> = match &entry.value {
+			JecsSpannedType::Map(map) => map.values().collect(),
+			JecsSpannedType::List(list) => list.iter().collect(),
+			_ => return,
+		};
+		if entry.span.row != 0 {
+			//Siblings share their indentation, thus any child tells the step:
+			if let Some(first) = children.iter().min_by_key(|child| child.span.row) {
+				let indentation = first.span.key_columns.start;
+				groups.push((first.span.row, indentation, indentation - entry.span.key_columns.start));
+			}
+		}
+		for child in children {
+			collect_groups(child, groups);
+		}
+	}
+}
+
+//Naming conventions for keys. To convert keys, they are split into words at every character which is no letter or digit
+// and in front of upper case letters starting a new word. Digits stay with the word before them.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Debug)]
+pub enum KeyCase {
+	//'MaxSpeed', as Logic World uses it. Words written fully in upper case are kept as they are, like 'ID'.
+	Pascal,
+	//'max_speed'
+	Snake,
+}
+
+impl KeyCase {
+	pub fn name(&self) -> &'static str {
+		match self {
+			KeyCase::Pascal => "PascalCase",
+			KeyCase::Snake => "snake_case",
+		}
+	}
+	
+	pub fn matches(&self, key: &str) -> bool {
+		match self {
+			KeyCase::Pascal => key.starts_with(char::is_uppercase) && key.chars().all(char::is_alphanumeric),
+			KeyCase::Snake => key == self.convert(key),
+		}
+	}
+	
+	//The key in this convention, empty if the key has no letters or digits.
+	pub fn convert(&self, key: &str) -> String {
+		let words = split_words(key);
+		match self {
+			KeyCase::Pascal => words.iter().map(|word| {
+				let mut chars = word.chars();
+				chars.next().into_iter().flat_map(char::to_uppercase).chain(chars).collect::<String>()
+			}).collect(),
+			KeyCase::Snake => words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_"),
+		}
+	}
+}
+
+impl FromStr for KeyCase {
+	type Err = JecsDecodeError;
+	
+	fn from_str(text: &str) -> Result<Self, JecsDecodeError> {
+		Ok(match text {
+			"pascal" | "PascalCase" => KeyCase::Pascal,
+			"snake" | "snake_case" => KeyCase::Snake,
+			_ => return Err(malformed("key case", text)),
+		})
+	}
+}
+
+fn split_words(key: &str) -> Vec<&str> {
+	let chars: Vec<(usize, char)> = key.char_indices().collect();
+	let mut words = Vec::new();
+	let mut word_start = None;
+	for (position, (index, c)) in chars.iter().copied().enumerate() {
+		let previous = position.checked_sub(1).map(|previous| chars[previous].1);
+		let next = chars.get(position + 1).map(|(_, next)| *next);
+		if !c.is_alphanumeric() {
+			if let Some(start) = word_start.take() {
+				words.push(&key[start..index]);
+			}
+		} else if word_start.is_none() {
+			word_start = Some(index);
+		} else if c.is_uppercase() && previous.is_some_and(|previous| !previous.is_uppercase() || next.is_some_and(char::is_lowercase)) {
+			//'maxSpeed' and 'Value2Count' split in front of the upper case letter, 'HTTPServer' in front of the last one:
+			words.push(&key[word_start.unwrap()..index]);
+			word_start = Some(index);
+		}
+	}
+	if let Some(start) = word_start {
+		words.push(&key[start..]);
+	}
+	words
+}
+
+//Reports every key not following the convention, with the key converted to it as suggestion. Keys without letters or digits are skipped.
+pub fn lint_key_case(document: &JecsDocument, case: KeyCase) -> Vec<JecsWarning> {
+	let mut warnings = Vec::new();
+	check_children(document.root(), case, &mut warnings);
+	warnings.sort_by_key(|warning| (warning.row, warning.column));
+	return warnings;
+	
+	fn check_children(entry: &JecsSpannedEntry, case: KeyCase, warnings: &mut Vec<JecsWarning>) {
+		match &entry.value {
+			JecsSpannedType::Map(map) => {
+				for (key, child) in map {
+					let suggestion = case.convert(key);
+					if !case.matches(key) && !suggestion.is_empty() {
+						warnings.push(JecsWarning {
+							row: child.span.row,
+							column: child.span.key_columns.start,
+							code: JecsErrorCode::KeyCase,
+							description: format!("Key '{}' is not written in {}, rename it to '{}'", key, case.name(), suggestion),
+						});
+					}
+					check_children(child, case, warnings);
+				}
+			}
+			JecsSpannedType::List(list) => {
+				for child in list {
+					check_children(child, case, warnings);
+				}
+			}
+			_ => {}
+		}
+	}
+}
@@ -0,0 +1,57 @@
+use alloc::string::{String, ToString};
+
+use crate::errors::JecsDecodeError;
+use crate::path::JecsPath;
+use crate::types::{integer_error, malformed, HashMap, JecsType};
+
+//The numeric ID the game uses for a component type within a save.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug)]
+pub struct ComponentId(pub u16);
+
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub struct ComponentInfo {
+	pub name: String, //Text ID of the mod and component, like "MHG.AndGate"
+	pub id: ComponentId,
+	pub inputs: u32,
+	pub outputs: u32,
+}
+
+//Reads a component type registry. Each entry is either only the numeric ID, or a map with the ID and the peg counts:
+// MHG.CircuitBoard: 1
+// MHG.AndGate:
+//     ID: 2
+//     Inputs: 2
+//     Outputs: 1
+//Missing peg counts are 0. Every numeric ID may only be used once.
+pub fn parse_component_registry(tree: &JecsType) -> Result<HashMap<ComponentId, ComponentInfo>, JecsDecodeError> {
+	let map = match tree {
+		JecsType::Any() => return Ok(HashMap::default()),
+		_ => tree.expect_map()?,
+	};
+	let mut registry = HashMap::default();
+	for (name, entry) in map {
+		let path = JecsPath::root().join_key(name);
+		let (id_path, id_entry) = match entry {
+			JecsType::Value(_) => (path.clone(), entry),
+			_ => (path.join_key("ID"), entry.expect_at("ID", Ok).map_err(|error| error.at(&path))?),
+		};
+		let id = id_entry.expect_string().map_err(|error| error.at(&id_path))?;
+		let id = ComponentId(id.parse().map_err(|error| integer_error("component ID", id, &error).at(&id_path))?);
+		let count = |key: &str| match entry.get_map().and_then(|map| map.get(key)) {
+			None => Ok(0),
+			Some(count) => count.expect_unsigned().map_err(|error| error.at(path.join_key(key))),
+		};
+		let info = ComponentInfo {
+			name: name.to_string(),
+			id,
+			inputs: count("Inputs")?,
+			outputs: count("Outputs")?,
+		};
+		if registry.insert(id, info).is_some() {
+			return Err(malformed("unique component ID", &id.0.to_string()).at(id_path));
+		}
+	}
+	Ok(registry)
+}
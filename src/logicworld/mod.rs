@@ -0,0 +1,8 @@
+//Typed access to the JECS files of the game Logic World.
+
+mod components;
+pub mod settings;
+mod subassembly;
+
+pub use components::{parse_component_registry, ComponentId, ComponentInfo};
+pub use subassembly::{PegAddress, Subassembly, SubassemblyComponent, SubassemblyWire};
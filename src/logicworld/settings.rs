@@ -0,0 +1,152 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::{io::ErrorKind, path::Path};
+
+use crate::decode::FromJecs;
+use crate::encode::IntoJecs;
+use crate::keybind::KeyBind;
+use crate::errors::JecsDecodeError;
+#[cfg(feature = "std")]
+use crate::errors::JecsLoadError;
+#[cfg(feature = "std")]
+use crate::parser::parse_file_into;
+use crate::path::JecsPath;
+use crate::types::{JecsMap, JecsType};
+
+//The settings files of the game. Every entry is optional, missing entries get the default value of the game. Roots which are no map are rejected.
+//Entries unknown to these types are ignored when reading, thus writing them back drops them. Use a DataFile to edit the files instead.
+
+#[derive(Clone, PartialEq)]
+#[derive(Debug)]
+pub struct GraphicsSettings {
+	pub fullscreen: bool,
+	pub resolution_width: u32,
+	pub resolution_height: u32,
+	pub vsync: bool,
+	pub max_framerate: u32, //0 for unlimited
+	pub field_of_view: f32,
+}
+
+impl Default for GraphicsSettings {
+	fn default() -> Self {
+		Self {
+			fullscreen: true,
+			resolution_width: 1920,
+			resolution_height: 1080,
+			vsync: true,
+			max_framerate: 0,
+			field_of_view: 70.0,
+		}
+	}
+}
+
+impl FromJecs for GraphicsSettings {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		let map = entry.expect_map_or_any()?;
+		let default = Self::default();
+		Ok(Self {
+			fullscreen: field(&map, "Fullscreen", default.fullscreen)?,
+			resolution_width: field(&map, "ResolutionWidth", default.resolution_width)?,
+			resolution_height: field(&map, "ResolutionHeight", default.resolution_height)?,
+			vsync: field(&map, "VSync", default.vsync)?,
+			max_framerate: field(&map, "MaxFramerate", default.max_framerate)?,
+			field_of_view: field(&map, "FieldOfView", default.field_of_view)?,
+		})
+	}
+}
+
+impl IntoJecs for GraphicsSettings {
+	fn to_jecs(&self) -> JecsType {
+		let mut map = JecsMap::default();
+		map.insert("Fullscreen".into(), self.fullscreen.to_jecs());
+		map.insert("ResolutionWidth".into(), self.resolution_width.to_jecs());
+		map.insert("ResolutionHeight".into(), self.resolution_height.to_jecs());
+		map.insert("VSync".into(), self.vsync.to_jecs());
+		map.insert("MaxFramerate".into(), self.max_framerate.to_jecs());
+		map.insert("FieldOfView".into(), self.field_of_view.to_jecs());
+		JecsType::Map(map)
+	}
+}
+
+#[derive(Clone, PartialEq)]
+#[derive(Debug)]
+pub struct NetworkSettings {
+	pub last_server_address: String,
+	pub port: u16, //Used when hosting a server
+	pub timeout_seconds: u32,
+}
+
+impl Default for NetworkSettings {
+	fn default() -> Self {
+		Self {
+			last_server_address: String::new(),
+			port: 7777,
+			timeout_seconds: 30,
+		}
+	}
+}
+
+impl FromJecs for NetworkSettings {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		let map = entry.expect_map_or_any()?;
+		let default = Self::default();
+		Ok(Self {
+			last_server_address: field(&map, "LastServerAddress", default.last_server_address)?,
+			port: field(&map, "Port", default.port)?,
+			timeout_seconds: field(&map, "TimeoutSeconds", default.timeout_seconds)?,
+		})
+	}
+}
+
+impl IntoJecs for NetworkSettings {
+	fn to_jecs(&self) -> JecsType {
+		let mut map = JecsMap::default();
+		map.insert("LastServerAddress".into(), self.last_server_address.to_jecs());
+		map.insert("Port".into(), self.port.to_jecs());
+		map.insert("TimeoutSeconds".into(), self.timeout_seconds.to_jecs());
+		JecsType::Map(map)
+	}
+}
+
+//The key binding of every action, which got changed from the default. Actions are named like "Building.Rotate".
+#[derive(Clone, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub struct KeybindingSettings {
+	pub bindings: BTreeMap<String, KeyBind>,
+}
+
+impl FromJecs for KeybindingSettings {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		let mut bindings = BTreeMap::new();
+		for (action, binding) in entry.expect_map_or_any()?.iter() {
+			bindings.insert(action.clone(), binding.expect_keybind().map_err(|error| error.at(JecsPath::root().join_key(action)))?);
+		}
+		Ok(Self {
+			bindings,
+		})
+	}
+}
+
+impl IntoJecs for KeybindingSettings {
+	fn to_jecs(&self) -> JecsType {
+		JecsType::Map(self.bindings.iter().map(|(action, binding)| (action.clone(), binding.to_jecs())).collect())
+	}
+}
+
+//Reads a settings file, a missing file results in the default settings:
+// let graphics: GraphicsSettings = settings::load(Path::new("GameData/settings/graphics.succ"))?;
+#[cfg(feature = "std")]
+pub fn load<T: FromJecs + Default>(path: &Path) -> Result<T, JecsLoadError> {
+	match parse_file_into(path) {
+		Err(JecsLoadError::Io(error)) if error.kind() == ErrorKind::NotFound => Ok(T::default()),
+		result => result,
+	}
+}
+
+fn field<T: FromJecs>(map: &JecsMap, key: &str, default: T) -> Result<T, JecsDecodeError> {
+	match map.get(key) {
+		None => Ok(default),
+		Some(child) => T::from_jecs(child).map_err(|error| error.at(key)),
+	}
+}
@@ -0,0 +1,191 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::decode::FromJecs;
+use crate::errors::JecsDecodeError;
+use crate::path::JecsPath;
+use crate::types::{integer_error, malformed, missing_key, HashMap, JecsMap, JecsType};
+
+//The content of a subassembly file:
+// MetaData:
+//     Name: Adder
+// Components:
+//     C-1:
+//         Type: MHG.CircuitBoard
+//     C-2:
+//         Type: MHG.AndGate
+//         Parent: C-1
+//         Inputs: 2
+//         Outputs: 1
+// Wires:
+//     -
+//         From: C-2.O0
+//         To: C-3.I1
+//Components are sorted by their address. Parents and wires refer to them by their index in that list.
+#[derive(Clone, PartialEq, Default)]
+#[derive(Debug)]
+pub struct Subassembly {
+	pub metadata: JecsMap,
+	pub components: Vec<SubassemblyComponent>,
+	pub wires: Vec<SubassemblyWire>,
+}
+
+#[derive(Clone, PartialEq)]
+#[derive(Debug)]
+pub struct SubassemblyComponent {
+	pub address: u32,
+	pub component_type: String,
+	pub parent: Option<usize>, //Index of the parent component, None for components placed directly into the world
+	pub inputs: u32,
+	pub outputs: u32,
+	pub data: JecsMap, //All other entries, like position, rotation and custom data
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub struct PegAddress {
+	pub component: usize, //Index of the component
+	pub is_input: bool,
+	pub index: u32,
+}
+
+#[derive(Clone, PartialEq)]
+#[derive(Debug)]
+pub struct SubassemblyWire {
+	pub from: PegAddress,
+	pub to: PegAddress,
+	pub data: JecsMap, //All other entries of the wire
+}
+
+impl Subassembly {
+	//Missing sections are empty. Addresses of parents and wires have to point to components of the subassembly.
+	pub fn parse(tree: &JecsType) -> Result<Self, JecsDecodeError> {
+		let metadata = section_map(tree, "MetaData")?;
+		let components_map = section_map(tree, "Components")?;
+		
+		let mut addressed_entries = Vec::with_capacity(components_map.len());
+		for (key, entry) in &components_map {
+			let path = JecsPath::root().join_key("Components").join_key(key);
+			let address = JecsType::value(key.as_str()).expect_component_address().map_err(|error| error.at(&path))?;
+			addressed_entries.push((address, path, entry));
+		}
+		addressed_entries.sort_by_key(|(address, _, _)| *address);
+		let indices: HashMap<u32, usize> = addressed_entries.iter().enumerate().map(|(index, (address, _, _))| (*address, index)).collect();
+		let resolve = |path: &JecsPath, address: u32| {
+			indices.get(&address).copied().ok_or_else(|| JecsDecodeError::from(missing_key(&format!("C-{}", address), Some(&components_map))).at(path))
+		};
+		
+		let mut components = Vec::with_capacity(addressed_entries.len());
+		for (address, path, entry) in &addressed_entries {
+			let mut data = map_of(entry, path)?;
+			let component_type = String::from_jecs(&take(&mut data, path, "Type")?).map_err(|error| error.at(path.join_key("Type")))?;
+			let parent = match data.remove("Parent") {
+				None => None,
+				Some(parent) => {
+					let parent_path = path.join_key("Parent");
+					let parent_address = parent.expect_component_address().map_err(|error| error.at(&parent_path))?;
+					Some(resolve(&parent_path, parent_address)?)
+				}
+			};
+			let inputs = take_count(&mut data, path, "Inputs")?;
+			let outputs = take_count(&mut data, path, "Outputs")?;
+			components.push(SubassemblyComponent {
+				address: *address,
+				component_type,
+				parent,
+				inputs,
+				outputs,
+				data,
+			});
+		}
+		
+		let wire_list = match tree.get_path("Wires") {
+			None | Some(JecsType::Any()) => &[][..],
+			Some(entry) => entry.expect_list().map_err(|error| error.at("Wires"))?,
+		};
+		let mut wires = Vec::with_capacity(wire_list.len());
+		for (index, entry) in wire_list.iter().enumerate() {
+			let path = JecsPath::root().join_key("Wires").join_index(index);
+			let mut data = map_of(entry, &path)?;
+			let mut peg = |key: &str| {
+				let peg_path = path.join_key(key);
+				let value = take(&mut data, &path, key)?;
+				let text = value.expect_string().map_err(|error| error.at(&peg_path))?;
+				let (address, is_input, index) = parse_peg(text).map_err(|error| error.at(&peg_path))?;
+				let component = resolve(&peg_path, address)?;
+				let peg_count = if is_input { components[component].inputs } else { components[component].outputs };
+				if index >= peg_count {
+					return Err(malformed("peg address", text).at(&peg_path));
+				}
+				Ok(PegAddress {
+					component,
+					is_input,
+					index,
+				})
+			};
+			let from = peg("From")?;
+			let to = peg("To")?;
+			wires.push(SubassemblyWire {
+				from,
+				to,
+				data,
+			});
+		}
+		
+		Ok(Self {
+			metadata,
+			components,
+			wires,
+		})
+	}
+}
+
+impl FromJecs for Subassembly {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		Subassembly::parse(entry)
+	}
+}
+
+fn section_map(tree: &JecsType, key: &str) -> Result<JecsMap, JecsDecodeError> {
+	match tree.get_path(key) {
+		None => Ok(JecsMap::default()),
+		Some(entry) => map_of(entry, &JecsPath::root().join_key(key)),
+	}
+}
+
+//Entries without children are read as Any, which counts as empty map here.
+fn map_of(entry: &JecsType, path: &JecsPath) -> Result<JecsMap, JecsDecodeError> {
+	match entry {
+		JecsType::Any() => Ok(JecsMap::default()),
+		_ => entry.expect_map().cloned().map_err(|error| error.at(path)),
+	}
+}
+
+fn take(data: &mut JecsMap, path: &JecsPath, key: &str) -> Result<JecsType, JecsDecodeError> {
+	match data.remove(key) {
+		Some(entry) => Ok(entry),
+		None => Err(JecsDecodeError::from(missing_key(key, Some(data))).at(path.join_key(key))),
+	}
+}
+
+//Missing counts are 0, as most components only have inputs or only outputs.
+fn take_count(data: &mut JecsMap, path: &JecsPath, key: &str) -> Result<u32, JecsDecodeError> {
+	match data.remove(key) {
+		None => Ok(0),
+		Some(entry) => entry.expect_unsigned().map_err(|error| error.at(path.join_key(key))),
+	}
+}
+
+//Pegs are written as component address, '.', 'I' for inputs or 'O' for outputs and the index of the peg: "C-12.I0"
+fn parse_peg(text: &str) -> Result<(u32, bool, u32), JecsDecodeError> {
+	let (address, peg) = text.rsplit_once('.').ok_or_else(|| malformed("peg address", text))?;
+	let address = JecsType::value(address).expect_component_address()?;
+	let is_input = match peg.as_bytes().first() {
+		Some(b'I') => true,
+		Some(b'O') => false,
+		_ => return Err(malformed("peg address", text)),
+	};
+	let index = peg[1..].parse().map_err(|error| integer_error("peg address", text, &error))?;
+	Ok((address, is_input, index))
+}
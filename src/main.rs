@@ -1,8 +1,8 @@
-use std::fs::read_dir;
+use std::fs::{self, read_dir};
 use std::path::{Path, PathBuf};
 use std::str::Utf8Error;
 
-use ecc_jecs_lib::{debug, parser};
+use ecc_jecs_lib::{debug, diagnostic, parser};
 use ecc_jecs_lib::errors::JecsCorruptedDataError;
 use ecc_jecs_lib::types::JecsType;
 
@@ -18,9 +18,10 @@ fn main() {
 		}
 	});
 	
+	let mut jecs_parser = parser::Parser::default();
 	for file in files {
 		println!("- {}", file.to_str().unwrap());
-		let tree = match parser::parse_jecs_file(&file) {
+		let tree = match jecs_parser.parse_file(&file) {
 			Ok(tree) => tree,
 			Err(e) => {
 				if let Some(e) = e.downcast_ref::<std::io::Error>() {
@@ -28,7 +29,8 @@ fn main() {
 				} else if let Some(e) = e.downcast_ref::<Utf8Error>() {
 					panic!("Manifest file does not contain valid UTF-8! Error: {}", e);
 				} else if let Some(e) = e.downcast_ref::<JecsCorruptedDataError>() {
-					panic!("Manifest has invalid content: {}", e);
+					let source = fs::read_to_string(&file).unwrap();
+					panic!("Manifest has invalid content:\n{}", diagnostic::render_error(&source, e));
 				} else {
 					panic!("Unknown exception: {}", e);
 				}
@@ -0,0 +1,91 @@
+use alloc::vec::Vec;
+
+use crate::path::JecsPath;
+use crate::types::JecsType;
+
+//Which entry is kept, when both trees contain an entry at the same path. Maps are always merged key by key.
+#[derive(Clone, Copy, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub enum JecsMergeStrategy {
+	//Entries of the merged in tree replace the existing ones, like an override file on top of defaults.
+	#[default]
+	Override,
+	//Existing entries stay, only entries missing in the tree are added.
+	KeepExisting,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub enum JecsListMerge {
+	//Lists are entries like values and replaced as a whole according to the strategy.
+	#[default]
+	Replace,
+	//Entries of the merged in list are appended to the existing list, this never conflicts.
+	Append,
+}
+
+#[derive(Clone, Default)]
+#[derive(Debug)]
+pub struct JecsMergeOptions {
+	pub strategy: JecsMergeStrategy,
+	pub lists: JecsListMerge,
+}
+
+//Both trees had a different entry at this path, one of them got dropped according to the strategy.
+//Entries without value (Any) in the existing tree are filled in without conflict.
+#[derive(Clone, PartialEq)]
+#[derive(Debug)]
+pub struct JecsMergeConflict {
+	pub path: JecsPath,
+	pub existing: JecsType,
+	pub incoming: JecsType,
+}
+
+impl JecsType {
+	//Merges the other tree into this one and returns all conflicts, the merge itself always succeeds.
+	pub fn merge(&mut self, other: JecsType, options: &JecsMergeOptions) -> Vec<JecsMergeConflict> {
+		let mut conflicts = Vec::new();
+		merge_inner(self, other, &mut JecsPath::root(), options, &mut conflicts);
+		return conflicts;
+		
+		fn merge_inner(entry: &mut JecsType, other: JecsType, path: &mut JecsPath, options: &JecsMergeOptions, conflicts: &mut Vec<JecsMergeConflict>) {
+			match (entry, other) {
+				(JecsType::Map(map), JecsType::Map(other)) => {
+					for (key, child) in other {
+						match map.get_mut(&key) {
+							Some(existing) => {
+								path.push_key(&key);
+								merge_inner(existing, child, path, options, conflicts);
+								path.pop();
+							}
+							None => {
+								map.insert(key, child);
+							}
+						}
+					}
+				}
+				(JecsType::List(list), JecsType::List(other)) if options.lists == JecsListMerge::Append => {
+					list.extend(other);
+				}
+				(entry, other) => {
+					if entry.is_any() {
+						*entry = other;
+						return;
+					}
+					if *entry == other {
+						return;
+					}
+					let existing = match options.strategy {
+						JecsMergeStrategy::Override => core::mem::replace(entry, other.clone()),
+						JecsMergeStrategy::KeepExisting => entry.clone(),
+					};
+					conflicts.push(JecsMergeConflict {
+						path: path.clone(),
+						existing,
+						incoming: other,
+					});
+				}
+			}
+		}
+	}
+}
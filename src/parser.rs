@@ -1,27 +1,191 @@
-use std::{collections::HashMap, fs, str::from_utf8};
-use std::cmp::{Ordering, PartialEq};
-use std::error::Error;
-use std::iter::Peekable;
-use std::path::Path;
-use std::str::Chars;
+use alloc::{borrow::Cow, boxed::Box, format, string::{String, ToString}, vec::Vec};
+use core::cmp::{Ordering, PartialEq};
+use core::error::Error;
+#[cfg(feature = "serde")]
+use core::iter::{Enumerate, Map};
+use core::iter::Peekable;
+use core::ops::Range;
+use core::str::from_utf8;
+#[cfg(feature = "serde")]
+use core::str::Lines;
+#[cfg(feature = "std")]
+use std::{fs, io::Read, path::Path};
 
-use crate::errors::JecsCorruptedDataError;
-use crate::types::JecsType;
+#[cfg(feature = "std")]
+use crate::decode::FromJecs;
+#[cfg(feature = "std")]
+use crate::errors::JecsLoadError;
+use crate::errors::{JecsCorruptedDataError, JecsErrorCode, JecsWarning};
+#[cfg(feature = "arena")]
+use crate::arena::{BumpVec, JecsArenaType};
+#[cfg(feature = "arena")]
+use bumpalo::Bump;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use crate::borrowed::{JecsRef, JecsRefMap};
+use crate::spanned::{JecsSpan, JecsSpannedEntry, JecsSpannedMap, JecsSpannedType};
+use crate::types::{JecsMap, JecsType};
 
-pub fn parse_jecs_file(path: &Path) -> Result<HashMap<String, JecsType>, Box<dyn Error>> {
+#[cfg(feature = "std")]
+pub fn parse_jecs_file(path: &Path) -> Result<JecsMap, Box<dyn Error>> {
 	let bytes = fs::read(&path)?; //std::io::Error
 	parse_jecs_bytes(&bytes)
 }
 
-pub fn parse_jecs_bytes(bytes: &[u8]) -> Result<HashMap<String, JecsType>, Box<dyn Error>> {
+//Reads, parses and decodes a file in one go, the root map is passed to T::from_jecs().
+#[cfg(feature = "std")]
+pub fn parse_file_into<T: FromJecs>(path: &Path) -> Result<T, JecsLoadError> {
+	let bytes = fs::read(path)?;
+	let text = from_utf8(&bytes)?;
+	let map = parse_jecs_string(text.strip_prefix('\u{feff}').unwrap_or(text))?;
+	Ok(T::from_jecs(&JecsType::Map(map))?)
+}
+
+pub fn parse_jecs_bytes(bytes: &[u8]) -> Result<JecsMap, Box<dyn Error>> {
 	let text = from_utf8(bytes)?; //Utf8Error
 	//Remove BOM on encounter:
 	let text = if text.starts_with("\u{feff}") { &text[3..] } else { &text };
 	Ok(parse_jecs_string(text)?)
 }
 
-pub fn parse_jecs_string(text: &str) -> Result<HashMap<String, JecsType>, JecsCorruptedDataError> {
-	let mut tree_parser = TreeParser::default();
+//Differences to the default JECS parsing. The default is plain JECS, as this parser always did it.
+#[derive(Clone, Default)]
+#[derive(Debug)]
+pub struct ParserOptions {
+	//Removes a pair of surrounding '"' from single-line values. This keeps leading/trailing spaces, '""' is an empty string.
+	pub unquote_values: bool,
+	//Single-line values 'null' (not quoted) become Any entries. They still cannot have children.
+	pub null_as_any: bool,
+	//Single-line values 'null' (not quoted) become Null entries, which unlike Any entries are no empty map or list.
+	//The counterpart of WriterOptions::write_null, it takes precedence over null_as_any.
+	pub read_null: bool,
+	//Accepts entries which are not indented like their siblings or any parent level with a warning (see parse_jecs_string_with_warnings()).
+	//They are moved to the closest indentation level. Deeper indented entries after a value become its sibling.
+	pub lenient_indentation: bool,
+	//What happens with keys which are used twice within the same map.
+	pub duplicate_keys: DuplicateKeys,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub enum DuplicateKeys {
+	//The later entry replaces the earlier one, as this parser always did it. A warning names the lines of both.
+	#[default]
+	Tolerate,
+	//Fails at the later entry.
+	Reject,
+}
+
+impl ParserOptions {
+	//Behaves like the C# SUCC library, which Logic World uses to write its files.
+	//SUCC quotes values with leading/trailing spaces or surrounding quotes and writes null as 'null'. Its parser also tolerates sloppy indentation.
+	//Lists ('-'), multi-line strings and the '\#' escape are the same in both.
+	pub fn succ() -> Self {
+		Self {
+			unquote_values: true,
+			null_as_any: true,
+			read_null: false,
+			lenient_indentation: true,
+			duplicate_keys: DuplicateKeys::Tolerate,
+		}
+	}
+}
+
+pub fn parse_jecs_string(text: &str) -> Result<JecsMap, JecsCorruptedDataError> {
+	parse_jecs_string_with_options(text, &ParserOptions::default())
+}
+
+pub fn parse_jecs_string_with_options(text: &str, options: &ParserOptions) -> Result<JecsMap, JecsCorruptedDataError> {
+	Ok(parse_jecs_string_with_warnings(text, options)?.0)
+}
+
+//Also returns the problems, which did not prevent parsing. Besides the ones of lenient parsing these are lints:
+//Trailing whitespace, tab characters, changing indentation steps, comments which likely were meant as values and keys used twice.
+pub fn parse_jecs_string_with_warnings(text: &str, options: &ParserOptions) -> Result<(JecsMap, Vec<JecsWarning>), JecsCorruptedDataError> {
+	let mut warnings = Vec::new();
+	let root = build_tree::<JecsType>(text, options, (), &mut Vec::new(), &mut warnings)?;
+	//Lines of multi-line strings are checked before their opener got added to the tree:
+	warnings.sort_by_key(|warning| warning.row);
+	match root {
+		JecsType::Map(map) => Ok((map, warnings)),
+		_ => unreachable!("Impossible to reach code: Something is wrong with the TreeParser building JecsType nodes. Did get wrong root type."),
+	}
+}
+
+//Like parse_jecs_string(), but all keys, values and collections of the tree are allocated in the arena.
+#[cfg(feature = "arena")]
+pub fn parse_jecs_string_in<'bump>(bump: &'bump Bump, text: &str) -> Result<JecsArenaType<'bump>, JecsCorruptedDataError> {
+	build_tree(text, &ParserOptions::default(), bump, &mut Vec::new(), &mut Vec::new())
+}
+
+//Like parse_jecs_string_with_options(), but parts of the text are parsed on the rayon thread pool. Pays off for huge files with many root entries.
+//Lines without indentation always start a root entry, thus the text can be split there. If any part fails, the whole text is parsed again for the exact error.
+#[cfg(feature = "rayon")]
+pub fn parse_jecs_string_parallel(text: &str, options: &ParserOptions) -> Result<JecsMap, JecsCorruptedDataError> {
+	//Parts have at least this size, so that small files do not get split into lots of tiny maps:
+	const MIN_PART_BYTES: usize = 64 * 1024;
+	let mut parts = Vec::new();
+	let mut part_start = 0;
+	let mut offset = 0;
+	for line in text.split_inclusive('\n') {
+		if offset - part_start >= MIN_PART_BYTES && line.starts_with(|c: char| !matches!(c, ' ' | '#' | '\r' | '\n')) {
+			parts.push(&text[part_start..offset]);
+			part_start = offset;
+		}
+		offset += line.len();
+	}
+	parts.push(&text[part_start..]);
+	
+	let maps = match parts.into_par_iter().map(|part| parse_jecs_string_with_options(part, options)).collect::<Result<Vec<_>, _>>() {
+		Ok(maps) => maps,
+		//Rows of the parts do not match the rows of the text:
+		Err(_) => return parse_jecs_string_with_options(text, options),
+	};
+	//Like in a single map, the last entry with the same key wins:
+	let entry_count = maps.iter().map(JecsMap::len).sum();
+	let mut merged = JecsMap::with_capacity_and_hasher(entry_count, Default::default());
+	for map in maps {
+		merged.extend(map);
+	}
+	//Keys used in multiple parts are found by the full parse, which knows their rows:
+	if options.duplicate_keys == DuplicateKeys::Reject && merged.len() != entry_count {
+		return parse_jecs_string_with_options(text, options);
+	}
+	Ok(merged)
+}
+
+//Like parse_jecs_string(), but keys and values borrow from the text. Only the ones with escaped '#' characters are copied.
+pub fn parse_jecs_string_borrowed(text: &str) -> Result<JecsRefMap<'_>, JecsCorruptedDataError> {
+	match build_tree::<JecsRef>(text, &ParserOptions::default(), (), &mut Vec::new(), &mut Vec::new())? {
+		JecsRef::Map(map) => Ok(map),
+		_ => unreachable!("Impossible to reach code: Something is wrong with the TreeParser building JecsRef nodes. Did get wrong root type."),
+	}
+}
+
+//Like parse_jecs_string(), but every entry keeps the location it was read from, for reporting problems found after parsing.
+pub fn parse_jecs_string_with_spans(text: &str) -> Result<JecsSpannedMap, JecsCorruptedDataError> {
+	match build_tree::<JecsSpannedEntry>(text, &ParserOptions::default(), (), &mut Vec::new(), &mut Vec::new())?.value {
+		JecsSpannedType::Map(map) => Ok(map),
+		_ => unreachable!("Impossible to reach code: Something is wrong with the TreeParser building JecsSpannedEntry nodes. Did get wrong root type."),
+	}
+}
+
+//Returns the root node, the warnings are stored in the passed list. The lists are only borrowed to keep their allocations.
+fn build_tree<'t, N: ParsedNode<'t>>(text: &'t str, options: &ParserOptions, context: N::Context, stack: &mut Vec<StackEntry<N, N::Key>>, warnings: &mut Vec<JecsWarning>) -> Result<N, JecsCorruptedDataError> {
+	//Clear the content of the previous parse, failed ones also leave stack entries behind:
+	stack.clear();
+	warnings.clear();
+	let mut tree_parser = TreeParser {
+		context,
+		root: N::new_root(context),
+		stack,
+		lenient_indentation: options.lenient_indentation,
+		read_null: options.read_null,
+		duplicate_keys: options.duplicate_keys,
+		text,
+		warnings,
+		indentation_step: None,
+	};
 	
 	let mut line_iterator = text.lines()
 		.into_iter()
@@ -29,43 +193,173 @@ pub fn parse_jecs_string(text: &str) -> Result<HashMap<String, JecsType>, JecsCo
 		.peekable();
 	//The stack is still empty, handle the very first line (differently):
 	while let Some(line_data) = line_iterator.next() {
-		if let Some(line_meta) = parse_line(line_data, &mut line_iterator)? {
+		if let Some(line_meta) = parse_line(line_data, &mut line_iterator, options, tree_parser.warnings)? {
 			tree_parser.add_validate_root(line_meta)?;
 			break;
 		}
 	}
 	//Process every remaining line of the file:
 	while let Some(line_data) = line_iterator.next() {
-		if let Some(line_meta) = parse_line(line_data, &mut line_iterator)? {
+		if let Some(line_meta) = parse_line(line_data, &mut line_iterator, options, tree_parser.warnings)? {
 			tree_parser.append_next_line(line_meta)?;
 		}
 	}
-	//Empty the stack, so that only root elements and their child structures remain:
-	tree_parser.post_line_addition_cleanup();
+	//Empty the stack, so that only the root node with all of its children remains:
+	tree_parser.finish()
+}
+
+//Parses many texts after each other, keeping the buffers between them. Meant for tools that parse lots of files in a loop.
+#[derive(Default)]
+pub struct Parser {
+	options: ParserOptions,
+	stack: Vec<StackEntry<JecsType, String>>,
+	warnings: Vec<JecsWarning>,
+	#[cfg(feature = "std")]
+	file_buffer: Vec<u8>,
+}
+
+impl Parser {
+	pub fn new(options: ParserOptions) -> Self {
+		Self {
+			options,
+			..Self::default()
+		}
+	}
+	
+	pub fn options(&self) -> &ParserOptions {
+		&self.options
+	}
+	
+	//The warnings of the last parsed text, see parse_jecs_string_with_warnings().
+	pub fn warnings(&self) -> &[JecsWarning] {
+		&self.warnings
+	}
+	
+	pub fn parse_string(&mut self, text: &str) -> Result<JecsMap, JecsCorruptedDataError> {
+		let root = build_tree::<JecsType>(text, &self.options, (), &mut self.stack, &mut self.warnings)?;
+		//Lines of multi-line strings are checked before their opener got added to the tree:
+		self.warnings.sort_by_key(|warning| warning.row);
+		match root {
+			JecsType::Map(map) => Ok(map),
+			_ => unreachable!("Impossible to reach code: Something is wrong with the TreeParser building JecsType nodes. Did get wrong root type."),
+		}
+	}
+	
+	pub fn parse_bytes(&mut self, bytes: &[u8]) -> Result<JecsMap, Box<dyn Error>> {
+		let text = from_utf8(bytes)?; //Utf8Error
+		//Remove BOM on encounter:
+		Ok(self.parse_string(text.strip_prefix('\u{feff}').unwrap_or(text))?)
+	}
+	
+	//The file is read into the buffer of the previous file.
+	#[cfg(feature = "std")]
+	pub fn parse_file(&mut self, path: &Path) -> Result<JecsMap, Box<dyn Error>> {
+		let mut bytes = core::mem::take(&mut self.file_buffer);
+		bytes.clear();
+		let result = match fs::File::open(path).and_then(|mut file| file.read_to_end(&mut bytes)) {
+			Ok(_) => self.parse_bytes(&bytes),
+			Err(error) => Err(error.into()), //std::io::Error
+		};
+		self.file_buffer = bytes;
+		result
+	}
+}
+
+//Checks the text like parse_jecs_string() does, but without building the tree or copying keys and values.
+//Instead of stopping at the first problem, the broken line is skipped and every error gets returned.
+pub fn validate_jecs_string(text: &str) -> Result<(), Vec<JecsCorruptedDataError>> {
+	let mut validator = Validator {
+		levels: Vec::new(),
+		errors: Vec::new(),
+	};
+	let mut line_iterator = text.lines()
+		.enumerate().map(|(index, line)| (index + 1, line))
+		.peekable();
+	while let Some((row, line)) = line_iterator.next() {
+		validator.validate_line(row, line, &mut line_iterator);
+	}
+	if validator.errors.is_empty() {
+		Ok(())
+	} else {
+		Err(validator.errors)
+	}
+}
+
+#[cfg(feature = "serde")]
+type NumberedLines<'t> = Map<Enumerate<Lines<'t>>, fn((usize, &'t str)) -> (usize, &'t str)>;
+
+//Hands out the lines with an entry one after another, without building a tree. How the lines nest is up to the caller to check.
+#[cfg(feature = "serde")]
+pub(crate) struct LineReader<'t> {
+	line_iterator: Peekable<NumberedLines<'t>>,
+	options: ParserOptions,
+	warnings: Vec<JecsWarning>, //Not reported, only kept to reuse the allocation
+	peeked: Option<LineMeta<'t>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'t> LineReader<'t> {
+	pub(crate) fn new(text: &'t str, options: ParserOptions) -> Self {
+		let number_line: fn((usize, &'t str)) -> (usize, &'t str) = |(index, line)| (index + 1, line);
+		Self {
+			line_iterator: text.lines().enumerate().map(number_line).peekable(),
+			options,
+			warnings: Vec::new(),
+			peeked: None,
+		}
+	}
+	
+	pub(crate) fn peek(&mut self) -> Result<Option<&LineMeta<'t>>, JecsCorruptedDataError> {
+		if self.peeked.is_none() {
+			self.peeked = self.read_line()?;
+		}
+		Ok(self.peeked.as_ref())
+	}
+	
+	pub(crate) fn next(&mut self) -> Result<Option<LineMeta<'t>>, JecsCorruptedDataError> {
+		match self.peeked.take() {
+			Some(line_meta) => Ok(Some(line_meta)),
+			None => self.read_line(),
+		}
+	}
 	
-	//Finally convert everything to JECS type structures without the meta & temporary information:
-	Ok(tree_parser.finalize_to_map()?)
+	fn read_line(&mut self) -> Result<Option<LineMeta<'t>>, JecsCorruptedDataError> {
+		while let Some(line_data) = self.line_iterator.next() {
+			self.warnings.clear();
+			if let Some(line_meta) = parse_line(line_data, &mut self.line_iterator, &self.options, &mut self.warnings)? {
+				return Ok(Some(line_meta));
+			}
+		}
+		Ok(None)
+	}
 }
 
 #[derive(Eq, PartialEq)]
 #[derive(Debug)]
 enum JecsTypeInner {
 	Any,
+	NullAsAny, //Like Any, but it was written as value and thus cannot get children
+	Null,
 	Value,
 	Map,
 	List,
 }
 
+//Keys and values borrow from the parsed text, unless they had to be unescaped.
 #[derive(Debug)]
-struct LineMeta {
-	row: usize,
-	indentation: usize,
-	key: Option<String>,
-	value: Option<String>,
+pub(crate) struct LineMeta<'t> {
+	pub(crate) row: usize,
+	pub(crate) indentation: usize,
+	pub(crate) key: Option<Cow<'t, str>>,
+	pub(crate) value: Option<Cow<'t, str>>,
+	pub(crate) key_columns: Range<usize>,
+	value_columns: Option<Range<usize>>,
+	last_row: usize,
+	pub(crate) is_null: bool,
 }
 
-impl LineMeta {
-	fn is_list(&self) -> bool {
+impl LineMeta<'_> {
+	pub(crate) fn is_list(&self) -> bool {
 		self.key.is_none()
 	}
 	
@@ -83,98 +377,146 @@ impl LineMeta {
 }
 
 macro_rules! jecs_error {
-	($row:expr, $($arguments:tt)*) => {
+	($row:expr, $column:expr, $code:ident, $($arguments:tt)*) => {
 		Err(JecsCorruptedDataError {
 			row: $row,
+			column: $column,
+			code: JecsErrorCode::$code,
 			description: format!($($arguments)*),
 		})?
 	};
 }
 
-fn parse_line<'a>((row, line): (usize, &str), line_iterator: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>) -> Result<Option<LineMeta>, JecsCorruptedDataError> {
-	let mut iterator = line.chars().peekable();
+fn parse_line<'a>((row, line): (usize, &'a str), line_iterator: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>, options: &ParserOptions, warnings: &mut Vec<JecsWarning>) -> Result<Option<LineMeta<'a>>, JecsCorruptedDataError> {
+	lint_line(row, line, warnings);
+	//All syntax characters are ASCII, thus the line is scanned as bytes. Positions are byte offsets into the line.
 	
 	//Read indentation:
-	let indentation = match read_indentation(row, &mut iterator, true)? {
+	let indentation = match read_indentation(row, line, true)? {
 		None => return Ok(None),
 		Some(indentation) => indentation,
 	};
-	//At this point, we know that there still is a symbol, as we used 'break'.
+	//At this point, we know that there still is a symbol after the indentation.
 	
 	//Read key:
-	let key = read_key(row, &mut iterator)?;
+	let (key, key_end) = read_key(row, line, indentation)?;
+	//Indentation only consists of single byte spaces. For list entries the '-' is used as key:
+	let key_columns = indentation..indentation + key.as_ref().map_or(1, |key| key.len());
 	
 	//Skip space until value:
-	while iterator.peek().is_some() && *iterator.peek().unwrap() == ' ' {
-		iterator.next();
-	}
+	let value_start = key_end + line[key_end..].len() - line[key_end..].trim_start_matches(' ').len();
 	
 	//Read value:
-	let value = read_value(row, indentation, &mut iterator, line_iterator)?;
+	let (mut value, value_end, last_row) = read_value(row, indentation, line, value_start, line_iterator, warnings)?;
+	//The value reading stops before any comment, the columns cover the value as written (for multi-line strings the opener):
+	let value_columns = value.as_ref().map(|_| {
+		value_start..value_start + line[value_start..value_end].trim_end_matches(' ').len()
+	});
+	
+	//A '#' glued to the value or in place of the value is likely meant as part of the value (like colors '#FF0000'):
+	if line.as_bytes().get(value_end) == Some(&b'#') {
+		let comment_start = value_end;
+		let comment = &line[comment_start + 1..];
+		if value.is_some() && !line[..comment_start].ends_with(' ') {
+			warnings.push(JecsWarning {
+				row,
+				column: comment_start,
+				code: JecsErrorCode::CommentAfterValue,
+				description: "Comment directly follows the value, if the '#' belongs to the value escape it as '\\#'".to_string(),
+			});
+		} else if value.is_none() && !comment.is_empty() && !comment.starts_with(' ') {
+			warnings.push(JecsWarning {
+				row,
+				column: comment_start,
+				code: JecsErrorCode::CommentInPlaceOfValue,
+				description: "Comment in place of a value, if the '#' belongs to the value escape it as '\\#'".to_string(),
+			});
+		}
+	}
+	
+	//Multi-line strings are never quoted or null:
+	let mut is_null = false;
+	if let (Some(content), true) = (&mut value, last_row == row) {
+		if (options.null_as_any || options.read_null) && content == "null" {
+			is_null = true;
+		} else if options.unquote_values && content.len() >= 2 && content.starts_with('"') && content.ends_with('"') {
+			*content = match content {
+				Cow::Borrowed(borrowed) => Cow::Borrowed(&borrowed[1..borrowed.len() - 1]),
+				Cow::Owned(owned) => Cow::Owned(owned[1..owned.len() - 1].to_string()),
+			};
+		}
+	}
 	
 	return Ok(Some(LineMeta {
 		row,
 		indentation,
 		key,
 		value,
+		key_columns,
+		value_columns,
+		last_row,
+		is_null,
 	}));
 	
-	fn read_indentation(row: usize, iterator: &mut Peekable<Chars>, check_for_column: bool) -> Result<Option<usize>, JecsCorruptedDataError> {
-		let mut indentation = 0;
-		loop {
-			let c = match iterator.peek() {
-				None => return Ok(None), //Empty line
-				Some(c) => *c,
-			};
-			
-			if c == ' ' {
-				indentation += 1;
-				iterator.next(); //Consume the character from the line
-			} else if c == '#' {
-				return Ok(None); //This line only contains a comment.
-			} else if check_for_column && c == ':' {
-				jecs_error!(row, "Line has no key, encountered ':'");
-			} else {
-				//Whatever character comes here, it must be part of the key. Do not consume.
-				break;
+	fn lint_line(row: usize, line: &str, warnings: &mut Vec<JecsWarning>) {
+		if line.ends_with([' ', '\t']) {
+			warnings.push(JecsWarning {
+				row,
+				column: line.trim_end_matches([' ', '\t']).len(),
+				code: JecsErrorCode::TrailingWhitespace,
+				description: "Line has trailing whitespace".to_string(),
+			});
+		}
+		if let Some(column) = line.find('\t') {
+			warnings.push(JecsWarning {
+				row,
+				column,
+				code: JecsErrorCode::TabCharacter,
+				description: "Line contains tab characters, which JECS does not treat as whitespace".to_string(),
+			});
+		}
+	}
+	
+	fn read_indentation(row: usize, line: &str, check_for_column: bool) -> Result<Option<usize>, JecsCorruptedDataError> {
+		let indentation = line.len() - line.trim_start_matches(' ').len();
+		match line.as_bytes().get(indentation) {
+			None => Ok(None), //Empty line
+			Some(b'#') => Ok(None), //This line only contains a comment.
+			Some(b':') if check_for_column => {
+				jecs_error!(row, indentation, MissingKey, "Line has no key, encountered ':'")
 			}
+			//Whatever character comes here, it must be part of the key.
+			_ => Ok(Some(indentation)),
 		}
-		Ok(Some(indentation))
 	}
 	
-	fn read_key(row: usize, iterator: &mut Peekable<Chars>) -> Result<Option<String>, JecsCorruptedDataError> {
-		if *iterator.peek().unwrap() != '-' {
-			let mut key_builder = String::new();
-			loop {
-				let c = match iterator.next() {
-					None => {
-						jecs_error!(row, "Unexpected line end while reading key") //Key never completely read
-					}
-					Some(c) => c,
-				};
-				
-				if c == ':' {
-					//Encountered the end of the key. Stop the loop, but consume the column (its part of the key).
-					break;
-				} else if c == '#' {
-					jecs_error!(row, "key may not contain a # character"); //Key never completely read
-				} else {
-					key_builder.push(c);
-				}
+	//Returns the key and the position after it.
+	fn read_key(row: usize, line: &str, position: usize) -> Result<(Option<Cow<'_, str>>, usize), JecsCorruptedDataError> {
+		let rest = &line.as_bytes()[position..];
+		if rest[0] == b'-' {
+			//Skip the '-', as it is part of the key. This is a "list entry", thus there is no key.
+			return Ok((None, position + 1));
+		}
+		match rest.iter().position(|c| *c == b':' || *c == b'#') {
+			None => {
+				jecs_error!(row, line.len(), UnterminatedKey, "Unexpected line end while reading key") //Key never completely read
+			}
+			Some(end) if rest[end] == b'#' => {
+				jecs_error!(row, position + end, InvalidKeyCharacter, "key may not contain a # character") //Key never completely read
+			}
+			Some(end) => {
+				//Remove any trailing spaces from the key. As a key may not have spaces at its end. The ':' is part of the key.
+				Ok((Some(Cow::Borrowed(line[position..position + end].trim_end_matches(' '))), position + end + 1))
 			}
-			//Remove any trailing spaces from the key. As a key may not have spaces at its end.
-			Ok(Some(key_builder.trim_end_matches(|c| c == ' ').to_string()))
-		} else {
-			iterator.next(); //Skip the '-', as it is part of the key.
-			Ok(None) //This is a "list entry", thus there is no key.
 		}
 	}
 	
-	fn read_value<'a>(mut row: usize, original_indentation: usize, iterator: &mut Peekable<Chars>, line_iterator: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>) -> Result<Option<String>, JecsCorruptedDataError> {
-		let content = read_value_raw(iterator);
-		if content.is_none() || content.as_ref().unwrap() != "\"\"\"" {
+	//Returns the value, the position where its line got left (the end or a comment) and the last row of the value.
+	fn read_value<'a>(mut row: usize, original_indentation: usize, line: &'a str, position: usize, line_iterator: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>, warnings: &mut Vec<JecsWarning>) -> Result<(Option<Cow<'a, str>>, usize, usize), JecsCorruptedDataError> {
+		let (content, end) = read_value_raw(line, position);
+		if content.as_deref() != Some("\"\"\"") {
 			//Not a multi-line string, return
-			return Ok(content);
+			return Ok((content, end, row));
 		}
 		//Value is a multi-line string, thus read more lines until the value is fully read:
 		let mut string_builder = String::new();
@@ -184,14 +526,14 @@ fn parse_line<'a>((row, line): (usize, &str), line_iterator: &mut Peekable<impl
 			//Get next line:
 			let tuple = line_iterator.next();
 			if tuple.is_none() {
-				jecs_error!(row, "Multi-line string started, but file ends unexpectedly");
+				jecs_error!(row, 0, UnterminatedMultiLineString, "Multi-line string started, but file ends unexpectedly");
 			}
 			let (next_row, content) = tuple.unwrap();
 			row = next_row; //Update the row index, to show correct row in errors
-			let mut iterator = content.chars().peekable();
+			lint_line(row, content, warnings);
 			
 			//Get indentation (and skip spaces) of next line:
-			let indentation = match read_indentation(row, &mut iterator, false)? {
+			let indentation = match read_indentation(row, content, false)? {
 				None => {
 					//Line simply ends, save a newline and proceed with the next line
 					if wrote_first_line {
@@ -208,22 +550,22 @@ fn parse_line<'a>((row, line): (usize, &str), line_iterator: &mut Peekable<impl
 				None => {
 					//First line, check and save indentation
 					if indentation <= original_indentation {
-						jecs_error!(row, "Multi-line string lines must have more indentation than its opener");
+						jecs_error!(row, indentation, MultiLineStringNotIndented, "Multi-line string lines must have more indentation than its opener");
 					}
 					last_indentation = Some(indentation);
 				}
 				Some(last_indentation) => {
 					if last_indentation != indentation {
-						jecs_error!(row, "Multi-line string lines must have consistent indentation until its terminator (\"\"\")");
+						jecs_error!(row, indentation, MultiLineStringInconsistentIndentation, "Multi-line string lines must have consistent indentation until its terminator (\"\"\")");
 					}
 				}
 			}
 		
 			//Get actual content:
-			let content = read_value_raw(&mut iterator).unwrap(); //It is impossible to get None here, as the indentation check would have terminated then.
+			let content = read_value_raw(content, indentation).0.unwrap(); //It is impossible to get None here, as the indentation check would have terminated then.
 			if content == "\"\"\"" {
 				//Found termination of multi-line string.
-				return Ok(Some(string_builder));
+				return Ok((Some(Cow::Owned(string_builder)), end, row));
 			}
 			if wrote_first_line {
 				string_builder.push('\n');
@@ -233,82 +575,154 @@ fn parse_line<'a>((row, line): (usize, &str), line_iterator: &mut Peekable<impl
 		}
 	}
 	
-	fn read_value_raw(iterator: &mut Peekable<Chars>) -> Option<String> {
-		if iterator.peek().is_none() || *iterator.peek().unwrap() == '#' {
-			None //The line has no value as it reached the end. Or the line has reached a comment and thus there is no value.
-		} else {
-			let mut value_builder = String::new();
-			//It is ensured, that the very first character exists and is not a comment.
-			loop {
-				let c = match iterator.next() {
-					None => break,
-					Some(c) => c,
-				};
-				
-				if c == '\\' && iterator.peek().is_some() && *iterator.peek().unwrap() == '#' {
-					value_builder.push('#');
-					iterator.next(); //Skip the '#'
-				} else if c == '#' {
-					break; //Reached end of content, rest is comment.
-				} else {
-					//Append normal data:
-					value_builder.push(c);
+	//Returns the value and the position it ends at, which is the line end or the '#' of a comment.
+	fn read_value_raw(line: &str, position: usize) -> (Option<Cow<'_, str>>, usize) {
+		if position == line.len() || line.as_bytes()[position] == b'#' {
+			return (None, position); //The line has no value as it reached the end. Or the line has reached a comment and thus there is no value.
+		}
+		//It is ensured, that the very first character exists and is not a comment.
+		//Stops in front of an unescaped '#', as the rest is comment. A '#' is escaped, if the byte in front of it is a '\':
+		let mut value_builder: Option<String> = None; //Only needed once an escaped '#' got found
+		let mut start = position;
+		let end = loop {
+			match line[start..].find('#') {
+				None => break line.len(),
+				Some(index) if line.as_bytes()[start + index - 1] == b'\\' => {
+					let builder = value_builder.get_or_insert_with(String::new);
+					builder.push_str(&line[start..start + index - 1]);
+					builder.push('#');
+					start += index + 1;
 				}
+				Some(index) => break start + index,
 			}
-			
-			Some(value_builder.trim_end_matches(|c| c == ' ').to_string())
-		}
+		};
+		let value = match value_builder {
+			None => Cow::Borrowed(line[position..end].trim_end_matches(' ')),
+			Some(mut builder) => {
+				builder.push_str(&line[start..end]);
+				builder.truncate(builder.trim_end_matches(' ').len());
+				Cow::Owned(builder)
+			}
+		};
+		(Some(value), end)
 	}
 }
 
-#[derive(Debug)]
-struct LineContext {
-	meta: LineMeta,
-	children: Vec<LineContext>,
+//An entry on the stack of the TreeParser. Its node is complete except for the children, which are still on the stack.
+struct StackEntry<N, K> {
+	key: Option<K>,
+	row: usize,
+	indentation: usize,
+	node: N,
 	expected_child_indentation: usize,
 	determined_type: JecsTypeInner,
 }
 
-impl LineContext {
-	fn new(meta: LineMeta) -> Self {
-		Self {
-			determined_type: if meta.is_parent() { JecsTypeInner::Any } else { JecsTypeInner::Value },
-			meta,
-			children: Vec::new(),
+//Builds the output nodes while the lines are consumed. The stack holds the current line and all of its parents,
+// entries are added to their parent node as soon as a following line shows that they got all of their children.
+struct TreeParser<'b, 't, N: ParsedNode<'t>> {
+	context: N::Context,
+	root: N,
+	stack: &'b mut Vec<StackEntry<N, N::Key>>,
+	lenient_indentation: bool,
+	read_null: bool,
+	duplicate_keys: DuplicateKeys,
+	text: &'t str, //Only used to find the earlier entry of duplicate keys
+	warnings: &'b mut Vec<JecsWarning>,
+	indentation_step: Option<usize>, //The first step between a parent and its children, others are expected to match it
+}
+
+impl<'t, N: ParsedNode<'t>> TreeParser<'_, 't, N> {
+	fn push(&mut self, mut meta: LineMeta<'t>) {
+		let determined_type = if meta.is_parent() {
+			JecsTypeInner::Any
+		} else if meta.is_null && self.read_null {
+			JecsTypeInner::Null
+		} else if meta.is_null {
+			JecsTypeInner::NullAsAny
+		} else {
+			JecsTypeInner::Value
+		};
+		self.stack.push(StackEntry {
+			node: N::from_line(self.context, &mut meta, &determined_type),
+			key: meta.key.map(|key| N::key(self.context, key)),
+			row: meta.row,
+			indentation: meta.indentation,
 			expected_child_indentation: 0,
+			determined_type,
+		});
+	}
+	
+	//Adds the complete entry to the node of its parent, which is the top of the stack, or the root if the stack is empty.
+	fn add_to_parent(&mut self, entry: StackEntry<N, N::Key>) -> Result<(), JecsCorruptedDataError> {
+		let context = self.context;
+		let (parent, parent_row) = match self.stack.last_mut() {
+			Some(parent) => (&mut parent.node, parent.row),
+			None => (&mut self.root, 0),
+		};
+		if entry.key.as_ref().is_some_and(|key| parent.has_child(key)) {
+			self.handle_duplicate_key(parent_row, &entry)?;
 		}
+		let parent = match self.stack.last_mut() {
+			Some(parent) => &mut parent.node,
+			None => &mut self.root,
+		};
+		parent.add_child(context, entry.key, entry.node);
+		Ok(())
 	}
-}
-
-struct TreeParser {
-	roots: Vec<LineContext>,
-	stack: Vec<LineContext>,
-}
-
-impl Default for TreeParser {
-	fn default() -> Self {
-		Self {
-			roots: Vec::new(),
-			stack: Vec::new(),
+	
+	//Duplicates are rare, thus the earlier entry is only searched for in the text once one is found. It is the closest line
+	// between the parent and the entry, which has the indentation and key of the entry.
+	fn handle_duplicate_key(&mut self, parent_row: usize, entry: &StackEntry<N, N::Key>) -> Result<(), JecsCorruptedDataError> {
+		let key = entry.key.as_ref().unwrap().as_ref();
+		let earlier_row = self.text.lines()
+			.enumerate().map(|(index, line)| (index + 1, line))
+			.skip(parent_row)
+			.take_while(|(row, _)| *row < entry.row)
+			.filter(|(_, line)| {
+				let content = line.trim_start_matches(' ');
+				line.len() - content.len() == entry.indentation
+					&& content.strip_prefix(key).is_some_and(|rest| rest.trim_start_matches(' ').starts_with(':'))
+			})
+			.last()
+			.map(|(row, _)| row);
+		//Lenient indentation might have moved the earlier entry, then its line is not found:
+		let earlier = match earlier_row {
+			Some(row) => format!("in line {}", row),
+			None => "before".to_string(),
+		};
+		match self.duplicate_keys {
+			DuplicateKeys::Reject => {
+				jecs_error!(entry.row, entry.indentation, DuplicateKey, "Key '{}' is already used {} within the same parent", key, earlier)
+			}
+			DuplicateKeys::Tolerate => self.warnings.push(JecsWarning {
+				row: entry.row,
+				column: entry.indentation,
+				code: JecsErrorCode::ReplacedDuplicateKey,
+				description: format!("Key '{}' is already used {} within the same parent, this later entry replaces it", key, earlier),
+			}),
 		}
+		Ok(())
 	}
-}
-
-impl TreeParser {
-	fn add_validate_root(&mut self, line_meta: LineMeta) -> Result<(), JecsCorruptedDataError> {
+	
+	fn add_validate_root(&mut self, mut line_meta: LineMeta<'t>) -> Result<(), JecsCorruptedDataError> {
 		if line_meta.indentation != 0 {
-			jecs_error!(line_meta.row, "Root level entries need indentation level {}, but got {}", 0, line_meta.indentation);
+			if !self.lenient_indentation {
+				jecs_error!(line_meta.row, line_meta.key_columns.start, IndentedRootEntry, "Root level entries need indentation level {}, but got {}", 0, line_meta.indentation);
+			}
+			self.warnings.push(indentation_warning(line_meta.row, 0, line_meta.indentation));
+			line_meta.indentation = 0;
 		}
 		if line_meta.is_list() {
-			jecs_error!(line_meta.row, "Root level entries need a key, they may not be list entries");
+			jecs_error!(line_meta.row, line_meta.key_columns.start, RootListEntry, "Root level entries need a key, they may not be list entries");
 		}
-		self.stack.push(LineContext::new(line_meta));
+		self.push(line_meta);
 		Ok(())
 	}
 	
-	fn append_next_line(&mut self, current_line_meta: LineMeta) -> Result<(), JecsCorruptedDataError> {
+	fn append_next_line(&mut self, current_line_meta: LineMeta<'t>) -> Result<(), JecsCorruptedDataError> {
 		let previous_line = self.stack.last_mut().unwrap();
-		match current_line_meta.indentation.cmp(&previous_line.meta.indentation) {
+		match current_line_meta.indentation.cmp(&previous_line.indentation) {
 			Ordering::Greater => {
 				//New child entry.
 				self.handle_new_child_line(current_line_meta)?;
@@ -326,173 +740,515 @@ impl TreeParser {
 		return Ok(());
 	}
 	
-	fn handle_new_child_line(&mut self, current_line_meta: LineMeta) -> Result<(), JecsCorruptedDataError> {
+	fn handle_new_child_line(&mut self, mut current_line_meta: LineMeta<'t>) -> Result<(), JecsCorruptedDataError> {
 		let previous_line = self.stack.last_mut().unwrap(); //For borrowing reasons, this has to be queried here again.
 		//Parent node type MUST be Any (no value):
 		if previous_line.determined_type != JecsTypeInner::Any {
-			jecs_error!(current_line_meta.row, "Child entries can only be added to entries without value");
+			if !self.lenient_indentation {
+				jecs_error!(current_line_meta.row, current_line_meta.key_columns.start, ChildOfValue, "Child entries can only be added to entries without value");
+			}
+			//The entry is most likely meant as sibling:
+			self.warnings.push(indentation_warning(current_line_meta.row, previous_line.indentation, current_line_meta.indentation));
+			current_line_meta.indentation = previous_line.indentation;
+			return self.handle_new_sibling_line(current_line_meta);
+		}
+		let step = current_line_meta.indentation - previous_line.indentation;
+		match self.indentation_step {
+			None => self.indentation_step = Some(step),
+			Some(expected_step) if expected_step != step => {
+				self.warnings.push(JecsWarning {
+					row: current_line_meta.row,
+					column: current_line_meta.key_columns.start,
+					code: JecsErrorCode::InconsistentIndentationStep,
+					description: format!("Indentation step of {} differs from the step of {} used before", step, expected_step),
+				});
+			}
+			_ => {}
 		}
-		//Indentation and type of the parent entry, can only be inferred from the child entry. Apply now:
-		previous_line.determined_type = if current_line_meta.is_list() { JecsTypeInner::List } else { JecsTypeInner::Map };
+		//Indentation and type of the parent entry, can only be inferred from the child entry. Apply now and turn the node of the parent into a collection:
+		previous_line.determined_type = current_line_meta.get_data_type();
 		previous_line.expected_child_indentation = current_line_meta.indentation;
+		previous_line.node.make_collection(self.context, &previous_line.determined_type);
 		
-		self.stack.push(LineContext::new(current_line_meta));
+		self.push(current_line_meta);
 		Ok(())
 	}
 	
-	fn handle_new_sibling_line(&mut self, current_line_meta: LineMeta) -> Result<(), JecsCorruptedDataError> {
+	fn handle_new_sibling_line(&mut self, current_line_meta: LineMeta<'t>) -> Result<(), JecsCorruptedDataError> {
 		//First remove the previous entry, and inject it into the previous parent (or root):
 		let previous_line = self.stack.pop().unwrap();
 		if self.stack.is_empty() {
-			//Save the old root node and replace with a new one:
-			self.roots.push(previous_line);
+			//Save the old root entry and replace with a new one:
+			self.add_to_parent(previous_line)?;
 			self.add_validate_root(current_line_meta)?; //The indentation validation here is not required.
 		} else {
 			//We got a parent node. Merge previous into that and take its place.
-			let parent = self.stack.last_mut().unwrap();
+			let parent = self.stack.last().unwrap();
 			if parent.determined_type != current_line_meta.get_data_type() {
-				jecs_error!(current_line_meta.row, "Cannot mix list and dict collection entries with the same parent");
+				jecs_error!(current_line_meta.row, current_line_meta.key_columns.start, MixedListAndMap, "Cannot mix list and dict collection entries with the same parent");
 			}
-			parent.children.push(previous_line);
+			self.add_to_parent(previous_line)?;
 			//Take the place of the previous line
-			self.stack.push(LineContext::new(current_line_meta));
+			self.push(current_line_meta);
 		}
 		Ok(())
 	}
 	
-	fn handle_new_parents_sibling_line(&mut self, current_line_meta: LineMeta) -> Result<(), JecsCorruptedDataError> {
+	fn handle_new_parents_sibling_line(&mut self, mut current_line_meta: LineMeta<'t>) -> Result<(), JecsCorruptedDataError> {
+		//The entry removed in the previous iteration. It is only added to its parent at the start of the next iteration,
+		// as lenient indentation might put it back onto the stack.
+		let mut completed_child: Option<StackEntry<N, N::Key>> = None;
 		loop {
 			//There exists an element with higher indentation, thus it has to be removed and merged to its parent.
 			//This may have to be done repeatedly as long as there is an entry on the stack with higher indentation.
-			let previous_entry_with_higher_indentation = self.stack.pop().unwrap();
+			let mut previous_entry_with_higher_indentation = self.stack.pop().unwrap();
+			let level_indentation = self.stack.last().map_or(0, |parent| parent.expected_child_indentation);
+			if self.lenient_indentation && current_line_meta.indentation > level_indentation {
+				//The indentation is between the level of the previous entry and the level of its children. Use the closer one, the deeper one on a tie:
+				let child_level_indentation = previous_entry_with_higher_indentation.expected_child_indentation;
+				let use_child_level = child_level_indentation - current_line_meta.indentation <= current_line_meta.indentation - level_indentation;
+				let indentation = if use_child_level { child_level_indentation } else { level_indentation };
+				self.warnings.push(indentation_warning(current_line_meta.row, indentation, current_line_meta.indentation));
+				current_line_meta.indentation = indentation;
+				if use_child_level {
+					//Restore the stack, so that the last child of the previous entry becomes the sibling:
+					self.stack.push(previous_entry_with_higher_indentation);
+					self.stack.push(completed_child.unwrap());
+					return self.handle_new_sibling_line(current_line_meta);
+				}
+			}
+			if let Some(child) = completed_child.take() {
+				if child.key.as_ref().is_some_and(|key| previous_entry_with_higher_indentation.node.has_child(key)) {
+					self.handle_duplicate_key(previous_entry_with_higher_indentation.row, &child)?;
+				}
+				previous_entry_with_higher_indentation.node.add_child(self.context, child.key, child.node);
+			}
 			if self.stack.is_empty() {
 				//Stack is empty, we must be adding a new root level entry.
-				//Save the old root node and replace with a new one:
-				self.roots.push(previous_entry_with_higher_indentation);
+				//Save the old root entry and replace with a new one:
+				self.add_to_parent(previous_entry_with_higher_indentation)?;
 				self.add_validate_root(current_line_meta)?;
 				break; //Done, as the new entry is injected properly.
 			} else {
-				//The stack is not empty, thus add the top line to its parent on the stack:
-				let potential_parent = self.stack.last_mut().unwrap();
-				potential_parent.children.push(previous_entry_with_higher_indentation);
+				//The stack is not empty, thus the top line belongs to the parent on the stack.
 				//From now on, work with the parent (now top from stack).
+				let potential_parent = self.stack.last().unwrap();
 				
 				//First confirm, that the indentation is not above the next parent. As that would be impossible.
 				//We have less indentation for this line that the child of the parent, thus the indentation cannot be bigger than the parents child indentation.
 				if current_line_meta.indentation > potential_parent.expected_child_indentation {
-					jecs_error!(current_line_meta.row, "Wrongly indented JECS entry! Expected indentation {} but got {}", potential_parent.expected_child_indentation, current_line_meta.indentation);
+					jecs_error!(current_line_meta.row, current_line_meta.key_columns.start, WrongIndentation, "Wrongly indented JECS entry! Expected indentation {} but got {}", potential_parent.expected_child_indentation, current_line_meta.indentation);
 				}
 				//Check if the indentation level is the same as the current parent. If that is the case, we found the correct new parent.
 				if current_line_meta.indentation == potential_parent.expected_child_indentation {
 					if potential_parent.determined_type != current_line_meta.get_data_type() {
-						jecs_error!(current_line_meta.row, "Cannot mix list and dict collection entries within the same parent");
+						jecs_error!(current_line_meta.row, current_line_meta.key_columns.start, MixedListAndMap, "Cannot mix list and dict collection entries within the same parent");
 					}
 					
-					self.stack.push(LineContext::new(current_line_meta));
+					self.add_to_parent(previous_entry_with_higher_indentation)?;
+					self.push(current_line_meta);
 					break;
 				}
 				//else The indentation level is below the parent, thus the current line must be the child of another parent (or root).
 				//Repeat the process with the next parent.
+				completed_child = Some(previous_entry_with_higher_indentation);
 			}
 		}
 		Ok(())
 	}
 	
-	fn post_line_addition_cleanup(&mut self) {
+	fn finish(mut self) -> Result<N, JecsCorruptedDataError> {
 		//Merge every stack entry into its parent, until the stack is empty.
-		//The last stack entry gets added as a root entry.
-		while !self.stack.is_empty() {
-			let entry = self.stack.pop().unwrap();
-			if let Some(parent) = self.stack.last_mut() {
-				parent.children.push(entry);
-			} else {
-				self.roots.push(entry);
-			}
+		//The last stack entry gets added to the root.
+		while let Some(entry) = self.stack.pop() {
+			self.add_to_parent(entry)?;
 		}
+		Ok(self.root)
 	}
-	
-	fn finalize_to_map(self) -> Result<HashMap<String, JecsType>, JecsCorruptedDataError> {
-		struct ConvertedMeta {
-			name: Option<String>,
-			converted: JecsType,
-			child_count: usize,
+}
+
+//The TreeParser rules, but only the indentation and type of the entries on the stack are kept.
+struct ValidatedLevel {
+	indentation: usize,
+	determined_type: JecsTypeInner,
+	expected_child_indentation: usize,
+}
+
+struct Validator {
+	levels: Vec<ValidatedLevel>,
+	errors: Vec<JecsCorruptedDataError>,
+}
+
+impl Validator {
+	fn validate_line<'a>(&mut self, row: usize, line: &'a str, line_iterator: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>) {
+		let indentation = line.len() - line.trim_start_matches(' ').len();
+		let content = &line[indentation..];
+		if content.is_empty() || content.starts_with('#') {
+			return;
 		}
-		//Create a root component, which the map can be extracted from later:
-		let mut converted_stack = vec![ConvertedMeta {
-			name: None,
-			converted: JecsType::Map(HashMap::with_capacity(self.roots.len())),
-			child_count: self.roots.len(),
-		}];
-		let mut process_stack : Vec<LineContext> = self.roots.into_iter().rev().collect();
-		
-		while let Some(mut entry) = process_stack.pop() {
-			//First create a converted Jecs type without child components:
-			let converted_entry = match entry.determined_type {
-				JecsTypeInner::Any => JecsType::Any(),
-				JecsTypeInner::Value => {
-					JecsType::Value(entry.meta.value.take().unwrap())
-				},
-				JecsTypeInner::Map => {
-					JecsType::Map(HashMap::with_capacity(entry.children.len()))
+		if content.starts_with(':') {
+			return self.error(row, indentation, JecsErrorCode::MissingKey, "Line has no key, encountered ':'".to_string());
+		}
+		//Read key:
+		let (is_list, rest) = match content.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => match content.find([':', '#']) {
+				None => return self.error(row, line.len(), JecsErrorCode::UnterminatedKey, "Unexpected line end while reading key".to_string()),
+				Some(end) if content[end..].starts_with('#') => {
+					return self.error(row, indentation + end, JecsErrorCode::InvalidKeyCharacter, "key may not contain a # character".to_string());
 				}
-				JecsTypeInner::List => {
-					JecsType::List(Vec::with_capacity(entry.children.len()))
+				Some(end) => (false, &content[end + 1..]),
+			},
+		};
+		//Read value, multi-line strings are checked before their opener, like the parser does it:
+		let value = raw_value(rest.trim_start_matches(' '));
+		if value == Some("\"\"\"") {
+			self.skip_multi_line_string(row, indentation, line_iterator);
+		}
+		self.add_entry(row, indentation, is_list, value.is_some());
+	}
+	
+	fn skip_multi_line_string<'a>(&mut self, opener_row: usize, original_indentation: usize, line_iterator: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>) {
+		let mut last_row = opener_row;
+		let mut string_indentation = None;
+		while let Some(&(row, line)) = line_iterator.peek() {
+			last_row = row;
+			let indentation = line.len() - line.trim_start_matches(' ').len();
+			let content = &line[indentation..];
+			if content.is_empty() || content.starts_with('#') {
+				line_iterator.next();
+				continue;
+			}
+			match string_indentation {
+				None if indentation <= original_indentation => {
+					//The string most likely just misses its content, the line is checked as normal entry:
+					return self.error(row, indentation, JecsErrorCode::MultiLineStringNotIndented, "Multi-line string lines must have more indentation than its opener".to_string());
 				}
-			};
-			
-			if entry.children.len() == 0 {
-				//If the entry has no children, it needs to immediately be injected into its parent (on the converted stack).
-				//If that or further parent has all of their children, they get merged into their parent as well.
-				
-				//Keep a reference to the latest parent and create a new converted meta entry for the currently converted entry:
-				let mut parent = converted_stack.last_mut().unwrap();
-				let mut child = Some(ConvertedMeta {
-					name: entry.meta.key.take(),
-					converted: converted_entry,
-					child_count: entry.children.len()
-				});
-				//The process for all iterations stays the same, only parent and child variables need to be updated.
-				loop {
-					//Add the child into the parent component. During that, check if the parent is full (has_more).
-					let mut has_more = false;
-					if let JecsType::Map(ref mut map) = &mut parent.converted {
-						let converted_meta = child.take().unwrap();
-						map.insert(converted_meta.name.unwrap(), converted_meta.converted);
-						has_more = parent.child_count > map.len();
-					} else if let JecsType::List(ref mut list) = &mut parent.converted {
-						list.push(child.take().unwrap().converted);
-						has_more = parent.child_count > list.len();
-					} //else - impossible.
-					
-					if has_more || converted_stack.len() <= 1 {
-						//Parent is not full, or there is no more child to merge on the converted stack.
-						break; //Stop and inject the next entry.
+				None => string_indentation = Some(indentation),
+				Some(expected_indentation) if expected_indentation != indentation => {
+					self.error(row, indentation, JecsErrorCode::MultiLineStringInconsistentIndentation, "Multi-line string lines must have consistent indentation until its terminator (\"\"\")".to_string());
+				}
+				_ => {}
+			}
+			line_iterator.next();
+			if raw_value(content) == Some("\"\"\"") {
+				return;
+			}
+		}
+		self.error(last_row, 0, JecsErrorCode::UnterminatedMultiLineString, "Multi-line string started, but file ends unexpectedly".to_string());
+	}
+	
+	//Applies the checks of TreeParser::append_next_line(), the stack is only changed if the entry is valid.
+	fn add_entry(&mut self, row: usize, indentation: usize, is_list: bool, has_value: bool) {
+		let entry_type = if is_list { JecsTypeInner::List } else { JecsTypeInner::Map };
+		let level = ValidatedLevel {
+			indentation,
+			determined_type: if has_value { JecsTypeInner::Value } else { JecsTypeInner::Any },
+			expected_child_indentation: 0,
+		};
+		let top_indentation = match self.levels.last() {
+			None => return self.add_root(row, level, is_list),
+			Some(top) => top.indentation,
+		};
+		match indentation.cmp(&top_indentation) {
+			Ordering::Greater => {
+				let top = self.levels.last_mut().unwrap();
+				if top.determined_type != JecsTypeInner::Any {
+					return self.error(row, indentation, JecsErrorCode::ChildOfValue, "Child entries can only be added to entries without value".to_string());
+				}
+				top.determined_type = entry_type;
+				top.expected_child_indentation = indentation;
+				self.levels.push(level);
+			}
+			Ordering::Equal => {
+				if self.levels.len() == 1 {
+					if is_list {
+						return self.error(row, indentation, JecsErrorCode::RootListEntry, "Root level entries need a key, they may not be list entries".to_string());
 					}
-					//Else there is no more child to add to the parent, thus merge that parent as well:
-					child = Some(converted_stack.pop().unwrap());
-					parent = converted_stack.last_mut().unwrap();
+				} else if self.levels[self.levels.len() - 2].determined_type != entry_type {
+					return self.error(row, indentation, JecsErrorCode::MixedListAndMap, "Cannot mix list and dict collection entries with the same parent".to_string());
 				}
-				
-			} else {
-				//Store the node in the output stack, for later access by the now queued child nodes:
-				converted_stack.push(ConvertedMeta {
-					name: entry.meta.key.take(),
-					converted: converted_entry,
-					child_count: entry.children.len()
-				});
-				//Children to process first. Queue them up for processing in the next iteration.
-				// Important is to reverse the order. So that the first child gets processed first when being popped from the stack.
-				for child in entry.children.into_iter().rev() {
-					process_stack.push(child);
+				self.levels.pop();
+				self.levels.push(level);
+			}
+			Ordering::Less => {
+				//Go up the stack until the level with this indentation is found:
+				let mut depth = self.levels.len() - 1;
+				while depth > 0 {
+					let parent = &self.levels[depth - 1];
+					if indentation > parent.expected_child_indentation {
+						return self.error(row, indentation, JecsErrorCode::WrongIndentation, format!("Wrongly indented JECS entry! Expected indentation {} but got {}", parent.expected_child_indentation, indentation));
+					}
+					if indentation == parent.expected_child_indentation {
+						if parent.determined_type != entry_type {
+							return self.error(row, indentation, JecsErrorCode::MixedListAndMap, "Cannot mix list and dict collection entries within the same parent".to_string());
+						}
+						self.levels.truncate(depth);
+						self.levels.push(level);
+						return;
+					}
+					depth -= 1;
 				}
+				self.add_root(row, level, is_list);
 			}
 		}
-		
-		let root = converted_stack.pop().unwrap().converted;
-		if let JecsType::Map(map) = root {
-			Ok(map)
-		} else {
-			unreachable!("Impossible to reach code: Something is wrong with the LineContext to JecsType converting code. Did get wrong root type.");
+	}
+	
+	fn add_root(&mut self, row: usize, level: ValidatedLevel, is_list: bool) {
+		if level.indentation != 0 {
+			return self.error(row, level.indentation, JecsErrorCode::IndentedRootEntry, format!("Root level entries need indentation level {}, but got {}", 0, level.indentation));
+		}
+		if is_list {
+			return self.error(row, level.indentation, JecsErrorCode::RootListEntry, "Root level entries need a key, they may not be list entries".to_string());
+		}
+		self.levels.clear();
+		self.levels.push(level);
+	}
+	
+	fn error(&mut self, row: usize, column: usize, code: JecsErrorCode, description: String) {
+		self.errors.push(JecsCorruptedDataError {
+			row,
+			column,
+			code,
+			description,
+		});
+	}
+}
+
+//The value in front of the first unescaped '#', without trailing spaces. Escapes stay as they are.
+fn raw_value(text: &str) -> Option<&str> {
+	let mut end = text.len();
+	let mut previous = None;
+	for (index, c) in text.char_indices() {
+		if c == '#' && previous != Some('\\') {
+			end = index;
+			break;
+		}
+		previous = Some(c);
+	}
+	if end == 0 {
+		None //Empty, or only a comment
+	} else {
+		Some(text[..end].trim_end_matches(' '))
+	}
+}
+
+fn indentation_warning(row: usize, expected: usize, got: usize) -> JecsWarning {
+	JecsWarning {
+		row,
+		column: got,
+		code: JecsErrorCode::AdjustedIndentation,
+		description: format!("Wrongly indented JECS entry! Expected indentation {} but got {}, treated as {}", expected, got, expected),
+	}
+}
+
+//The output structures of the parser, built by the TreeParser while the lines are consumed. The lifetime is the one of the parsed text.
+//The context is passed to every call, for nodes which need to allocate somewhere special.
+trait ParsedNode<'t>: Sized {
+	type Context: Copy;
+	type Key: AsRef<str>;
+	
+	fn new_root(context: Self::Context) -> Self;
+	
+	//Creates the node without its children, these are added with add_child().
+	fn from_line(context: Self::Context, meta: &mut LineMeta<'t>, data_type: &JecsTypeInner) -> Self;
+	
+	//Parents start as Any node, their first child determines the collection type.
+	fn make_collection(&mut self, context: Self::Context, data_type: &JecsTypeInner);
+	
+	fn key(context: Self::Context, key: Cow<'t, str>) -> Self::Key;
+	
+	//The key is None for list entries.
+	fn add_child(&mut self, context: Self::Context, key: Option<Self::Key>, child: Self);
+	
+	//If a child with the key was already added, which the next one with that key replaces.
+	fn has_child(&self, key: &Self::Key) -> bool;
+}
+
+impl<'t> ParsedNode<'t> for JecsType {
+	type Context = ();
+	type Key = String;
+	
+	fn new_root(_: ()) -> Self {
+		JecsType::Map(JecsMap::default())
+	}
+	
+	fn from_line(_: (), meta: &mut LineMeta, data_type: &JecsTypeInner) -> Self {
+		match data_type {
+			JecsTypeInner::Any | JecsTypeInner::NullAsAny => JecsType::Any(),
+			JecsTypeInner::Null => JecsType::Null(),
+			JecsTypeInner::Value => JecsType::value(meta.value.take().unwrap()),
+			JecsTypeInner::Map => JecsType::Map(JecsMap::default()),
+			JecsTypeInner::List => JecsType::List(Vec::new()),
 		}
 	}
+	
+	fn make_collection(&mut self, _: (), data_type: &JecsTypeInner) {
+		*self = match data_type {
+			JecsTypeInner::List => JecsType::List(Vec::new()),
+			_ => JecsType::Map(JecsMap::default()),
+		};
+	}
+	
+	fn key(_: (), key: Cow<'t, str>) -> String {
+		key.into_owned()
+	}
+	
+	fn add_child(&mut self, _: (), key: Option<String>, child: Self) {
+		match self {
+			JecsType::Map(map) => {
+				map.insert(key.unwrap(), child);
+			}
+			JecsType::List(list) => list.push(child),
+			_ => {} //Impossible, only collections get children.
+		}
+	}
+	
+	fn has_child(&self, key: &String) -> bool {
+		matches!(self, JecsType::Map(map) if map.contains_key(key))
+	}
+}
+
+impl<'t> ParsedNode<'t> for JecsSpannedEntry {
+	type Context = ();
+	type Key = String;
+	
+	fn new_root(_: ()) -> Self {
+		JecsSpannedEntry::root(JecsSpannedMap::default())
+	}
+	
+	fn from_line(_: (), meta: &mut LineMeta, data_type: &JecsTypeInner) -> Self {
+		JecsSpannedEntry {
+			span: JecsSpan {
+				row: meta.row,
+				last_row: meta.last_row,
+				key_columns: meta.key_columns.clone(),
+				value_columns: meta.value_columns.clone(),
+			},
+			value: match data_type {
+				//Parsed with the default options, which never read Null entries:
+				JecsTypeInner::Any | JecsTypeInner::NullAsAny | JecsTypeInner::Null => JecsSpannedType::Any(),
+				JecsTypeInner::Value => JecsSpannedType::Value(meta.value.take().unwrap().into_owned()),
+				JecsTypeInner::Map => JecsSpannedType::Map(JecsSpannedMap::default()),
+				JecsTypeInner::List => JecsSpannedType::List(Vec::new()),
+			},
+		}
+	}
+	
+	fn make_collection(&mut self, _: (), data_type: &JecsTypeInner) {
+		self.value = match data_type {
+			JecsTypeInner::List => JecsSpannedType::List(Vec::new()),
+			_ => JecsSpannedType::Map(JecsSpannedMap::default()),
+		};
+	}
+	
+	fn key(_: (), key: Cow<'t, str>) -> String {
+		key.into_owned()
+	}
+	
+	fn add_child(&mut self, _: (), key: Option<String>, child: Self) {
+		//Parents span over all their children:
+		self.span.last_row = self.span.last_row.max(child.span.last_row);
+		match &mut self.value {
+			JecsSpannedType::Map(map) => {
+				map.insert(key.unwrap(), child);
+			}
+			JecsSpannedType::List(list) => list.push(child),
+			_ => {} //Impossible, only collections get children.
+		}
+	}
+	
+	fn has_child(&self, key: &String) -> bool {
+		matches!(&self.value, JecsSpannedType::Map(map) if map.contains_key(key))
+	}
+}
+
+//Keys and values are copied into the arena, the text does not need to outlive the tree.
+#[cfg(feature = "arena")]
+impl<'t, 'bump> ParsedNode<'t> for JecsArenaType<'bump> {
+	type Context = &'bump Bump;
+	type Key = &'bump str;
+	
+	fn new_root(bump: &'bump Bump) -> Self {
+		JecsArenaType::Map(BumpVec::new_in(bump))
+	}
+	
+	fn from_line(bump: &'bump Bump, meta: &mut LineMeta, data_type: &JecsTypeInner) -> Self {
+		match data_type {
+			//Parsed with the default options, which never read Null entries:
+			JecsTypeInner::Any | JecsTypeInner::NullAsAny | JecsTypeInner::Null => JecsArenaType::Any(),
+			JecsTypeInner::Value => JecsArenaType::Value(bump.alloc_str(meta.value.as_ref().unwrap())),
+			JecsTypeInner::Map => JecsArenaType::Map(BumpVec::new_in(bump)),
+			JecsTypeInner::List => JecsArenaType::List(BumpVec::new_in(bump)),
+		}
+	}
+	
+	fn make_collection(&mut self, bump: &'bump Bump, data_type: &JecsTypeInner) {
+		*self = match data_type {
+			JecsTypeInner::List => JecsArenaType::List(BumpVec::new_in(bump)),
+			_ => JecsArenaType::Map(BumpVec::new_in(bump)),
+		};
+	}
+	
+	fn key(bump: &'bump Bump, key: Cow<'t, str>) -> &'bump str {
+		bump.alloc_str(&key)
+	}
+	
+	fn add_child(&mut self, _: &'bump Bump, key: Option<&'bump str>, child: Self) {
+		match self {
+			JecsArenaType::Map(map) => map.push((key.unwrap(), child)),
+			JecsArenaType::List(list) => list.push(child),
+			_ => {} //Impossible, only collections get children.
+		}
+	}
+	
+	//Maps keep every entry, the arena parser is always tolerant and searching the entries for every key would make it quadratic.
+	fn has_child(&self, _: &&'bump str) -> bool {
+		false
+	}
+}
+
+//Keys and values borrow from the text, unless they had to be unescaped.
+impl<'t> ParsedNode<'t> for JecsRef<'t> {
+	type Context = ();
+	type Key = Cow<'t, str>;
+	
+	fn new_root(_: ()) -> Self {
+		JecsRef::Map(JecsRefMap::default())
+	}
+	
+	fn from_line(_: (), meta: &mut LineMeta<'t>, data_type: &JecsTypeInner) -> Self {
+		match data_type {
+			JecsTypeInner::Any | JecsTypeInner::NullAsAny => JecsRef::Any(),
+			JecsTypeInner::Null => JecsRef::Null(),
+			JecsTypeInner::Value => JecsRef::Value(meta.value.take().unwrap()),
+			JecsTypeInner::Map => JecsRef::Map(JecsRefMap::default()),
+			JecsTypeInner::List => JecsRef::List(Vec::new()),
+		}
+	}
+	
+	fn make_collection(&mut self, _: (), data_type: &JecsTypeInner) {
+		*self = match data_type {
+			JecsTypeInner::List => JecsRef::List(Vec::new()),
+			_ => JecsRef::Map(JecsRefMap::default()),
+		};
+	}
+	
+	fn key(_: (), key: Cow<'t, str>) -> Cow<'t, str> {
+		key
+	}
+	
+	fn add_child(&mut self, _: (), key: Option<Cow<'t, str>>, child: Self) {
+		match self {
+			JecsRef::Map(map) => {
+				map.insert(key.unwrap(), child);
+			}
+			JecsRef::List(list) => list.push(child),
+			_ => {} //Impossible, only collections get children.
+		}
+	}
+	
+	fn has_child(&self, key: &Cow<'t, str>) -> bool {
+		matches!(self, JecsRef::Map(map) if map.contains_key(key))
+	}
 }
@@ -0,0 +1,177 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum JecsPathSegment {
+	Key(String), //Key of a map entry
+	Index(usize), //Index of a list entry
+}
+
+//Borrowed version of JecsPathSegment, to look up a single child by key or index without allocating (see JecsType::get()).
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum JecsIndex<'a> {
+	Key(&'a str),
+	Index(usize),
+}
+
+impl<'a> From<&'a str> for JecsIndex<'a> {
+	fn from(key: &'a str) -> Self {
+		JecsIndex::Key(key)
+	}
+}
+
+impl<'a> From<&'a String> for JecsIndex<'a> {
+	fn from(key: &'a String) -> Self {
+		JecsIndex::Key(key)
+	}
+}
+
+impl From<usize> for JecsIndex<'_> {
+	fn from(index: usize) -> Self {
+		JecsIndex::Index(index)
+	}
+}
+
+impl<'a> From<&'a JecsPathSegment> for JecsIndex<'a> {
+	fn from(segment: &'a JecsPathSegment) -> Self {
+		match segment {
+			JecsPathSegment::Key(key) => JecsIndex::Key(key),
+			JecsPathSegment::Index(index) => JecsIndex::Index(*index),
+		}
+	}
+}
+
+//A location inside of a JECS tree, written as "MainInfo.Components[3].Color".
+//Keys containing '.' or '[' cannot be expressed in the text form, but can still be built with the push functions.
+#[derive(Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug)]
+pub struct JecsPath {
+	segments: Vec<JecsPathSegment>,
+}
+
+impl JecsPath {
+	pub fn root() -> Self {
+		Self::default()
+	}
+	
+	pub fn parse(text: &str) -> Self {
+		let mut path = Self::root();
+		if text.is_empty() {
+			return path;
+		}
+		for part in text.split('.') {
+			//Split off trailing index brackets, as long as they contain valid numbers:
+			let mut key_end = part.len();
+			let mut indices = Vec::new();
+			while part[..key_end].ends_with(']') {
+				let open = match part[..key_end].rfind('[') {
+					None => break,
+					Some(open) => open,
+				};
+				match part[open + 1..key_end - 1].parse::<usize>() {
+					Ok(index) => indices.push(index),
+					Err(_) => break,
+				}
+				key_end = open;
+			}
+			if key_end != 0 || indices.is_empty() {
+				path.push_key(&part[..key_end]);
+			}
+			for index in indices.into_iter().rev() {
+				path.push_index(index);
+			}
+		}
+		path
+	}
+	
+	pub fn segments(&self) -> &[JecsPathSegment] {
+		&self.segments
+	}
+	
+	pub fn is_root(&self) -> bool {
+		self.segments.is_empty()
+	}
+	
+	pub fn len(&self) -> usize {
+		self.segments.len()
+	}
+	
+	pub fn is_empty(&self) -> bool {
+		self.is_root()
+	}
+	
+	pub fn last(&self) -> Option<&JecsPathSegment> {
+		self.segments.last()
+	}
+	
+	//Returns the last key of this path, if the path points to a map entry.
+	pub fn last_key(&self) -> Option<&str> {
+		if let Some(JecsPathSegment::Key(key)) = self.segments.last() {
+			return Some(key);
+		}
+		None
+	}
+	
+	pub fn push_key(&mut self, key: &str) {
+		self.segments.push(JecsPathSegment::Key(key.to_string()));
+	}
+	
+	pub fn push_index(&mut self, index: usize) {
+		self.segments.push(JecsPathSegment::Index(index));
+	}
+	
+	pub fn pop(&mut self) -> Option<JecsPathSegment> {
+		self.segments.pop()
+	}
+	
+	//Appends all segments of a path, which is relative to this one.
+	pub fn extend(&mut self, path: JecsPath) {
+		self.segments.extend(path.segments);
+	}
+	
+	pub fn join_key(&self, key: &str) -> Self {
+		let mut path = self.clone();
+		path.push_key(key);
+		path
+	}
+	
+	pub fn join_index(&self, index: usize) -> Self {
+		let mut path = self.clone();
+		path.push_index(index);
+		path
+	}
+}
+
+impl From<&str> for JecsPath {
+	fn from(text: &str) -> Self {
+		Self::parse(text)
+	}
+}
+
+impl From<&JecsPath> for JecsPath {
+	fn from(path: &JecsPath) -> Self {
+		path.clone()
+	}
+}
+
+impl Display for JecsPath {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		for (index, segment) in self.segments.iter().enumerate() {
+			match segment {
+				JecsPathSegment::Key(key) => {
+					if index != 0 {
+						write!(f, ".")?;
+					}
+					write!(f, "{}", key)?;
+				}
+				JecsPathSegment::Index(list_index) => {
+					write!(f, "[{}]", list_index)?;
+				}
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,89 @@
+//Python module 'ecc_jecs'. Build it with maturin ('maturin build --features python') or as 'cdylib' with the 'python' feature.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyList, PyString, PyTuple};
+
+use crate::parser;
+use crate::types::{JecsMap, JecsType};
+use crate::writer;
+
+#[pymodule]
+fn ecc_jecs(module: &Bound<'_, PyModule>) -> PyResult<()> {
+	module.add_function(wrap_pyfunction!(parse, module)?)?;
+	module.add_function(wrap_pyfunction!(query, module)?)?;
+	module.add_function(wrap_pyfunction!(write, module)?)?;
+	Ok(())
+}
+
+//Parses JECS text into dicts, lists and strings. Any entries become None.
+#[pyfunction]
+fn parse<'py>(py: Python<'py>, text: &str) -> PyResult<Bound<'py, PyAny>> {
+	let tree = JecsType::Map(parser::parse_jecs_string(text).map_err(value_error)?);
+	to_python(py, &tree)
+}
+
+//Parses JECS text and returns the entry at a path like "MainInfo.Components[3]", or None if there is none.
+#[pyfunction]
+fn query<'py>(py: Python<'py>, text: &str, path: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+	let tree = JecsType::Map(parser::parse_jecs_string(text).map_err(value_error)?);
+	tree.get_path(path).map(|entry| to_python(py, entry)).transpose()
+}
+
+//Writes a dict as JECS text. Values which are no strings are written as their str() representation.
+#[pyfunction]
+fn write(tree: &Bound<'_, PyAny>) -> PyResult<String> {
+	match from_python(tree)? {
+		JecsType::Map(map) => writer::write_jecs_string(&map).map_err(value_error),
+		_ => Err(PyValueError::new_err("JECS data must have a dict as root")),
+	}
+}
+
+fn value_error(error: impl ToString) -> PyErr {
+	PyValueError::new_err(error.to_string().trim_end().to_string())
+}
+
+fn to_python<'py>(py: Python<'py>, entry: &JecsType) -> PyResult<Bound<'py, PyAny>> {
+	Ok(match entry {
+		JecsType::Any() | JecsType::Null() => py.None().into_bound(py),
+		JecsType::Value(value) => PyString::new(py, value).into_any(),
+		JecsType::Map(map) => {
+			let dict = PyDict::new(py);
+			for (key, child) in map {
+				dict.set_item(key, to_python(py, child)?)?;
+			}
+			dict.into_any()
+		}
+		JecsType::List(list) => {
+			let python_list = PyList::empty(py);
+			for child in list {
+				python_list.append(to_python(py, child)?)?;
+			}
+			python_list.into_any()
+		}
+	})
+}
+
+fn from_python(object: &Bound<'_, PyAny>) -> PyResult<JecsType> {
+	if object.is_none() {
+		return Ok(JecsType::Any());
+	}
+	if let Ok(dict) = object.cast::<PyDict>() {
+		let mut map = JecsMap::with_capacity_and_hasher(dict.len(), Default::default());
+		for (key, child) in dict.iter() {
+			map.insert(key.str()?.to_string(), from_python(&child)?);
+		}
+		return Ok(JecsType::Map(map));
+	}
+	if let Ok(list) = object.cast::<PyList>() {
+		return Ok(JecsType::List(list.iter().map(|child| from_python(&child)).collect::<PyResult<_>>()?));
+	}
+	if let Ok(tuple) = object.cast::<PyTuple>() {
+		return Ok(JecsType::List(tuple.iter().map(|child| from_python(&child)).collect::<PyResult<_>>()?));
+	}
+	//Booleans would otherwise end up as 'True' and 'False':
+	if let Ok(boolean) = object.cast::<PyBool>() {
+		return Ok(JecsType::value(if boolean.is_true() { "true" } else { "false" }));
+	}
+	Ok(JecsType::value(object.str()?.to_string()))
+}
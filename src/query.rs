@@ -0,0 +1,135 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::path::JecsPath;
+use crate::redact::matches_pattern;
+use crate::types::JecsType;
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Debug)]
+pub enum JecsQuerySegment {
+	Key(String), //Pattern for keys of map entries, '*' matches any amount of characters and '?' exactly one
+	Index(usize), //Index of a list entry
+	AnyIndex, //"[*]", any entry of a list
+	AnyChild, //"*", any entry of a map or list
+	AnyDepth, //"**", any amount of entries, including none
+}
+
+//A path with wildcards, written like a path: "Components.*.Color", "Wires[*].From", "**.Color"
+//Patterns are case-sensitive and only match within one key, "**" is the only way to match multiple levels.
+#[derive(Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug)]
+pub struct JecsQuery {
+	segments: Vec<JecsQuerySegment>,
+}
+
+impl JecsQuery {
+	pub fn parse(text: &str) -> Self {
+		let mut query = Self::default();
+		if text.is_empty() {
+			return query;
+		}
+		for part in text.split('.') {
+			//Split off trailing index brackets, as long as they contain valid numbers or '*':
+			let mut key_end = part.len();
+			let mut indices = Vec::new();
+			while part[..key_end].ends_with(']') {
+				let open = match part[..key_end].rfind('[') {
+					None => break,
+					Some(open) => open,
+				};
+				match &part[open + 1..key_end - 1] {
+					"*" => indices.push(JecsQuerySegment::AnyIndex),
+					index => match index.parse::<usize>() {
+						Ok(index) => indices.push(JecsQuerySegment::Index(index)),
+						Err(_) => break,
+					},
+				}
+				key_end = open;
+			}
+			if key_end != 0 || indices.is_empty() {
+				query.push(match &part[..key_end] {
+					"*" => JecsQuerySegment::AnyChild,
+					"**" => JecsQuerySegment::AnyDepth,
+					key => JecsQuerySegment::Key(key.to_string()),
+				});
+			}
+			for index in indices.into_iter().rev() {
+				query.push(index);
+			}
+		}
+		query
+	}
+	
+	pub fn segments(&self) -> &[JecsQuerySegment] {
+		&self.segments
+	}
+	
+	fn push(&mut self, segment: JecsQuerySegment) {
+		//Multiple "**" in a row match the same entries as one, but would report them multiple times.
+		if segment == JecsQuerySegment::AnyDepth && self.segments.last() == Some(&JecsQuerySegment::AnyDepth) {
+			return;
+		}
+		self.segments.push(segment);
+	}
+}
+
+impl From<&str> for JecsQuery {
+	fn from(text: &str) -> Self {
+		Self::parse(text)
+	}
+}
+
+impl JecsType {
+	//All entries matching the query, with their path. Map entries are visited in alphabetical order, thus the result is stable.
+	pub fn query(&self, query: impl Into<JecsQuery>) -> Vec<(JecsPath, &JecsType)> {
+		let query = query.into();
+		let mut results = Vec::new();
+		query_inner(self, query.segments(), &mut JecsPath::root(), &mut results);
+		return results;
+		
+		fn query_inner<'a>(entry: &'a JecsType, segments: &[JecsQuerySegment], path: &mut JecsPath, results: &mut Vec<(JecsPath, &'a JecsType)>) {
+			let (segment, rest) = match segments.split_first() {
+				None => {
+					results.push((path.clone(), entry));
+					return;
+				}
+				Some(split) => split,
+			};
+			if *segment == JecsQuerySegment::AnyDepth {
+				query_inner(entry, rest, path, results);
+			}
+			match entry {
+				JecsType::Any() | JecsType::Null() | JecsType::Value(_) => {}
+				JecsType::Map(map) => {
+					let mut children: Vec<_> = map.iter().filter(|(key, _)| match segment {
+						JecsQuerySegment::Key(pattern) => matches_pattern(pattern, key),
+						JecsQuerySegment::AnyChild | JecsQuerySegment::AnyDepth => true,
+						JecsQuerySegment::Index(_) | JecsQuerySegment::AnyIndex => false,
+					}).collect();
+					children.sort_by_key(|(key, _)| *key);
+					for (key, child) in children {
+						path.push_key(key);
+						//"**" stays in front, to also match deeper entries.
+						query_inner(child, if *segment == JecsQuerySegment::AnyDepth { segments } else { rest }, path, results);
+						path.pop();
+					}
+				}
+				JecsType::List(list) => {
+					for (index, child) in list.iter().enumerate() {
+						let matches = match segment {
+							JecsQuerySegment::Key(pattern) => matches_pattern(pattern, &index.to_string()),
+							JecsQuerySegment::Index(wanted) => index == *wanted,
+							JecsQuerySegment::AnyIndex | JecsQuerySegment::AnyChild | JecsQuerySegment::AnyDepth => true,
+						};
+						if matches {
+							path.push_index(index);
+							query_inner(child, if *segment == JecsQuerySegment::AnyDepth { segments } else { rest }, path, results);
+							path.pop();
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,91 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::path::{JecsPath, JecsPathSegment};
+use crate::types::JecsType;
+
+//Replaces the values of sensitive entries, so that trees can be printed or exported safely.
+//An entry is sensitive if any key on its path matches a pattern, thus whole sections like "Secrets:" can be hidden.
+//Patterns are matched case-insensitive, '*' matches any amount of characters and '?' exactly one.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct JecsRedaction {
+	patterns: Vec<String>,
+}
+
+impl Default for JecsRedaction {
+	fn default() -> Self {
+		Self::new()
+			.with_pattern("*password*")
+			.with_pattern("*token*")
+			.with_pattern("*secret*")
+	}
+}
+
+impl JecsRedaction {
+	pub const REPLACEMENT: &'static str = "***";
+	
+	//Creates a redaction without any patterns, use default() to get the common ones.
+	pub fn new() -> Self {
+		Self {
+			patterns: Vec::new(),
+		}
+	}
+	
+	pub fn with_pattern(mut self, pattern: &str) -> Self {
+		self.patterns.push(pattern.to_lowercase());
+		self
+	}
+	
+	pub fn is_sensitive_key(&self, key: &str) -> bool {
+		let key = key.to_lowercase();
+		self.patterns.iter().any(|pattern| matches_pattern(pattern, &key))
+	}
+	
+	pub fn is_sensitive(&self, path: &JecsPath) -> bool {
+		path.segments().iter().any(|segment| match segment {
+			JecsPathSegment::Key(key) => self.is_sensitive_key(key),
+			JecsPathSegment::Index(_) => false,
+		})
+	}
+	
+	pub fn redact(&self, tree: &mut JecsType) {
+		tree.map_values(|path, value| {
+			if self.is_sensitive(path) {
+				Self::REPLACEMENT.to_string()
+			} else {
+				value.to_string()
+			}
+		});
+	}
+	
+	pub fn redacted(&self, tree: &JecsType) -> JecsType {
+		let mut copy = tree.clone();
+		self.redact(&mut copy);
+		copy
+	}
+}
+
+pub(crate) fn matches_pattern(pattern: &str, text: &str) -> bool {
+	let pattern: Vec<char> = pattern.chars().collect();
+	let text: Vec<char> = text.chars().collect();
+	//Greedy wildcard matching, which backtracks to the last '*' on mismatch:
+	let (mut pattern_index, mut text_index) = (0, 0);
+	let mut last_star: Option<(usize, usize)> = None;
+	while text_index < text.len() {
+		if pattern_index < pattern.len() && (pattern[pattern_index] == '?' || pattern[pattern_index] == text[text_index]) {
+			pattern_index += 1;
+			text_index += 1;
+		} else if pattern_index < pattern.len() && pattern[pattern_index] == '*' {
+			last_star = Some((pattern_index, text_index));
+			pattern_index += 1;
+		} else if let Some((star_index, star_text_index)) = last_star {
+			pattern_index = star_index + 1;
+			text_index = star_text_index + 1;
+			last_star = Some((star_index, star_text_index + 1));
+		} else {
+			return false;
+		}
+	}
+	pattern[pattern_index..].iter().all(|c| *c == '*')
+}
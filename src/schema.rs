@@ -0,0 +1,598 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use crate::codec::JecsCodecRegistry;
+use crate::decode::FromJecs;
+use crate::document::JecsDocument;
+use crate::errors::{JecsDecodeError, JecsErrorCode, JecsWarning, JecsWriteError, JecsWrongEntryTypeError};
+use crate::path::JecsPath;
+use crate::types::{closest_key, malformed, missing_key, AnyAs, JecsMap, JecsType};
+use crate::writer::{write_error, write_jecs_string_with_options, KeyOrder, WriterOptions};
+
+#[derive(Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug)]
+pub enum JecsSchemaType {
+	#[default]
+	Any, //Everything is accepted, also entries without value
+	String,
+	Bool,
+	Integer, //Signed 64 bit
+	Unsigned, //Unsigned 64 bit
+	Float,
+	Color,
+	Map, //Entries without value count as empty map
+	List, //Entries without value count as empty list
+	Custom(String), //Value checked by the codec of that name, see JecsCodecRegistry
+}
+
+impl JecsSchemaType {
+	//Name used in schema files.
+	pub fn name(&self) -> &str {
+		match self {
+			JecsSchemaType::Any => "any",
+			JecsSchemaType::String => "string",
+			JecsSchemaType::Bool => "bool",
+			JecsSchemaType::Integer => "integer",
+			JecsSchemaType::Unsigned => "unsigned",
+			JecsSchemaType::Float => "float",
+			JecsSchemaType::Color => "color",
+			JecsSchemaType::Map => "map",
+			JecsSchemaType::List => "list",
+			JecsSchemaType::Custom(name) => name,
+		}
+	}
+}
+
+impl FromStr for JecsSchemaType {
+	type Err = JecsDecodeError;
+	
+	//Unknown names are codecs, they are only looked up when validating.
+	fn from_str(text: &str) -> Result<Self, JecsDecodeError> {
+		Ok(match text {
+			"any" => JecsSchemaType::Any,
+			"string" => JecsSchemaType::String,
+			"bool" => JecsSchemaType::Bool,
+			"integer" => JecsSchemaType::Integer,
+			"unsigned" => JecsSchemaType::Unsigned,
+			"float" => JecsSchemaType::Float,
+			"color" => JecsSchemaType::Color,
+			"map" => JecsSchemaType::Map,
+			"list" => JecsSchemaType::List,
+			_ if !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')) => JecsSchemaType::Custom(text.to_string()),
+			_ => return Err(malformed("schema type", text)),
+		})
+	}
+}
+
+impl Display for JecsSchemaType {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+//Describes which entries a tree has to contain and of which type they are. Keys not described by the schema are allowed.
+//Schemas can be built in code, or read from a JECS file with from_jecs(), in which each entry is either only its type or a map:
+// Type: map
+// Keys:
+//     Name: string
+//     Port:
+//         Type: unsigned
+//         Required: true
+//         Description: Port the server listens on
+//     Motd:
+//         Type: string
+//         Default: Welcome!
+//     Plugins:
+//         Type: list
+//         Entries: string
+#[derive(Clone, PartialEq, Default)]
+#[derive(Debug)]
+pub struct JecsSchema {
+	pub entry_type: JecsSchemaType,
+	pub required: bool, //Only for map entries, the key has to be present
+	pub keys: BTreeMap<String, JecsSchema>, //Known entries of maps
+	pub entries: Option<Box<JecsSchema>>, //Schema of all list entries, or of the map entries without schema in 'keys'
+	pub default: Option<JecsType>, //Inserted for missing map entries by apply_defaults()
+	pub description: Option<String>, //Documentation for humans, validation ignores it
+}
+
+impl JecsSchema {
+	pub fn new(entry_type: JecsSchemaType) -> Self {
+		Self {
+			entry_type,
+			..Self::default()
+		}
+	}
+	
+	pub fn required(mut self) -> Self {
+		self.required = true;
+		self
+	}
+	
+	pub fn with_key(mut self, key: &str, schema: JecsSchema) -> Self {
+		self.keys.insert(key.to_string(), schema);
+		self
+	}
+	
+	pub fn with_entries(mut self, schema: JecsSchema) -> Self {
+		self.entries = Some(Box::new(schema));
+		self
+	}
+	
+	pub fn with_default(mut self, default: JecsType) -> Self {
+		self.default = Some(default);
+		self
+	}
+	
+	pub fn with_description(mut self, description: &str) -> Self {
+		self.description = Some(description.to_string());
+		self
+	}
+	
+	//Inserts the default of every missing map entry which has one. Missing maps are added too, if any of their entries has a default.
+	//Existing entries are never changed, also not if they are invalid.
+	pub fn apply_defaults(&self, tree: &mut JecsType) {
+		if tree.is_any() && !self.keys.is_empty() {
+			*tree = JecsType::new_map();
+			self.apply_defaults(tree);
+			if tree.get_map().is_some_and(|map| map.is_empty()) {
+				*tree = JecsType::Any();
+			}
+			return;
+		}
+		match tree {
+			JecsType::Map(map) => {
+				for (key, child_schema) in &self.keys {
+					if map.contains_key(key) {
+						continue;
+					}
+					let mut child = child_schema.default.clone().unwrap_or(JecsType::Any());
+					child_schema.apply_defaults(&mut child);
+					if !child.is_any() || child_schema.default.is_some() {
+						map.insert(key.clone(), child);
+					}
+				}
+				for (key, child) in map.iter_mut() {
+					let child_schema = match self.keys.get(key) {
+						Some(child_schema) => child_schema,
+						None => match &self.entries {
+							Some(child_schema) => child_schema,
+							None => continue,
+						},
+					};
+					child_schema.apply_defaults(child);
+				}
+			}
+			JecsType::List(list) => {
+				if let Some(child_schema) = &self.entries {
+					for child in list {
+						child_schema.apply_defaults(child);
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+	
+	//Returns every violation instead of stopping at the first one, each error has the path of the offending entry.
+	//Missing keys are reported at the path of their map. Custom types are checked with the default codecs.
+	pub fn validate(&self, tree: &JecsType) -> Vec<JecsDecodeError> {
+		self.validate_with(tree, &JecsCodecRegistry::default())
+	}
+	
+	//Same as validate(), with the codecs of the registry for custom types.
+	pub fn validate_with(&self, tree: &JecsType, codecs: &JecsCodecRegistry) -> Vec<JecsDecodeError> {
+		let mut errors = Vec::new();
+		validate_inner(self, tree, codecs, &mut JecsPath::root(), &mut errors);
+		return errors;
+		
+		fn validate_inner(schema: &JecsSchema, entry: &JecsType, codecs: &JecsCodecRegistry, path: &mut JecsPath, errors: &mut Vec<JecsDecodeError>) {
+			if let Err(error) = check_type(&schema.entry_type, entry, codecs) {
+				errors.push(error.at(&*path));
+				return;
+			}
+			match entry {
+				JecsType::Map(map) => {
+					for (key, child_schema) in &schema.keys {
+						if child_schema.required && !map.contains_key(key) {
+							errors.push(JecsDecodeError::from(missing_key(key, Some(map))).at(&*path));
+						}
+					}
+					//Sorted, so that the errors have a stable order:
+					let mut children: Vec<_> = map.iter().collect();
+					children.sort_by_key(|(key, _)| *key);
+					for (key, child) in children {
+						let child_schema = match schema.keys.get(key) {
+							Some(child_schema) => child_schema,
+							None => match &schema.entries {
+								Some(child_schema) => child_schema,
+								None => continue,
+							},
+						};
+						path.push_key(key);
+						validate_inner(child_schema, child, codecs, path, errors);
+						path.pop();
+					}
+				}
+				JecsType::List(list) => {
+					if let Some(child_schema) = &schema.entries {
+						for (index, child) in list.iter().enumerate() {
+							path.push_index(index);
+							validate_inner(child_schema, child, codecs, path, errors);
+							path.pop();
+						}
+					}
+				}
+				JecsType::Any() => {
+					for (key, child_schema) in &schema.keys {
+						if child_schema.required {
+							errors.push(JecsDecodeError::from(missing_key(key, None)).at(&*path));
+						}
+					}
+				}
+				JecsType::Null() | JecsType::Value(_) => {}
+			}
+		}
+	}
+	
+	//Keys of the tree which the schema does not describe, likely typos or leftovers. Validation accepts them, thus they are reported separately.
+	//Only maps whose schema describes keys, but has no schema for other entries, are checked. Each key comes with the closest described key, if one is similar.
+	pub fn unknown_keys(&self, tree: &JecsType) -> Vec<(JecsPath, Option<String>)> {
+		let mut unknown = Vec::new();
+		unknown_inner(self, tree, &mut JecsPath::root(), &mut unknown);
+		return unknown;
+		
+		fn unknown_inner(schema: &JecsSchema, entry: &JecsType, path: &mut JecsPath, unknown: &mut Vec<(JecsPath, Option<String>)>) {
+			match entry {
+				JecsType::Map(map) => {
+					let mut children: Vec<_> = map.iter().collect();
+					children.sort_by_key(|(key, _)| *key);
+					for (key, child) in children {
+						path.push_key(key);
+						match (schema.keys.get(key), schema.entries.as_deref()) {
+							(Some(child_schema), _) | (None, Some(child_schema)) => unknown_inner(child_schema, child, path, unknown),
+							(None, None) if !schema.keys.is_empty() => unknown.push((path.clone(), closest_key(key, schema.keys.keys()))),
+							(None, None) => {}
+						}
+						path.pop();
+					}
+				}
+				JecsType::List(list) => {
+					if let Some(child_schema) = &schema.entries {
+						for (index, child) in list.iter().enumerate() {
+							path.push_index(index);
+							unknown_inner(child_schema, child, path, unknown);
+							path.pop();
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+	}
+	
+	//Same as unknown_keys(), as warnings with the line and column of each key, in the order of the document.
+	pub fn unknown_key_warnings(&self, document: &JecsDocument) -> Vec<JecsWarning> {
+		let mut warnings: Vec<JecsWarning> = self.unknown_keys(&JecsType::Map(document.to_jecs_map())).into_iter().map(|(path, suggestion)| {
+			let span = &document.get_entry(&path).unwrap().span; //The paths are taken from the document itself
+			let mut description = format!("Key '{}' is not described by the schema", path);
+			if let Some(suggestion) = suggestion {
+				description.push_str(&format!(", did you mean '{}'?", suggestion));
+			}
+			JecsWarning {
+				row: span.row,
+				column: span.key_columns.start,
+				code: JecsErrorCode::UnknownKey,
+				description,
+			}
+		}).collect();
+		warnings.sort_by_key(|warning| (warning.row, warning.column));
+		warnings
+	}
+}
+
+impl FromJecs for JecsSchema {
+	fn from_jecs(entry: &JecsType) -> Result<Self, JecsDecodeError> {
+		let map = match entry {
+			JecsType::Value(value) => return Ok(JecsSchema::new(value.parse()?)),
+			JecsType::Map(map) => map,
+			_ => return Err(JecsWrongEntryTypeError {
+				expected_type: "schema VALUE or MAP".to_string(),
+				encountered_type: entry.name().to_string(),
+				path: None,
+			}.into()),
+		};
+		let field = |key: &str| map.get(key).filter(|child| !child.is_any());
+		let mut schema = JecsSchema::default();
+		if let Some(keys) = field("Keys") {
+			let keys = keys.expect_map().map_err(|error| error.at("Keys"))?;
+			for (key, child) in keys {
+				let child_schema = JecsSchema::from_jecs(child).map_err(|error| error.at(JecsPath::root().join_key("Keys").join_key(key)))?;
+				schema.keys.insert(key.clone(), child_schema);
+			}
+			schema.entry_type = JecsSchemaType::Map;
+		}
+		if let Some(entries) = field("Entries") {
+			schema.entries = Some(Box::new(JecsSchema::from_jecs(entries).map_err(|error| error.at("Entries"))?));
+		}
+		if let Some(entry_type) = field("Type") {
+			schema.entry_type = entry_type.expect_string().and_then(str::parse).map_err(|error| error.at("Type"))?;
+		}
+		if let Some(required) = field("Required") {
+			schema.required = required.expect_bool().map_err(|error| error.at("Required"))?;
+		}
+		if let Some(description) = field("Description") {
+			schema.description = Some(description.expect_string().map_err(|error| error.at("Description"))?.to_string());
+		}
+		if let Some(default) = field("Default") {
+			//Custom types need the codecs of the application, thus their defaults are not checked here:
+			if !matches!(schema.entry_type, JecsSchemaType::Custom(_)) {
+				check_type(&schema.entry_type, default, &JecsCodecRegistry::empty()).map_err(|error| error.at("Default"))?;
+			}
+			schema.default = Some(default.clone());
+		}
+		Ok(schema)
+	}
+}
+
+fn check_type(entry_type: &JecsSchemaType, entry: &JecsType, codecs: &JecsCodecRegistry) -> Result<(), JecsDecodeError> {
+	match entry_type {
+		JecsSchemaType::Any => Ok(()),
+		//Entries without value are empty maps and lists:
+		JecsSchemaType::Map | JecsSchemaType::List if entry.is_any() => Ok(()),
+		JecsSchemaType::String => entry.expect_string().map(drop),
+		JecsSchemaType::Bool => entry.expect_bool().map(drop),
+		JecsSchemaType::Integer => i64::from_jecs(entry).map(drop),
+		JecsSchemaType::Unsigned => u64::from_jecs(entry).map(drop),
+		JecsSchemaType::Float => entry.expect_double().map(drop),
+		JecsSchemaType::Color => entry.expect_color().map(drop),
+		//Empty maps and lists are written as key without value:
+		JecsSchemaType::Map => entry.resolve_any(AnyAs::EmptyMap)?.expect_map().map(drop),
+		JecsSchemaType::List => entry.resolve_any(AnyAs::EmptyList)?.expect_list().map(drop),
+		JecsSchemaType::Custom(name) => entry.expect_custom(codecs, name).map(drop),
+	}
+}
+
+impl JecsSchema {
+	//An example file with every key of the schema, ordered alphabetically. Keys get their default, or no value if they have none.
+	//Descriptions, types and required flags become the comments above the keys, the description of the root is written at the top of the file.
+	pub fn render_template(&self) -> Result<String, JecsWriteError> {
+		let root = match self.template_entry() {
+			JecsType::Map(map) => map,
+			_ => JecsMap::default(),
+		};
+		let options = WriterOptions {
+			key_order: KeyOrder::Alphabetical,
+			..WriterOptions::default()
+		};
+		let mut document = JecsDocument::parse(&write_jecs_string_with_options(&root, &options)?)
+			.map_err(|error| write_error(&JecsPath::root(), &error.description))?;
+		add_comments(self, &mut document, &mut JecsPath::root())?;
+		
+		let mut text = String::new();
+		if let Some(description) = &self.description {
+			for line in description.lines() {
+				text.push('#');
+				if !line.is_empty() {
+					text.push(' ');
+					text.push_str(line);
+				}
+				text.push('\n');
+			}
+			text.push('\n');
+		}
+		text.push_str(document.as_str());
+		return Ok(text);
+		
+		fn add_comments(schema: &JecsSchema, document: &mut JecsDocument, path: &mut JecsPath) -> Result<(), JecsWriteError> {
+			for (key, child_schema) in &schema.keys {
+				path.push_key(key);
+				let mut comment = child_schema.description.clone().unwrap_or_default();
+				if child_schema.entry_type != JecsSchemaType::Any || child_schema.required {
+					if !comment.is_empty() {
+						comment.push('\n');
+					}
+					comment.push_str("Type: ");
+					comment.push_str(child_schema.entry_type.name());
+					if child_schema.required {
+						comment.push_str(", required");
+					}
+				}
+				if !comment.is_empty() {
+					document.set_comment(&*path, &comment)?;
+				}
+				add_comments(child_schema, document, path)?;
+				path.pop();
+			}
+			Ok(())
+		}
+	}
+	
+	//The default with all keys of the schema which it does not contain yet.
+	fn template_entry(&self) -> JecsType {
+		let mut entry = self.default.clone().unwrap_or(JecsType::Any());
+		if entry.is_any() && !self.keys.is_empty() {
+			entry = JecsType::new_map();
+		}
+		if let JecsType::Map(map) = &mut entry {
+			for (key, child_schema) in &self.keys {
+				if !map.contains_key(key) {
+					map.insert(key.clone(), child_schema.template_entry());
+				}
+			}
+		}
+		entry
+	}
+	
+	//JSON Schema (draft 2020-12) for trees converted to JSON like the serde support does it: Values become strings, entries without value null.
+	//Numbers and booleans are accepted as well, as converting JSON to JECS reads them as their text.
+	//Custom types become strings with their name as 'format'.
+	pub fn to_json_schema(&self) -> String {
+		let mut object = vec![("$schema".to_string(), Json::String("https://json-schema.org/draft/2020-12/schema".to_string()))];
+		object.extend(self.json_schema_object());
+		let mut output = String::new();
+		write_json(&mut output, &Json::Object(object), 0);
+		output.push('\n');
+		output
+	}
+	
+	fn json_schema_object(&self) -> Vec<(String, Json)> {
+		let mut object = Vec::new();
+		let mut add = |key: &str, value: Json| object.push((key.to_string(), value));
+		let types = |names: &[&str]| match names {
+			[name] => Json::String(name.to_string()),
+			_ => Json::Array(names.iter().map(|name| Json::String(name.to_string())).collect()),
+		};
+		let pattern = |pattern: &str| Json::String(pattern.to_string());
+		match &self.entry_type {
+			JecsSchemaType::Any => {}
+			JecsSchemaType::String => add("type", types(&["string"])),
+			JecsSchemaType::Bool => {
+				//Booleans are read ignoring the case:
+				let words: Vec<String> = ["true", "on", "yes", "y", "false", "off", "no", "n"].iter().map(|word| {
+					word.chars().map(|c| format!("[{}{}]", c.to_ascii_uppercase(), c)).collect()
+				}).collect();
+				add("type", types(&["boolean", "string"]));
+				add("pattern", Json::String(format!("^({})$", words.join("|"))));
+			}
+			JecsSchemaType::Integer => {
+				add("type", types(&["integer", "string"]));
+				add("pattern", pattern("^[+-]?[0-9]+$"));
+			}
+			JecsSchemaType::Unsigned => {
+				add("type", types(&["integer", "string"]));
+				add("minimum", Json::Integer(0));
+				add("pattern", pattern("^[+]?[0-9]+$"));
+			}
+			JecsSchemaType::Float => {
+				add("type", types(&["number", "string"]));
+				add("pattern", pattern("^[+-]?([0-9]+[.]?[0-9]*|[.][0-9]+)([eE][+-]?[0-9]+)?$"));
+			}
+			JecsSchemaType::Color => {
+				add("type", types(&["string"]));
+				add("pattern", pattern("^[0-9A-F]{6}$"));
+			}
+			JecsSchemaType::Custom(name) => {
+				add("type", types(&["string"]));
+				add("format", Json::String(name.clone()));
+			}
+			JecsSchemaType::Map => add("type", types(&["object", "null"])),
+			JecsSchemaType::List => add("type", types(&["array", "null"])),
+		}
+		if let Some(description) = &self.description {
+			add("description", Json::String(description.clone()));
+		}
+		if let Some(default) = &self.default {
+			add("default", Json::from_entry(default));
+		}
+		if !self.keys.is_empty() {
+			add("properties", Json::Object(self.keys.iter().map(|(key, child)| (key.clone(), Json::Object(child.json_schema_object()))).collect()));
+			let required: Vec<Json> = self.keys.iter().filter(|(_, child)| child.required).map(|(key, _)| Json::String(key.clone())).collect();
+			if !required.is_empty() {
+				add("required", Json::Array(required));
+			}
+		}
+		if let Some(entries) = &self.entries {
+			//Without a type the entries apply to maps and lists:
+			if matches!(self.entry_type, JecsSchemaType::Map | JecsSchemaType::Any) {
+				add("additionalProperties", Json::Object(entries.json_schema_object()));
+			}
+			if matches!(self.entry_type, JecsSchemaType::List | JecsSchemaType::Any) {
+				add("items", Json::Object(entries.json_schema_object()));
+			}
+		}
+		object
+	}
+}
+
+enum Json {
+	Null,
+	String(String),
+	Integer(u64),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+}
+
+impl Json {
+	fn from_entry(entry: &JecsType) -> Self {
+		match entry {
+			JecsType::Any() | JecsType::Null() => Json::Null,
+			JecsType::Value(value) => Json::String(value.to_string()),
+			JecsType::Map(map) => {
+				let mut children: Vec<_> = map.iter().map(|(key, child)| (key.clone(), Json::from_entry(child))).collect();
+				children.sort_by(|(a, _), (b, _)| a.cmp(b));
+				Json::Object(children)
+			}
+			JecsType::List(list) => Json::Array(list.iter().map(Json::from_entry).collect()),
+		}
+	}
+}
+
+//Pretty printed with two spaces, arrays without objects or arrays in them stay on one line.
+fn write_json(output: &mut String, value: &Json, depth: usize) {
+	let indentation = |depth: usize| "  ".repeat(depth);
+	match value {
+		Json::Null => output.push_str("null"),
+		Json::Integer(number) => output.push_str(&number.to_string()),
+		Json::String(text) => {
+			output.push('"');
+			for c in text.chars() {
+				match c {
+					'"' => output.push_str("\\\""),
+					'\\' => output.push_str("\\\\"),
+					'\n' => output.push_str("\\n"),
+					'\r' => output.push_str("\\r"),
+					'\t' => output.push_str("\\t"),
+					_ if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+					_ => output.push(c),
+				}
+			}
+			output.push('"');
+		}
+		Json::Array(entries) if entries.iter().all(|entry| !matches!(entry, Json::Array(_) | Json::Object(_))) => {
+			output.push('[');
+			for (index, entry) in entries.iter().enumerate() {
+				if index != 0 {
+					output.push_str(", ");
+				}
+				write_json(output, entry, depth);
+			}
+			output.push(']');
+		}
+		Json::Array(entries) => {
+			output.push_str("[\n");
+			for (index, entry) in entries.iter().enumerate() {
+				if index != 0 {
+					output.push_str(",\n");
+				}
+				output.push_str(&indentation(depth + 1));
+				write_json(output, entry, depth + 1);
+			}
+			output.push('\n');
+			output.push_str(&indentation(depth));
+			output.push(']');
+		}
+		Json::Object(members) if members.is_empty() => output.push_str("{}"),
+		Json::Object(members) => {
+			output.push_str("{\n");
+			for (index, (key, member)) in members.iter().enumerate() {
+				if index != 0 {
+					output.push_str(",\n");
+				}
+				output.push_str(&indentation(depth + 1));
+				write_json(output, &Json::String(key.clone()), depth + 1);
+				output.push_str(": ");
+				write_json(output, member, depth + 1);
+			}
+			output.push('\n');
+			output.push_str(&indentation(depth));
+			output.push('}');
+		}
+	}
+}
@@ -0,0 +1,1160 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Formatter;
+use core::str::{from_utf8, Utf8Error};
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{DeserializeOwned, DeserializeSeed, EnumAccess, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{JecsCorruptedDataError, JecsErrorCode, JecsSerdeError};
+use crate::parser::{LineMeta, LineReader, ParserOptions};
+use crate::path::JecsPath;
+use crate::types::{JecsMap, JecsString, JecsType};
+use crate::writer;
+
+pub fn from_jecs_string<T: DeserializeOwned>(text: &str) -> Result<T, Box<dyn Error>> {
+	Ok(T::deserialize(Deserializer::from_jecs_string(text))?)
+}
+
+pub fn to_jecs_string<T: Serialize + ?Sized>(value: &T) -> Result<String, JecsSerdeError> {
+	let mut serializer = Serializer::new();
+	value.serialize(&mut serializer)?;
+	Ok(serializer.into_string())
+}
+
+fn serde_error(description: impl Into<String>) -> JecsSerdeError {
+	JecsSerdeError {
+		description: description.into(),
+	}
+}
+
+// ###### JecsType <-> Serde data model ######
+
+//Values are serialized as strings, Any and Null entries as unit (null in JSON).
+impl Serialize for JecsType {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self {
+			JecsType::Any() | JecsType::Null() => serializer.serialize_unit(),
+			JecsType::Value(value) => serializer.serialize_str(value),
+			JecsType::Map(map) => serializer.collect_map(map),
+			JecsType::List(list) => serializer.collect_seq(list),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for JecsType {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_any(JecsTypeVisitor)
+	}
+}
+
+struct JecsTypeVisitor;
+
+impl<'de> Visitor<'de> for JecsTypeVisitor {
+	type Value = JecsType;
+	
+	fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+		formatter.write_str("any JECS compatible data")
+	}
+	
+	fn visit_bool<E>(self, value: bool) -> Result<JecsType, E> {
+		Ok(JecsType::value(value.to_string()))
+	}
+	
+	fn visit_i64<E>(self, value: i64) -> Result<JecsType, E> {
+		Ok(JecsType::value(value.to_string()))
+	}
+	
+	fn visit_u64<E>(self, value: u64) -> Result<JecsType, E> {
+		Ok(JecsType::value(value.to_string()))
+	}
+	
+	fn visit_f64<E>(self, value: f64) -> Result<JecsType, E> {
+		Ok(JecsType::value(value.to_string()))
+	}
+	
+	fn visit_str<E>(self, value: &str) -> Result<JecsType, E> {
+		Ok(JecsType::value(value))
+	}
+	
+	fn visit_string<E>(self, value: String) -> Result<JecsType, E> {
+		Ok(JecsType::value(value))
+	}
+	
+	fn visit_unit<E>(self) -> Result<JecsType, E> {
+		Ok(JecsType::Any())
+	}
+	
+	fn visit_none<E>(self) -> Result<JecsType, E> {
+		Ok(JecsType::Any())
+	}
+	
+	fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<JecsType, D::Error> {
+		JecsType::deserialize(deserializer)
+	}
+	
+	fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<JecsType, A::Error> {
+		let mut list = Vec::with_capacity(access.size_hint().unwrap_or(0));
+		while let Some(entry) = access.next_element()? {
+			list.push(entry);
+		}
+		Ok(JecsType::List(list))
+	}
+	
+	fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<JecsType, A::Error> {
+		let mut map = JecsMap::with_capacity_and_hasher(access.size_hint().unwrap_or(0), Default::default());
+		while let Some((key, entry)) = access.next_entry::<String, JecsType>()? {
+			map.insert(key, entry);
+		}
+		Ok(JecsType::Map(map))
+	}
+}
+
+// ###### Deserializer ######
+
+//Reads JECS text line by line while deserializing, without building the tree. The text is checked like the parser does it
+// with default options, but problems are only found once their line is reached.
+//Keys used twice within the same map are both passed on, unlike the parser which keeps the later entry.
+pub struct Deserializer<'de> {
+	reader: LineReader<'de>,
+}
+
+impl<'de> Deserializer<'de> {
+	pub fn from_jecs_string(text: &'de str) -> Self {
+		Self {
+			reader: LineReader::new(text, ParserOptions::default()),
+		}
+	}
+	
+	pub fn from_jecs_bytes(bytes: &'de [u8]) -> Result<Self, Utf8Error> {
+		let text = from_utf8(bytes)?;
+		//Remove BOM on encounter:
+		Ok(Self::from_jecs_string(text.strip_prefix('\u{feff}').unwrap_or(text)))
+	}
+	
+	//The root is a map with unindented children.
+	fn root(&mut self) -> Entry<'_, 'de> {
+		Entry::Collection(Children {
+			reader: &mut self.reader,
+			parent_indentation: None,
+			child_indentation: 0,
+			is_list: false,
+			has_started: false,
+			pending: None,
+		})
+	}
+}
+
+macro_rules! forward_to_root {
+	($($method:ident($($argument:ident: $type:ty),*);)*) => {
+		$(
+			fn $method<V: Visitor<'de>>(mut self, $($argument: $type,)* visitor: V) -> Result<V::Value, JecsSerdeError> {
+				serde::Deserializer::$method(self.root(), $($argument,)* visitor)
+			}
+		)*
+	};
+}
+
+impl<'de> serde::Deserializer<'de> for Deserializer<'de> {
+	type Error = JecsSerdeError;
+	
+	forward_to_root! {
+		deserialize_any();
+		deserialize_bool();
+		deserialize_i8();
+		deserialize_i16();
+		deserialize_i32();
+		deserialize_i64();
+		deserialize_i128();
+		deserialize_u8();
+		deserialize_u16();
+		deserialize_u32();
+		deserialize_u64();
+		deserialize_u128();
+		deserialize_f32();
+		deserialize_f64();
+		deserialize_char();
+		deserialize_str();
+		deserialize_string();
+		deserialize_bytes();
+		deserialize_byte_buf();
+		deserialize_option();
+		deserialize_unit();
+		deserialize_unit_struct(name: &'static str);
+		deserialize_newtype_struct(name: &'static str);
+		deserialize_seq();
+		deserialize_tuple(length: usize);
+		deserialize_tuple_struct(name: &'static str, length: usize);
+		deserialize_map();
+		deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+		deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+		deserialize_identifier();
+		deserialize_ignored_any();
+	}
+}
+
+//An entry of which the line got read. Entries without children are turned into a JecsType, which then does the deserializing.
+enum Entry<'a, 'de> {
+	Leaf(JecsType),
+	Collection(Children<'a, 'de>),
+}
+
+impl<'a, 'de> Entry<'a, 'de> {
+	//Whether the entry has children is only known after peeking at the next line.
+	fn read(reader: &'a mut LineReader<'de>, line: LineMeta<'de>) -> Result<Self, JecsSerdeError> {
+		if line.is_null {
+			return Ok(Entry::Leaf(JecsType::Any()));
+		}
+		if let Some(value) = line.value {
+			return Ok(Entry::Leaf(JecsType::value(value)));
+		}
+		let (child_indentation, is_list) = match reader.peek()? {
+			Some(child) if child.indentation > line.indentation => (child.indentation, child.is_list()),
+			_ => return Ok(Entry::Leaf(JecsType::Any())),
+		};
+		Ok(Entry::Collection(Children {
+			reader,
+			parent_indentation: Some(line.indentation),
+			child_indentation,
+			is_list,
+			has_started: false,
+			pending: None,
+		}))
+	}
+	
+	fn name(&self) -> &str {
+		match self {
+			Entry::Leaf(leaf) => leaf.name(),
+			Entry::Collection(children) if children.is_list => "List",
+			Entry::Collection(_) => "Map",
+		}
+	}
+}
+
+macro_rules! forward_to_leaf {
+	($($method:ident($($argument:ident: $type:ty),*);)*) => {
+		$(
+			fn $method<V: Visitor<'de>>(self, $($argument: $type,)* visitor: V) -> Result<V::Value, JecsSerdeError> {
+				match self {
+					Entry::Leaf(leaf) => serde::Deserializer::$method(leaf, $($argument,)* visitor),
+					Entry::Collection(children) => children.visit(visitor),
+				}
+			}
+		)*
+	};
+}
+
+//Maps and lists are visited as such, no matter which type was asked for. Visitors which do not want them fail on their own.
+impl<'de> serde::Deserializer<'de> for Entry<'_, 'de> {
+	type Error = JecsSerdeError;
+	
+	forward_to_leaf! {
+		deserialize_any();
+		deserialize_bool();
+		deserialize_i8();
+		deserialize_i16();
+		deserialize_i32();
+		deserialize_i64();
+		deserialize_i128();
+		deserialize_u8();
+		deserialize_u16();
+		deserialize_u32();
+		deserialize_u64();
+		deserialize_u128();
+		deserialize_f32();
+		deserialize_f64();
+		deserialize_char();
+		deserialize_str();
+		deserialize_string();
+		deserialize_bytes();
+		deserialize_byte_buf();
+		deserialize_unit();
+		deserialize_unit_struct(name: &'static str);
+		deserialize_seq();
+		deserialize_tuple(length: usize);
+		deserialize_tuple_struct(name: &'static str, length: usize);
+		deserialize_map();
+		deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+		deserialize_identifier();
+	}
+	
+	fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			Entry::Leaf(leaf) => leaf.deserialize_option(visitor),
+			collection => visitor.visit_some(collection),
+		}
+	}
+	
+	fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		visitor.visit_newtype_struct(self)
+	}
+	
+	fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			Entry::Leaf(leaf) => leaf.deserialize_enum(name, variants, visitor),
+			//Variants with content are a map with a single entry, the key being the variant:
+			Entry::Collection(mut children) if !children.is_list => {
+				let value = visitor.visit_enum(&mut children)?;
+				if children.next_line()?.is_some() {
+					return Err(serde_error("Expected enum variant as Value or Map with a single entry, got Map with more entries"));
+				}
+				Ok(value)
+			}
+			list => Err(serde_error(format!("Expected enum variant as Value or Map with a single entry, got {}", list.name()))),
+		}
+	}
+	
+	fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		if let Entry::Collection(mut children) = self {
+			children.skip()?;
+		}
+		visitor.visit_unit()
+	}
+}
+
+impl<'de> VariantAccess<'de> for Entry<'_, 'de> {
+	type Error = JecsSerdeError;
+	
+	fn unit_variant(self) -> Result<(), JecsSerdeError> {
+		match self {
+			Entry::Leaf(JecsType::Any()) => Ok(()),
+			other => Err(serde_error(format!("Expected unit enum variant without content, got {}", other.name()))),
+		}
+	}
+	
+	fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, JecsSerdeError> {
+		seed.deserialize(self)
+	}
+	
+	fn tuple_variant<V: Visitor<'de>>(self, _length: usize, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		serde::Deserializer::deserialize_seq(self, visitor)
+	}
+	
+	fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, JecsSerdeError> {
+		serde::Deserializer::deserialize_map(self, visitor)
+	}
+}
+
+//The children of a map or list, their lines are read while they are visited. Only the lines of the children themselves are checked here,
+// lines below them are checked by the child entries.
+struct Children<'a, 'de> {
+	reader: &'a mut LineReader<'de>,
+	parent_indentation: Option<usize>, //None for the root
+	child_indentation: usize,
+	is_list: bool,
+	has_started: bool,
+	pending: Option<LineMeta<'de>>, //The line of the last map key, until its value is visited
+}
+
+impl<'de> Children<'_, 'de> {
+	//The line of the next child, or None if the next line belongs to some parent.
+	fn next_line(&mut self) -> Result<Option<LineMeta<'de>>, JecsSerdeError> {
+		let line = match self.reader.peek()? {
+			Some(line) if self.parent_indentation.is_none_or(|parent| line.indentation > parent) => line,
+			_ => return Ok(None),
+		};
+		let is_root = self.parent_indentation.is_none();
+		let (row, column) = (line.row, line.key_columns.start);
+		if is_root && !self.has_started && line.indentation != 0 {
+			return Err(corrupted_error(row, column, JecsErrorCode::IndentedRootEntry, format!("Root level entries need indentation level {}, but got {}", 0, line.indentation)));
+		}
+		if line.indentation > self.child_indentation {
+			//Deeper lines after an entry with children are read by that entry:
+			return Err(corrupted_error(row, column, JecsErrorCode::ChildOfValue, "Child entries can only be added to entries without value".to_string()));
+		}
+		if line.indentation < self.child_indentation {
+			return Err(corrupted_error(row, column, JecsErrorCode::WrongIndentation, format!("Wrongly indented JECS entry! Expected indentation {} but got {}", self.child_indentation, line.indentation)));
+		}
+		if is_root && line.is_list() {
+			return Err(corrupted_error(row, column, JecsErrorCode::RootListEntry, "Root level entries need a key, they may not be list entries".to_string()));
+		}
+		if line.is_list() != self.is_list {
+			return Err(corrupted_error(row, column, JecsErrorCode::MixedListAndMap, "Cannot mix list and dict collection entries within the same parent".to_string()));
+		}
+		self.has_started = true;
+		Ok(self.reader.next()?)
+	}
+	
+	//The visitor might stop early, the remaining children are still read to check them and to get to the next entry.
+	fn visit<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		let value = if self.is_list {
+			visitor.visit_seq(&mut self)?
+		} else {
+			visitor.visit_map(&mut self)?
+		};
+		self.skip()?;
+		Ok(value)
+	}
+	
+	fn skip(&mut self) -> Result<(), JecsSerdeError> {
+		if let Some(line) = self.pending.take() {
+			serde::Deserializer::deserialize_ignored_any(Entry::read(self.reader, line)?, IgnoredAny)?;
+		}
+		while let Some(line) = self.next_line()? {
+			serde::Deserializer::deserialize_ignored_any(Entry::read(self.reader, line)?, IgnoredAny)?;
+		}
+		Ok(())
+	}
+}
+
+impl<'de> MapAccess<'de> for Children<'_, 'de> {
+	type Error = JecsSerdeError;
+	
+	fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, JecsSerdeError> {
+		let mut line = match self.next_line()? {
+			None => return Ok(None),
+			Some(line) => line,
+		};
+		//Keys are deserialized as values, so that they can be parsed into numbers and such:
+		let key = seed.deserialize(JecsType::value(line.key.take().unwrap()))?;
+		self.pending = Some(line);
+		Ok(Some(key))
+	}
+	
+	fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, JecsSerdeError> {
+		let line = self.pending.take().ok_or_else(|| serde_error("Map value requested before its key"))?;
+		seed.deserialize(Entry::read(self.reader, line)?)
+	}
+}
+
+impl<'de> SeqAccess<'de> for Children<'_, 'de> {
+	type Error = JecsSerdeError;
+	
+	fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, JecsSerdeError> {
+		match self.next_line()? {
+			None => Ok(None),
+			Some(line) => seed.deserialize(Entry::read(self.reader, line)?).map(Some),
+		}
+	}
+}
+
+impl<'a, 'de> EnumAccess<'de> for &'a mut Children<'_, 'de> {
+	type Error = JecsSerdeError;
+	type Variant = Entry<'a, 'de>;
+	
+	fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Entry<'a, 'de>), JecsSerdeError> {
+		//Only the root can be an empty map:
+		let mut line = self.next_line()?.ok_or_else(|| serde_error("Expected enum variant as Value or Map with a single entry, got empty Map"))?;
+		let variant = seed.deserialize(JecsType::value(line.key.take().unwrap()))?;
+		Ok((variant, Entry::read(self.reader, line)?))
+	}
+}
+
+fn corrupted_error(row: usize, column: usize, code: JecsErrorCode, description: String) -> JecsSerdeError {
+	JecsCorruptedDataError {
+		row,
+		column,
+		code,
+		description,
+	}.into()
+}
+
+// ###### JecsType Deserializer ######
+
+impl<'de> IntoDeserializer<'de, JecsSerdeError> for JecsType {
+	type Deserializer = JecsType;
+	
+	fn into_deserializer(self) -> JecsType {
+		self
+	}
+}
+
+macro_rules! deserialize_parsed {
+	($($method:ident => $visit:ident: $type:ty;)*) => {
+		$(
+			fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+				let value = into_value_text(self, stringify!($type))?;
+				visitor.$visit(value.parse::<$type>().map_err(|_| serde_error(format!("Failed to parse {} data with value '{}'", stringify!($type), value)))?)
+			}
+		)*
+	};
+}
+
+#[cfg(feature = "compact_str")]
+fn into_string(value: JecsString) -> String {
+	value.into_string()
+}
+
+#[cfg(not(feature = "compact_str"))]
+fn into_string(value: JecsString) -> String {
+	value
+}
+
+fn into_value_text(entry: JecsType, expected: &str) -> Result<String, JecsSerdeError> {
+	match entry {
+		JecsType::Value(value) => Ok(into_string(value)),
+		other => Err(serde_error(format!("Expected {} JECS data type, got {}", expected, other.name()))),
+	}
+}
+
+//Deserializes a (sub-)tree, consuming it. Type hints are used to interpret the textual values.
+impl<'de> serde::Deserializer<'de> for JecsType {
+	type Error = JecsSerdeError;
+	
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			JecsType::Any() | JecsType::Null() => visitor.visit_unit(),
+			JecsType::Value(value) => visitor.visit_string(into_string(value)),
+			JecsType::Map(map) => visit_map(map, visitor),
+			JecsType::List(list) => visit_list(list, visitor),
+		}
+	}
+	
+	fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		let value = self.expect_bool().map_err(|e| serde_error(e.to_string().trim_end()))?;
+		visitor.visit_bool(value)
+	}
+	
+	deserialize_parsed! {
+		deserialize_i8 => visit_i8: i8;
+		deserialize_i16 => visit_i16: i16;
+		deserialize_i32 => visit_i32: i32;
+		deserialize_i64 => visit_i64: i64;
+		deserialize_i128 => visit_i128: i128;
+		deserialize_u8 => visit_u8: u8;
+		deserialize_u16 => visit_u16: u16;
+		deserialize_u32 => visit_u32: u32;
+		deserialize_u64 => visit_u64: u64;
+		deserialize_u128 => visit_u128: u128;
+		deserialize_f32 => visit_f32: f32;
+		deserialize_f64 => visit_f64: f64;
+		deserialize_char => visit_char: char;
+	}
+	
+	fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_string(visitor)
+	}
+	
+	fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			//Empty values written as key without value are read as Any entry:
+			JecsType::Any() => visitor.visit_str(""),
+			JecsType::Value(value) => visitor.visit_string(into_string(value)),
+			other => other.deserialize_any(visitor),
+		}
+	}
+	
+	fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_seq(visitor)
+	}
+	
+	fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_seq(visitor)
+	}
+	
+	fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			JecsType::Any() | JecsType::Null() => visitor.visit_none(),
+			other => visitor.visit_some(other),
+		}
+	}
+	
+	fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_any(visitor)
+	}
+	
+	fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_unit(visitor)
+	}
+	
+	fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		visitor.visit_newtype_struct(self)
+	}
+	
+	fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			//Any entries are empty lists or maps, whatever is needed:
+			JecsType::Any() => visit_list(Vec::new(), visitor),
+			JecsType::List(list) => visit_list(list, visitor),
+			other => other.deserialize_any(visitor),
+		}
+	}
+	
+	fn deserialize_tuple<V: Visitor<'de>>(self, _length: usize, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_seq(visitor)
+	}
+	
+	fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, _length: usize, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_seq(visitor)
+	}
+	
+	fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			JecsType::Any() => visit_map(JecsMap::default(), visitor),
+			JecsType::Map(map) => visit_map(map, visitor),
+			other => other.deserialize_any(visitor),
+		}
+	}
+	
+	fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_map(visitor)
+	}
+	
+	fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, JecsSerdeError> {
+		match self {
+			//Unit variants are written as plain value:
+			JecsType::Value(variant) => visitor.visit_enum(EnumDeserializer {
+				variant: into_string(variant),
+				content: None,
+			}),
+			//All other variants are a map with a single entry, the key being the variant:
+			JecsType::Map(map) if map.len() == 1 => {
+				let (variant, content) = map.into_iter().next().unwrap();
+				visitor.visit_enum(EnumDeserializer {
+					variant,
+					content: Some(content),
+				})
+			}
+			other => Err(serde_error(format!("Expected enum variant as Value or Map with a single entry, got {}", other.name()))),
+		}
+	}
+	
+	fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		self.deserialize_string(visitor)
+	}
+	
+	fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		visitor.visit_unit()
+	}
+}
+
+fn visit_map<'de, V: Visitor<'de>>(map: JecsMap, visitor: V) -> Result<V::Value, JecsSerdeError> {
+	//Keys are deserialized as values, so that they can be parsed into numbers and such:
+	let mut deserializer = MapDeserializer::new(map.into_iter().map(|(key, entry)| (JecsType::value(key), entry)));
+	let value = visitor.visit_map(&mut deserializer)?;
+	deserializer.end()?;
+	Ok(value)
+}
+
+fn visit_list<'de, V: Visitor<'de>>(list: Vec<JecsType>, visitor: V) -> Result<V::Value, JecsSerdeError> {
+	let mut deserializer = SeqDeserializer::new(list.into_iter());
+	let value = visitor.visit_seq(&mut deserializer)?;
+	deserializer.end()?;
+	Ok(value)
+}
+
+struct EnumDeserializer {
+	variant: String,
+	content: Option<JecsType>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+	type Error = JecsSerdeError;
+	type Variant = VariantDeserializer;
+	
+	fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, VariantDeserializer), JecsSerdeError> {
+		let variant = seed.deserialize(IntoDeserializer::<JecsSerdeError>::into_deserializer(self.variant))?;
+		Ok((variant, VariantDeserializer {
+			content: self.content,
+		}))
+	}
+}
+
+struct VariantDeserializer {
+	content: Option<JecsType>,
+}
+
+impl VariantDeserializer {
+	fn into_content(self) -> Result<JecsType, JecsSerdeError> {
+		self.content.ok_or_else(|| serde_error("Expected enum variant with content, got plain Value"))
+	}
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+	type Error = JecsSerdeError;
+	
+	fn unit_variant(self) -> Result<(), JecsSerdeError> {
+		match self.content {
+			None | Some(JecsType::Any()) => Ok(()),
+			Some(other) => Err(serde_error(format!("Expected unit enum variant without content, got {}", other.name()))),
+		}
+	}
+	
+	fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, JecsSerdeError> {
+		seed.deserialize(self.into_content()?)
+	}
+	
+	fn tuple_variant<V: Visitor<'de>>(self, _length: usize, visitor: V) -> Result<V::Value, JecsSerdeError> {
+		serde::Deserializer::deserialize_seq(self.into_content()?, visitor)
+	}
+	
+	fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, JecsSerdeError> {
+		serde::Deserializer::deserialize_map(self.into_content()?, visitor)
+	}
+}
+
+// ###### Serializer ######
+
+//Where the next written entry ends up. The root must be a map and has no line of its own.
+enum Slot {
+	Root,
+	Key(String),
+	ListEntry,
+}
+
+//Writes JECS text while serializing, without building a JecsType tree in between.
+pub struct Serializer {
+	output: String,
+	path: JecsPath,
+	depth: usize,
+	slot: Slot,
+}
+
+impl Default for Serializer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Serializer {
+	pub fn new() -> Self {
+		Self {
+			output: String::new(),
+			path: JecsPath::root(),
+			depth: 0,
+			slot: Slot::Root,
+		}
+	}
+	
+	pub fn into_string(self) -> String {
+		self.output
+	}
+	
+	fn write_entry(&mut self, value: Option<&str>) -> Result<(), JecsSerdeError> {
+		match core::mem::replace(&mut self.slot, Slot::Root) {
+			Slot::Root => Err(serde_error("JECS data must have a map as root")),
+			Slot::Key(key) => Ok(writer::write_line(&mut self.output, &self.path, self.depth, Some(&key), value, &writer::WriterOptions::default())?),
+			Slot::ListEntry => Ok(writer::write_line(&mut self.output, &self.path, self.depth, None, value, &writer::WriterOptions::default())?),
+		}
+	}
+	
+	fn write_value(&mut self, value: &str) -> Result<(), JecsSerdeError> {
+		self.write_entry(Some(value))
+	}
+	
+	//Writes the parent line of a map or list (if not root) and returns the depth its children are written at.
+	fn begin_compound(&mut self, is_map: bool) -> Result<usize, JecsSerdeError> {
+		if let Slot::Root = self.slot {
+			if !is_map {
+				return Err(serde_error("JECS data must have a map as root"));
+			}
+			return Ok(0);
+		}
+		self.write_entry(None)?;
+		Ok(self.depth + 1)
+	}
+	
+	//Enum variants with content are written as map with the variant name as only key.
+	fn begin_variant(&mut self, variant: &str, is_map: bool) -> Result<Compound<'_>, JecsSerdeError> {
+		let depth = self.begin_compound(true)?;
+		self.depth = depth;
+		self.slot = Slot::Key(variant.to_string());
+		self.path.push_key(variant);
+		let child_depth = self.begin_compound(is_map)?;
+		Ok(Compound {
+			serializer: self,
+			child_depth,
+			index: 0,
+			pending_key: None,
+			pop_variant: true,
+		})
+	}
+	
+	fn begin(&mut self, is_map: bool) -> Result<Compound<'_>, JecsSerdeError> {
+		let child_depth = self.begin_compound(is_map)?;
+		Ok(Compound {
+			serializer: self,
+			child_depth,
+			index: 0,
+			pending_key: None,
+			pop_variant: false,
+		})
+	}
+}
+
+impl<'a> serde::Serializer for &'a mut Serializer {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	type SerializeSeq = Compound<'a>;
+	type SerializeTuple = Compound<'a>;
+	type SerializeTupleStruct = Compound<'a>;
+	type SerializeTupleVariant = Compound<'a>;
+	type SerializeMap = Compound<'a>;
+	type SerializeStruct = Compound<'a>;
+	type SerializeStructVariant = Compound<'a>;
+	
+	fn serialize_bool(self, value: bool) -> Result<(), JecsSerdeError> {
+		self.write_value(if value { "true" } else { "false" })
+	}
+	
+	fn serialize_i8(self, value: i8) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_i16(self, value: i16) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_i32(self, value: i32) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_i64(self, value: i64) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_i128(self, value: i128) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_u8(self, value: u8) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_u16(self, value: u16) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_u32(self, value: u32) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_u64(self, value: u64) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_u128(self, value: u128) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_f32(self, value: f32) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_f64(self, value: f64) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_char(self, value: char) -> Result<(), JecsSerdeError> {
+		self.write_value(&value.to_string())
+	}
+	
+	fn serialize_str(self, value: &str) -> Result<(), JecsSerdeError> {
+		self.write_value(value)
+	}
+	
+	fn serialize_bytes(self, value: &[u8]) -> Result<(), JecsSerdeError> {
+		let mut compound = self.begin(false)?;
+		for byte in value {
+			SerializeSeq::serialize_element(&mut compound, byte)?;
+		}
+		SerializeSeq::end(compound)
+	}
+	
+	fn serialize_none(self) -> Result<(), JecsSerdeError> {
+		self.write_entry(None)
+	}
+	
+	fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), JecsSerdeError> {
+		value.serialize(self)
+	}
+	
+	fn serialize_unit(self) -> Result<(), JecsSerdeError> {
+		self.write_entry(None)
+	}
+	
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<(), JecsSerdeError> {
+		self.write_entry(None)
+	}
+	
+	fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<(), JecsSerdeError> {
+		self.write_value(variant)
+	}
+	
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), JecsSerdeError> {
+		value.serialize(self)
+	}
+	
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<(), JecsSerdeError> {
+		let depth = self.begin_compound(true)?;
+		self.depth = depth;
+		self.slot = Slot::Key(variant.to_string());
+		self.path.push_key(variant);
+		value.serialize(&mut *self)?;
+		self.path.pop();
+		Ok(())
+	}
+	
+	fn serialize_seq(self, _length: Option<usize>) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin(false)
+	}
+	
+	fn serialize_tuple(self, _length: usize) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin(false)
+	}
+	
+	fn serialize_tuple_struct(self, _name: &'static str, _length: usize) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin(false)
+	}
+	
+	fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, _length: usize) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin_variant(variant, false)
+	}
+	
+	fn serialize_map(self, _length: Option<usize>) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin(true)
+	}
+	
+	fn serialize_struct(self, _name: &'static str, _length: usize) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin(true)
+	}
+	
+	fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, _length: usize) -> Result<Compound<'a>, JecsSerdeError> {
+		self.begin_variant(variant, true)
+	}
+}
+
+pub struct Compound<'a> {
+	serializer: &'a mut Serializer,
+	child_depth: usize,
+	index: usize,
+	pending_key: Option<String>,
+	pop_variant: bool,
+}
+
+impl Compound<'_> {
+	fn serialize_list_entry<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JecsSerdeError> {
+		self.serializer.depth = self.child_depth;
+		self.serializer.slot = Slot::ListEntry;
+		self.serializer.path.push_index(self.index);
+		value.serialize(&mut *self.serializer)?;
+		self.serializer.path.pop();
+		self.index += 1;
+		Ok(())
+	}
+	
+	fn serialize_map_entry<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), JecsSerdeError> {
+		self.serializer.depth = self.child_depth;
+		self.serializer.path.push_key(&key);
+		self.serializer.slot = Slot::Key(key);
+		value.serialize(&mut *self.serializer)?;
+		self.serializer.path.pop();
+		Ok(())
+	}
+	
+	fn finish(self) -> Result<(), JecsSerdeError> {
+		if self.pop_variant {
+			self.serializer.path.pop();
+		}
+		Ok(())
+	}
+}
+
+impl SerializeSeq for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JecsSerdeError> {
+		self.serialize_list_entry(value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+impl SerializeTuple for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JecsSerdeError> {
+		self.serialize_list_entry(value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+impl SerializeTupleStruct for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JecsSerdeError> {
+		self.serialize_list_entry(value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+impl SerializeTupleVariant for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JecsSerdeError> {
+		self.serialize_list_entry(value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+impl SerializeMap for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), JecsSerdeError> {
+		self.pending_key = Some(key.serialize(KeySerializer)?);
+		Ok(())
+	}
+	
+	fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JecsSerdeError> {
+		let key = self.pending_key.take().ok_or_else(|| serde_error("Map value serialized before its key"))?;
+		self.serialize_map_entry(key, value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+impl SerializeStruct for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), JecsSerdeError> {
+		self.serialize_map_entry(key.to_string(), value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+impl SerializeStructVariant for Compound<'_> {
+	type Ok = ();
+	type Error = JecsSerdeError;
+	
+	fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), JecsSerdeError> {
+		self.serialize_map_entry(key.to_string(), value)
+	}
+	
+	fn end(self) -> Result<(), JecsSerdeError> {
+		self.finish()
+	}
+}
+
+//Map keys have to be text in JECS, thus only accept types which can be converted to text.
+struct KeySerializer;
+
+macro_rules! serialize_key_to_string {
+	($($method:ident: $type:ty;)*) => {
+		$(
+			fn $method(self, value: $type) -> Result<String, JecsSerdeError> {
+				Ok(value.to_string())
+			}
+		)*
+	};
+}
+
+impl serde::Serializer for KeySerializer {
+	type Ok = String;
+	type Error = JecsSerdeError;
+	type SerializeSeq = Impossible<String, JecsSerdeError>;
+	type SerializeTuple = Impossible<String, JecsSerdeError>;
+	type SerializeTupleStruct = Impossible<String, JecsSerdeError>;
+	type SerializeTupleVariant = Impossible<String, JecsSerdeError>;
+	type SerializeMap = Impossible<String, JecsSerdeError>;
+	type SerializeStruct = Impossible<String, JecsSerdeError>;
+	type SerializeStructVariant = Impossible<String, JecsSerdeError>;
+	
+	serialize_key_to_string! {
+		serialize_bool: bool;
+		serialize_i8: i8;
+		serialize_i16: i16;
+		serialize_i32: i32;
+		serialize_i64: i64;
+		serialize_i128: i128;
+		serialize_u8: u8;
+		serialize_u16: u16;
+		serialize_u32: u32;
+		serialize_u64: u64;
+		serialize_u128: u128;
+		serialize_f32: f32;
+		serialize_f64: f64;
+		serialize_char: char;
+		serialize_str: &str;
+	}
+	
+	fn serialize_bytes(self, _value: &[u8]) -> Result<String, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_none(self) -> Result<String, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, JecsSerdeError> {
+		value.serialize(self)
+	}
+	
+	fn serialize_unit(self) -> Result<String, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<String, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<String, JecsSerdeError> {
+		Ok(variant.to_string())
+	}
+	
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<String, JecsSerdeError> {
+		value.serialize(self)
+	}
+	
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T) -> Result<String, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_seq(self, _length: Option<usize>) -> Result<Self::SerializeSeq, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_tuple(self, _length: usize) -> Result<Self::SerializeTuple, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_tuple_struct(self, _name: &'static str, _length: usize) -> Result<Self::SerializeTupleStruct, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _length: usize) -> Result<Self::SerializeTupleVariant, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_map(self, _length: Option<usize>) -> Result<Self::SerializeMap, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_struct(self, _name: &'static str, _length: usize) -> Result<Self::SerializeStruct, JecsSerdeError> {
+		Err(key_error())
+	}
+	
+	fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _length: usize) -> Result<Self::SerializeStructVariant, JecsSerdeError> {
+		Err(key_error())
+	}
+}
+
+fn key_error() -> JecsSerdeError {
+	serde_error("Map keys must be convertible to text")
+}
@@ -0,0 +1,57 @@
+use alloc::sync::Arc;
+use core::ops::Deref;
+
+use crate::errors::JecsCorruptedDataError;
+use crate::parser::parse_jecs_string;
+use crate::types::{JecsMap, JecsType};
+
+//An immutable tree behind an Arc. Cloning only counts the reference up, thus one parsed config can be handed to lots of threads.
+//All reading functions of JecsType are available through Deref.
+#[derive(Clone, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub struct SharedJecs(Arc<JecsType>);
+
+impl SharedJecs {
+	pub fn new(tree: JecsType) -> Self {
+		Self(Arc::new(tree))
+	}
+	
+	pub fn parse(text: &str) -> Result<Self, JecsCorruptedDataError> {
+		Ok(Self::new(JecsType::Map(parse_jecs_string(text)?)))
+	}
+	
+	//Copy-on-write: The tree is only copied, if other clones of it exist.
+	pub fn make_mut(&mut self) -> &mut JecsType {
+		Arc::make_mut(&mut self.0)
+	}
+	
+	//Only copies the tree, if other clones of it exist.
+	pub fn into_jecs_type(self) -> JecsType {
+		Arc::unwrap_or_clone(self.0)
+	}
+	
+	//True, if both are clones of the same tree.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Deref for SharedJecs {
+	type Target = JecsType;
+	
+	fn deref(&self) -> &JecsType {
+		&self.0
+	}
+}
+
+impl From<JecsType> for SharedJecs {
+	fn from(tree: JecsType) -> Self {
+		Self::new(tree)
+	}
+}
+
+impl From<JecsMap> for SharedJecs {
+	fn from(map: JecsMap) -> Self {
+		Self::new(JecsType::Map(map))
+	}
+}
@@ -0,0 +1,100 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::path::{JecsPath, JecsPathSegment};
+use crate::types::{HashMap, JecsMap, JecsType};
+
+//Where an entry was read from. Rows start at 1, columns are byte offsets within the row.
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub struct JecsSpan {
+	pub row: usize,
+	//Last row belonging to the entry, differs from 'row' for multi-line strings and entries with children.
+	pub last_row: usize,
+	//The key, or the '-' of list entries.
+	pub key_columns: Range<usize>,
+	//The value as written, thus still escaped. For multi-line strings only the opener. None for entries without value.
+	pub value_columns: Option<Range<usize>>,
+}
+
+pub type JecsSpannedMap = HashMap<String, JecsSpannedEntry>;
+
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub struct JecsSpannedEntry {
+	pub span: JecsSpan,
+	pub value: JecsSpannedType,
+}
+
+//Same as JecsType, but every child carries its span.
+#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug)]
+pub enum JecsSpannedType {
+	Any(),
+	Value(String),
+	Map(JecsSpannedMap),
+	List(Vec<JecsSpannedEntry>),
+}
+
+impl JecsSpannedEntry {
+	//The root has no location of its own, its span is empty and starts at row 0.
+	pub fn root(map: JecsSpannedMap) -> Self {
+		JecsSpannedEntry {
+			span: JecsSpan {
+				row: 0,
+				last_row: 0,
+				key_columns: 0..0,
+				value_columns: None,
+			},
+			value: JecsSpannedType::Map(map),
+		}
+	}
+	
+	pub fn get_child(&self, segment: &JecsPathSegment) -> Option<&JecsSpannedEntry> {
+		match (&self.value, segment) {
+			(JecsSpannedType::Map(map), JecsPathSegment::Key(key)) => map.get(key),
+			(JecsSpannedType::List(list), JecsPathSegment::Index(index)) => list.get(*index),
+			(JecsSpannedType::List(list), JecsPathSegment::Key(key)) => list.get(key.parse::<usize>().ok()?),
+			_ => None,
+		}
+	}
+	
+	pub fn get_path(&self, path: impl Into<JecsPath>) -> Option<&JecsSpannedEntry> {
+		let path = path.into();
+		let mut entry = self;
+		for segment in path.segments() {
+			entry = entry.get_child(segment)?;
+		}
+		Some(entry)
+	}
+	
+	//Moves the entry and all of its children, for when lines got inserted or removed above it.
+	pub fn shift_rows(&mut self, delta: isize) {
+		self.span.row = self.span.row.saturating_add_signed(delta);
+		self.span.last_row = self.span.last_row.saturating_add_signed(delta);
+		match &mut self.value {
+			JecsSpannedType::Map(map) => map.values_mut().for_each(|child| child.shift_rows(delta)),
+			JecsSpannedType::List(list) => list.iter_mut().for_each(|child| child.shift_rows(delta)),
+			_ => {}
+		}
+	}
+	
+	//Drops the spans, leaving the plain tree.
+	pub fn into_jecs_type(self) -> JecsType {
+		match self.value {
+			JecsSpannedType::Any() => JecsType::Any(),
+			JecsSpannedType::Value(value) => JecsType::value(value),
+			JecsSpannedType::Map(map) => JecsType::Map(into_jecs_map(map)),
+			JecsSpannedType::List(list) => JecsType::List(list.into_iter().map(JecsSpannedEntry::into_jecs_type).collect()),
+		}
+	}
+}
+
+pub fn into_jecs_map(map: JecsSpannedMap) -> JecsMap {
+	let mut output = JecsMap::with_capacity_and_hasher(map.len(), Default::default());
+	for (key, entry) in map {
+		output.insert(key, entry.into_jecs_type());
+	}
+	output
+}
@@ -0,0 +1,142 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+
+use crate::path::JecsPath;
+use crate::types::{JecsString, JecsType};
+
+#[derive(Clone, Eq, PartialEq, Default)]
+#[derive(Debug)]
+pub struct JecsStats {
+	pub any_count: usize,
+	pub null_count: usize,
+	pub value_count: usize,
+	pub map_count: usize,
+	pub list_count: usize,
+	pub max_depth: usize, //The node stats are collected from has depth 0, its children depth 1 and so on
+	pub total_value_bytes: usize,
+	pub largest_map: Option<(JecsPath, usize)>, //Path and entry count of the map with the most entries
+	pub largest_list: Option<(JecsPath, usize)>, //Path and entry count of the list with the most entries
+}
+
+impl JecsStats {
+	pub fn node_count(&self) -> usize {
+		self.any_count + self.null_count + self.value_count + self.map_count + self.list_count
+	}
+}
+
+impl JecsType {
+	pub fn stats(&self) -> JecsStats {
+		let mut stats = JecsStats::default();
+		collect(self, &mut JecsPath::root(), &mut stats);
+		return stats;
+		
+		fn collect(entry: &JecsType, path: &mut JecsPath, stats: &mut JecsStats) {
+			stats.max_depth = stats.max_depth.max(path.len());
+			match entry {
+				JecsType::Any() => {
+					stats.any_count += 1;
+				}
+				JecsType::Null() => {
+					stats.null_count += 1;
+				}
+				JecsType::Value(value) => {
+					stats.value_count += 1;
+					stats.total_value_bytes += value.len();
+				}
+				JecsType::Map(map) => {
+					stats.map_count += 1;
+					if stats.largest_map.as_ref().is_none_or(|(_, size)| map.len() > *size) {
+						stats.largest_map = Some((path.clone(), map.len()));
+					}
+					for (key, child) in map {
+						path.push_key(key);
+						collect(child, path, stats);
+						path.pop();
+					}
+				}
+				JecsType::List(list) => {
+					stats.list_count += 1;
+					if stats.largest_list.as_ref().is_none_or(|(_, size)| list.len() > *size) {
+						stats.largest_list = Some((path.clone(), list.len()));
+					}
+					for (index, child) in list.iter().enumerate() {
+						path.push_index(index);
+						collect(child, path, stats);
+						path.pop();
+					}
+				}
+			}
+		}
+	}
+}
+
+//Functions for long living trees, to measure and trim their memory:
+impl JecsType {
+	//Estimated bytes of this node and all heap memory it owns. Hash tables are counted with one control byte per bucket, the overhead of the allocator is not included.
+	pub fn memory_usage(&self) -> usize {
+		return size_of::<JecsType>() + heap_usage(self);
+		
+		fn heap_usage(entry: &JecsType) -> usize {
+			match entry {
+				JecsType::Any() | JecsType::Null() => 0,
+				JecsType::Value(value) => string_heap_usage(value),
+				JecsType::Map(map) => {
+					let buckets = map.capacity() * (size_of::<(String, JecsType)>() + 1);
+					buckets + map.iter().map(|(key, child)| key.capacity() + heap_usage(child)).sum::<usize>()
+				}
+				JecsType::List(list) => {
+					list.capacity() * size_of::<JecsType>() + list.iter().map(heap_usage).sum::<usize>()
+				}
+			}
+		}
+		
+		fn string_heap_usage(value: &JecsString) -> usize {
+			//Short compact strings are stored inline, their capacity is not on the heap:
+			#[cfg(feature = "compact_str")]
+			if !value.is_heap_allocated() {
+				return 0;
+			}
+			value.capacity()
+		}
+	}
+	
+	//Frees the unused capacity of all values, maps and lists in the tree. Keys cannot be changed while in a map, but the parser allocates them exactly anyway.
+	pub fn shrink_to_fit(&mut self) {
+		match self {
+			JecsType::Any() | JecsType::Null() => {}
+			JecsType::Value(value) => value.shrink_to_fit(),
+			JecsType::Map(map) => {
+				map.values_mut().for_each(JecsType::shrink_to_fit);
+				map.shrink_to_fit();
+			}
+			JecsType::List(list) => {
+				list.iter_mut().for_each(JecsType::shrink_to_fit);
+				list.shrink_to_fit();
+			}
+		}
+	}
+}
+
+impl Display for JecsStats {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "Nodes: {} (Any: {}, Null: {}, Value: {}, Map: {}, List: {})", self.node_count(), self.any_count, self.null_count, self.value_count, self.map_count, self.list_count)?;
+		writeln!(f, "Max depth: {}", self.max_depth)?;
+		writeln!(f, "Total value bytes: {}", self.total_value_bytes)?;
+		if let Some((path, size)) = &self.largest_map {
+			writeln!(f, "Largest map: {} with {} entries", describe(path), size)?;
+		}
+		if let Some((path, size)) = &self.largest_list {
+			writeln!(f, "Largest list: {} with {} entries", describe(path), size)?;
+		}
+		Ok(())
+	}
+}
+
+fn describe(path: &JecsPath) -> String {
+	if path.is_root() {
+		"the root".to_string()
+	} else {
+		format!("'{}'", path)
+	}
+}
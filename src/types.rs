@@ -1,22 +1,72 @@
-use std::collections::HashMap;
-use std::error::Error;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::{IntErrorKind, ParseIntError};
 
-use crate::errors::{JecsIncompatibleOrMalformedError, JecsWrongEntryTypeError};
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::HashMap;
+#[cfg(feature = "std")]
+pub(crate) use std::collections::HashMap;
 
-#[derive(Eq, PartialEq)]
+use crate::errors::{JecsDecodeError, JecsIncompatibleOrMalformedError, JecsMissingKeyError, JecsOutOfRangeError, JecsWrongEntryTypeError};
+use crate::path::{JecsIndex, JecsPath, JecsPathSegment};
+
+//The map type of JECS maps. Without the 'std' feature it is the one from hashbrown.
+pub type JecsMap = HashMap<String, JecsType, JecsHasher>;
+
+//The hasher of JECS maps. Config files are no hash flooding target, thus the 'fxhash' feature swaps the DoS resistant default for the much faster FxHash.
+#[cfg(feature = "fxhash")]
+pub type JecsHasher = rustc_hash::FxBuildHasher;
+#[cfg(all(not(feature = "fxhash"), feature = "std"))]
+pub type JecsHasher = std::collections::hash_map::RandomState;
+#[cfg(all(not(feature = "fxhash"), not(feature = "std")))]
+pub type JecsHasher = hashbrown::DefaultHashBuilder;
+
+//The string type of values. With the 'compact_str' feature short values are stored inline, without a heap allocation.
+#[cfg(feature = "compact_str")]
+pub type JecsString = compact_str::CompactString;
+#[cfg(not(feature = "compact_str"))]
+pub type JecsString = String;
+
+#[derive(Clone, Eq, PartialEq)]
 #[derive(Debug)]
 pub enum JecsType {
 	Any(), //Could be literally any of the below types, but always a length of zero
-	Value(String), //Contains a single text value
-	Map(HashMap<String, JecsType>), //Contains a dictionary
+	Null(), //Explicitly written as 'null', see ParserOptions::read_null. Unlike Any it is no empty map or list
+	Value(JecsString), //Contains a single text value
+	Map(JecsMap), //Contains a dictionary
 	List(Vec<JecsType>), //Contains a list
 }
 
+//Functions to construct JECS entries:
+impl JecsType {
+	pub fn new_map() -> Self {
+		JecsType::Map(JecsMap::default())
+	}
+	
+	pub fn new_list() -> Self {
+		JecsType::List(Vec::new())
+	}
+	
+	pub fn value(value: impl Into<JecsString>) -> Self {
+		JecsType::Value(value.into())
+	}
+}
+
+//A JECS file always has a map as root, hence an empty map is the default.
+impl Default for JecsType {
+	fn default() -> Self {
+		Self::new_map()
+	}
+}
+
 //Functions to check the JECS entry type and
 impl JecsType {
 	pub fn name(&self) -> &str {
 		match self {
 			JecsType::Any{..} => "Any",
+			JecsType::Null() => "Null",
 			JecsType::Value{..} => "Value",
 			JecsType::Map{..} => "Map",
 			JecsType::List{..} => "List",
@@ -30,6 +80,10 @@ impl JecsType {
 		}
 	}
 	
+	pub fn is_null(&self) -> bool {
+		matches!(self, JecsType::Null())
+	}
+	
 	pub fn is_value(&self) -> bool {
 		match self {
 			JecsType::Value{..} => true,
@@ -44,27 +98,21 @@ impl JecsType {
 		return None;
 	}
 	
+	//Same as is_map_or_any(), prefer the clearer name or is_strict_map().
 	pub fn is_map(&self) -> bool {
-		match self {
-			JecsType::Any{..} => true,
-			JecsType::Map{..} => true,
-			_ => false,
-		}
+		self.is_map_or_any()
 	}
 	
-	pub fn get_map(&self) -> Option<&HashMap<String, JecsType>> {
+	pub fn get_map(&self) -> Option<&JecsMap> {
 		if let JecsType::Map(value) = self {
 			return Some(value);
 		}
 		return None;
 	}
 	
+	//Same as is_list_or_any(), prefer the clearer name or is_strict_list().
 	pub fn is_list(&self) -> bool {
-		match self {
-			JecsType::Any{..} => true,
-			JecsType::List{..} => true,
-			_ => false,
-		}
+		self.is_list_or_any()
 	}
 	
 	pub fn get_list(&self) -> Option<&Vec<JecsType>> {
@@ -73,108 +121,483 @@ impl JecsType {
 		}
 		return None;
 	}
+	
+	//Any entries count as map and list, as they could be either of them. Validators which need children should use is_strict_map().
+	pub fn is_map_or_any(&self) -> bool {
+		matches!(self, JecsType::Any() | JecsType::Map(_))
+	}
+	
+	pub fn is_list_or_any(&self) -> bool {
+		matches!(self, JecsType::Any() | JecsType::List(_))
+	}
+	
+	//Only actual maps and lists, Any entries are rejected.
+	pub fn is_strict_map(&self) -> bool {
+		matches!(self, JecsType::Map(_))
+	}
+	
+	pub fn is_strict_list(&self) -> bool {
+		matches!(self, JecsType::List(_))
+	}
+	
+	//Decides what an Any entry is, as the file does not tell. Other entries stay as they are:
+	// entry.resolve_any(AnyAs::EmptyList)?.expect_list()
+	pub fn resolve_any(&self, any_as: AnyAs) -> Result<Cow<'_, JecsType>, JecsDecodeError> {
+		if !self.is_any() {
+			return Ok(Cow::Borrowed(self));
+		}
+		Ok(Cow::Owned(match any_as {
+			AnyAs::EmptyMap => JecsType::new_map(),
+			AnyAs::EmptyList => JecsType::new_list(),
+			AnyAs::Error => return Err(self.wrong_type("MAP, LIST or VALUE")),
+		}))
+	}
+}
+
+//How resolve_any() treats Any entries, which are written as key without value and children.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Debug)]
+pub enum AnyAs {
+	EmptyMap,
+	EmptyList,
+	Error,
 }
 
+//Functions to look into maps and lists without matching on the type first. Any entries and values behave like empty collections:
 impl JecsType {
-	pub fn expect_map(&self) -> Result<&HashMap<String, JecsType>, JecsWrongEntryTypeError> {
-		if !self.is_map() {
-			return Err(JecsWrongEntryTypeError {
-				expected_type: "MAP".to_string(),
-				encountered_type: self.name().to_string(),
-			});
+	//Lists have no keys, their entries are only listed by values().
+	pub fn keys(&self) -> impl Iterator<Item = &str> {
+		self.get_map().into_iter().flat_map(JecsMap::keys).map(String::as_str)
+	}
+	
+	//The entries of maps, in the order of keys(), or the entries of lists.
+	pub fn values(&self) -> impl Iterator<Item = &JecsType> {
+		self.get_map().into_iter().flat_map(JecsMap::values).chain(self.get_list().into_iter().flatten())
+	}
+	
+	//Amount of children, not the length of values.
+	pub fn len(&self) -> usize {
+		match self {
+			JecsType::Map(map) => map.len(),
+			JecsType::List(list) => list.len(),
+			JecsType::Any() | JecsType::Null() | JecsType::Value(_) => 0,
 		}
-		Ok(self.get_map().unwrap())
 	}
 	
-	pub fn expect_list(&self) -> Result<&Vec<JecsType>, JecsWrongEntryTypeError> {
-		if !self.is_list() {
-			return Err(JecsWrongEntryTypeError {
-				expected_type: "LIST".to_string(),
-				encountered_type: self.name().to_string(),
-			});
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl JecsType {
+	//Any entries are rejected like in is_strict_map(), expect_map_or_any() reads them as empty map.
+	pub fn expect_map(&self) -> Result<&JecsMap, JecsDecodeError> {
+		self.get_map().ok_or_else(|| self.wrong_type("MAP"))
+	}
+	
+	pub fn expect_list(&self) -> Result<&Vec<JecsType>, JecsDecodeError> {
+		self.get_list().ok_or_else(|| self.wrong_type("LIST"))
+	}
+	
+	//Same as expect_map(), but Any entries are read as empty map, like is_map_or_any() accepts them.
+	pub fn expect_map_or_any(&self) -> Result<Cow<'_, JecsMap>, JecsDecodeError> {
+		match self {
+			JecsType::Any() => Ok(Cow::Owned(JecsMap::default())),
+			_ => self.expect_map().map(Cow::Borrowed),
 		}
-		Ok(self.get_list().unwrap())
 	}
 	
-	pub fn expect_string(&self) -> Result<&str, JecsWrongEntryTypeError> {
-		if !self.is_value() {
-			return Err(JecsWrongEntryTypeError {
-				expected_type: "VALUE".to_string(),
-				encountered_type: self.name().to_string(),
-			});
+	pub fn expect_list_or_any(&self) -> Result<Cow<'_, Vec<JecsType>>, JecsDecodeError> {
+		match self {
+			JecsType::Any() => Ok(Cow::Owned(Vec::new())),
+			_ => self.expect_list().map(Cow::Borrowed),
+		}
+	}
+	
+	//Returns the child of a map. If it is missing, the error suggests a similar existing key.
+	pub fn expect_entry(&self, key: &str) -> Result<&JecsType, JecsDecodeError> {
+		match self {
+			JecsType::Any() | JecsType::Map(_) => Ok(self.get_required(key)?),
+			_ => Err(self.wrong_type("MAP")),
 		}
-		Ok(self.get_value().unwrap())
 	}
 	
-	pub fn expect_bool(&self) -> Result<bool, Box<dyn Error>> {
-		let value = self.expect_string().map_err(|mut e| { e.expected_type = "bool".to_string(); e })?;
+	//Like expect_entry(), but only fails if the key is absent. Entries which are no maps have no keys.
+	pub fn get_required(&self, key: &str) -> Result<&JecsType, JecsMissingKeyError> {
+		let map = self.get_map();
+		map.and_then(|map| map.get(key)).ok_or_else(|| missing_key(key, map))
+	}
+	
+	pub fn expect_string(&self) -> Result<&str, JecsDecodeError> {
+		self.expect_value_of("VALUE")
+	}
+	
+	pub fn expect_bool(&self) -> Result<bool, JecsDecodeError> {
+		let value = self.expect_value_of("bool")?;
 		Ok(match &value.to_lowercase()[..] {
 			"true" | "on" | "yes" | "y" => true,
 			"false" | "off" | "no" | "n" => false,
 			_ => {
-				Err(JecsIncompatibleOrMalformedError {
-					data_type: "boolean".to_string(),
-					value: value.to_string(),
-				})?
+				Err(malformed("boolean", value))?
 			}
 		})
 	}
 	
-	pub fn expect_double(&self) -> Result<f64, Box<dyn Error>> {
-		let value = self.expect_string().map_err(|mut e| { e.expected_type = "double".to_string(); e })?;
-		Ok(value.parse::<f64>().map_err(|_| JecsIncompatibleOrMalformedError {
-			data_type: "double".to_string(),
-			value: value.to_string(),
-		})?)
-	}
-	
-	pub fn expect_color(&self) -> Result<(u8, u8, u8), Box<dyn Error>> {
-		let value = self.expect_string().map_err(|mut e| { e.expected_type = "color".to_string(); e })?;
-		if value.len() != 6 {
-			//Not 6 characters long...
-			Err(JecsIncompatibleOrMalformedError {
-				data_type: "color".to_string(),
-				value: value.to_string(),
-			})?;
-		}
-		if value.chars().position(|c| {
-			!(c >= '0' && c <= '9' || c >= 'A' && c <= 'F')
-		}).is_some() {
-			//Wrong characters, allowed: [0-9A-F]
-			Err(JecsIncompatibleOrMalformedError {
-				data_type: "color".to_string(),
-				value: value.to_string(),
-			})?;
-		}
-		//Data validated, time to parse it:
-		Ok((
-			u8::from_str_radix(&value[0..2], 16).unwrap(),
-			u8::from_str_radix(&value[2..4], 16).unwrap(),
-			u8::from_str_radix(&value[4..6], 16).unwrap(),
-		))
-	}
-	
-	pub fn expect_unsigned(&self) -> Result<u32, Box<dyn Error>> {
-		let value = self.expect_string().map_err(|mut e| { e.expected_type = "unsigned".to_string(); e })?;
-		Ok(value.parse::<u32>().map_err(|_e| JecsIncompatibleOrMalformedError {
-			data_type: "unsigned".to_string(),
-			value: value.to_string(),
-		})?)
+	pub fn expect_double(&self) -> Result<f64, JecsDecodeError> {
+		let value = self.expect_value_of("double")?;
+		value.parse::<f64>().map_err(|_| malformed("double", value))
+	}
+	
+	pub fn expect_color(&self) -> Result<(u8, u8, u8), JecsDecodeError> {
+		parse_color(self.expect_value_of("color")?)
+	}
+	
+	pub fn expect_unsigned(&self) -> Result<u32, JecsDecodeError> {
+		let value = self.expect_value_of("unsigned")?;
+		value.parse::<u32>().map_err(|error| integer_error("unsigned", value, &error))
 	}
 	
-	pub fn expect_component_address(&self) -> Result<u32, Box<dyn Error>> {
-		let mut value = self.expect_string().map_err(|mut e| { e.expected_type = "component address".to_string(); e })?;
-		if !value.starts_with("C-") {
-			//Must start with 'C-'
-			Err(JecsIncompatibleOrMalformedError {
-				data_type: "component address".to_string(),
-				value: value.to_string(),
-			})?;
+	pub fn expect_component_address(&self) -> Result<u32, JecsDecodeError> {
+		parse_component_address(self.expect_value_of("component address")?)
+	}
+	
+	//Like expect_string(), but names the expected data type in the error.
+	fn expect_value_of(&self, data_type: &str) -> Result<&str, JecsDecodeError> {
+		match self {
+			JecsType::Value(value) => Ok(value),
+			_ => Err(self.wrong_type(data_type)),
 		}
-		value = &value[2..];
-		Ok(value.parse::<u32>().map_err(|_| JecsIncompatibleOrMalformedError {
-			data_type: "component address".to_string(),
+	}
+	
+	pub(crate) fn wrong_type(&self, expected_type: &str) -> JecsDecodeError {
+		JecsWrongEntryTypeError {
+			expected_type: expected_type.to_string(),
+			encountered_type: self.name().to_string(),
+			path: None,
+		}.into()
+	}
+}
+
+pub(crate) fn missing_key(key: &str, map: Option<&JecsMap>) -> JecsMissingKeyError {
+	let mut available_keys: Vec<String> = map.map_or_else(Vec::new, |map| map.keys().cloned().collect());
+	available_keys.sort();
+	JecsMissingKeyError {
+		key: key.to_string(),
+		available_keys,
+		suggestion: map.and_then(|map| closest_key(key, map.keys())),
+		path: None,
+	}
+}
+
+//Finds the key with the smallest edit distance, as long as it is close enough to be a typo.
+pub(crate) fn closest_key<'a>(key: &str, candidates: impl Iterator<Item = &'a String>) -> Option<String> {
+	let key: Vec<char> = key.to_lowercase().chars().collect();
+	//Short keys only tolerate differences in case, as with one wrong character most keys would be similar:
+	let max_distance = (key.len() + 1) / 3;
+	candidates
+		.map(|candidate| (edit_distance(&key, &candidate.to_lowercase().chars().collect::<Vec<char>>()), candidate))
+		.filter(|(distance, _)| *distance <= max_distance)
+		//Maps have no order, thus equal distances are decided alphabetically to get stable suggestions:
+		.min_by(|(distance_a, key_a), (distance_b, key_b)| distance_a.cmp(distance_b).then(key_a.cmp(key_b)))
+		.map(|(_, candidate)| candidate.clone())
+}
+
+//Levenshtein distance, the amount of inserted, removed or replaced characters.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+	let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+	let mut current_row = vec![0; b.len() + 1];
+	for (index_a, char_a) in a.iter().enumerate() {
+		current_row[0] = index_a + 1;
+		for (index_b, char_b) in b.iter().enumerate() {
+			let replace_cost = if char_a == char_b { 0 } else { 1 };
+			current_row[index_b + 1] = (previous_row[index_b] + replace_cost)
+				.min(previous_row[index_b + 1] + 1)
+				.min(current_row[index_b] + 1);
+		}
+		core::mem::swap(&mut previous_row, &mut current_row);
+	}
+	previous_row[b.len()]
+}
+
+//Colors are written as 6 upper case hex digits, 'RRGGBB'.
+pub(crate) fn parse_color(value: &str) -> Result<(u8, u8, u8), JecsDecodeError> {
+	if value.len() != 6 {
+		//Not 6 characters long...
+		Err(malformed("color", value))?;
+	}
+	if value.chars().position(|c| {
+		!(c.is_ascii_digit() || ('A'..='F').contains(&c))
+	}).is_some() {
+		//Wrong characters, allowed: [0-9A-F]
+		Err(malformed("color", value))?;
+	}
+	//Data validated, time to parse it:
+	Ok((
+		u8::from_str_radix(&value[0..2], 16).unwrap(),
+		u8::from_str_radix(&value[2..4], 16).unwrap(),
+		u8::from_str_radix(&value[4..6], 16).unwrap(),
+	))
+}
+
+pub(crate) fn parse_component_address(mut value: &str) -> Result<u32, JecsDecodeError> {
+	if !value.starts_with("C-") {
+		//Must start with 'C-'
+		Err(malformed("component address", value))?;
+	}
+	value = &value[2..];
+	value.parse::<u32>().map_err(|error| integer_error("component address", value, &error))
+}
+
+pub(crate) fn malformed(data_type: &str, value: &str) -> JecsDecodeError {
+	JecsIncompatibleOrMalformedError {
+		data_type: data_type.to_string(),
+		value: value.to_string(),
+		path: None,
+	}.into()
+}
+
+//Numbers which are too big or small are valid, but cannot be represented:
+pub(crate) fn integer_error(data_type: &str, value: &str, error: &ParseIntError) -> JecsDecodeError {
+	match error.kind() {
+		IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => JecsOutOfRangeError {
+			data_type: data_type.to_string(),
 			value: value.to_string(),
-		})?)
+			path: None,
+		}.into(),
+		_ => malformed(data_type, value),
+	}
+}
+
+//Functions to prune the tree, the predicate gets the path and the node of every map and list entry:
+impl JecsType {
+	//Removes every entry (and with that its children), for which the predicate returns false.
+	pub fn retain_keys(&mut self, mut predicate: impl FnMut(&JecsPath, &JecsType) -> bool) {
+		retain_inner(self, &mut JecsPath::root(), &mut predicate);
+		
+		fn retain_inner(entry: &mut JecsType, path: &mut JecsPath, predicate: &mut impl FnMut(&JecsPath, &JecsType) -> bool) {
+			match entry {
+				JecsType::Map(map) => {
+					map.retain(|key, child| {
+						path.push_key(key);
+						let keep = predicate(path, child);
+						if keep {
+							retain_inner(child, path, predicate);
+						}
+						path.pop();
+						keep
+					});
+				}
+				JecsType::List(list) => {
+					//Indices in the path refer to the original list, thus count them separately from the retained entries:
+					let mut index = 0;
+					list.retain_mut(|child| {
+						path.push_index(index);
+						let keep = predicate(path, child);
+						if keep {
+							retain_inner(child, path, predicate);
+						}
+						path.pop();
+						index += 1;
+						keep
+					});
+				}
+				_ => {}
+			}
+		}
+	}
+	
+	//Same as retain_keys(), but leaves this tree untouched and returns a filtered copy instead.
+	pub fn filter(&self, mut predicate: impl FnMut(&JecsPath, &JecsType) -> bool) -> JecsType {
+		return filter_inner(self, &mut JecsPath::root(), &mut predicate);
+		
+		fn filter_inner(entry: &JecsType, path: &mut JecsPath, predicate: &mut impl FnMut(&JecsPath, &JecsType) -> bool) -> JecsType {
+			match entry {
+				JecsType::Any() => JecsType::Any(),
+				JecsType::Null() => JecsType::Null(),
+				JecsType::Value(value) => JecsType::Value(value.clone()),
+				JecsType::Map(map) => {
+					let mut filtered = JecsMap::default();
+					for (key, child) in map {
+						path.push_key(key);
+						if predicate(path, child) {
+							filtered.insert(key.clone(), filter_inner(child, path, predicate));
+						}
+						path.pop();
+					}
+					JecsType::Map(filtered)
+				}
+				JecsType::List(list) => {
+					let mut filtered = Vec::new();
+					for (index, child) in list.iter().enumerate() {
+						path.push_index(index);
+						if predicate(path, child) {
+							filtered.push(filter_inner(child, path, predicate));
+						}
+						path.pop();
+					}
+					JecsType::List(filtered)
+				}
+			}
+		}
+	}
+}
+
+//Functions to transform the tree:
+impl JecsType {
+	//Replaces every value in this tree with the result of the function, which gets the path and the current value.
+	pub fn map_values(&mut self, mut function: impl FnMut(&JecsPath, &str) -> String) {
+		map_inner(self, &mut JecsPath::root(), &mut function);
+		
+		fn map_inner(entry: &mut JecsType, path: &mut JecsPath, function: &mut impl FnMut(&JecsPath, &str) -> String) {
+			match entry {
+				JecsType::Any() | JecsType::Null() => {}
+				JecsType::Value(value) => {
+					let value = function(path, value);
+					*entry = JecsType::value(value);
+				}
+				JecsType::Map(map) => {
+					for (key, child) in map.iter_mut() {
+						path.push_key(key);
+						map_inner(child, path, function);
+						path.pop();
+					}
+				}
+				JecsType::List(list) => {
+					for (index, child) in list.iter_mut().enumerate() {
+						path.push_index(index);
+						map_inner(child, path, function);
+						path.pop();
+					}
+				}
+			}
+		}
+	}
+	
+	//Same as map_values(), but leaves this tree untouched and returns a transformed copy instead.
+	pub fn mapped_values(&self, function: impl FnMut(&JecsPath, &str) -> String) -> JecsType {
+		let mut copy = self.clone();
+		copy.map_values(function);
+		copy
+	}
+}
+
+//Functions to navigate the tree by paths:
+impl JecsType {
+	//The child of a map by key or of a list by index: entry.get("Name"), entry.get(3)
+	//Keys of list entries are their index as text, like in paths.
+	pub fn get<'a>(&self, index: impl Into<JecsIndex<'a>>) -> Option<&JecsType> {
+		match (self, index.into()) {
+			(JecsType::Map(map), JecsIndex::Key(key)) => map.get(key),
+			(JecsType::List(list), JecsIndex::Index(index)) => list.get(index),
+			(JecsType::List(list), JecsIndex::Key(key)) => list.get(key.parse::<usize>().ok()?),
+			_ => None,
+		}
+	}
+	
+	pub fn get_child(&self, segment: &JecsPathSegment) -> Option<&JecsType> {
+		self.get(segment)
+	}
+	
+	pub fn get_child_mut(&mut self, segment: &JecsPathSegment) -> Option<&mut JecsType> {
+		match (self, segment) {
+			(JecsType::Map(map), JecsPathSegment::Key(key)) => map.get_mut(key),
+			(JecsType::List(list), JecsPathSegment::Index(index)) => list.get_mut(*index),
+			(JecsType::List(list), JecsPathSegment::Key(key)) => list.get_mut(key.parse::<usize>().ok()?),
+			_ => None,
+		}
+	}
+	
+	pub fn get_path(&self, path: impl Into<JecsPath>) -> Option<&JecsType> {
+		let path = path.into();
+		let mut entry = self;
+		for segment in path.segments() {
+			entry = entry.get_child(segment)?;
+		}
+		Some(entry)
+	}
+	
+	pub fn get_path_mut(&mut self, path: impl Into<JecsPath>) -> Option<&mut JecsType> {
+		let path = path.into();
+		let mut entry = self;
+		for segment in path.segments() {
+			entry = entry.get_child_mut(segment)?;
+		}
+		Some(entry)
+	}
+	
+	//Moves the entry at the path out of the tree, without copying it.
+	//Taking a list entry shifts all following entries of that list down by one.
+	pub fn take_path(&mut self, path: impl Into<JecsPath>) -> Option<JecsType> {
+		let mut path = path.into();
+		let last = path.pop()?; //The root itself cannot be taken out of itself
+		let parent = self.get_path_mut(&path)?;
+		match (parent, &last) {
+			(JecsType::Map(map), JecsPathSegment::Key(key)) => map.remove(key),
+			(JecsType::List(list), JecsPathSegment::Index(index)) => take_index(list, *index),
+			(JecsType::List(list), JecsPathSegment::Key(key)) => take_index(list, key.parse::<usize>().ok()?),
+			_ => None,
+		}
+	}
+	
+	//Applies an accessor like expect_color() to the entry at the path, errors get the path attached:
+	// tree.expect_at("MainInfo.Components[3].Color", JecsType::expect_color)
+	pub fn expect_at<'a, T>(&'a self, path: impl Into<JecsPath>, accessor: impl FnOnce(&'a JecsType) -> Result<T, JecsDecodeError>) -> Result<T, JecsDecodeError> {
+		let path = path.into();
+		let entry = match self.get_path(&path) {
+			None => {
+				let mut parent_path = path.clone();
+				let key = match parent_path.pop() {
+					Some(JecsPathSegment::Key(key)) => key,
+					Some(JecsPathSegment::Index(index)) => index.to_string(),
+					None => String::new(),
+				};
+				let parent_map = self.get_path(&parent_path).and_then(JecsType::get_map);
+				return Err(JecsDecodeError::from(missing_key(&key, parent_map)).at(path));
+			}
+			Some(entry) => entry,
+		};
+		accessor(entry).map_err(|error| error.at(path))
+	}
+	
+	//Shortcuts for expect_at() with the typed accessors:
+	// tree.expect_string_at("MainInfo.Author")
+	pub fn expect_map_at(&self, path: impl Into<JecsPath>) -> Result<&JecsMap, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_map)
+	}
+	
+	pub fn expect_list_at(&self, path: impl Into<JecsPath>) -> Result<&Vec<JecsType>, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_list)
+	}
+	
+	pub fn expect_string_at(&self, path: impl Into<JecsPath>) -> Result<&str, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_string)
+	}
+	
+	pub fn expect_bool_at(&self, path: impl Into<JecsPath>) -> Result<bool, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_bool)
+	}
+	
+	pub fn expect_double_at(&self, path: impl Into<JecsPath>) -> Result<f64, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_double)
+	}
+	
+	pub fn expect_color_at(&self, path: impl Into<JecsPath>) -> Result<(u8, u8, u8), JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_color)
+	}
+	
+	pub fn expect_unsigned_at(&self, path: impl Into<JecsPath>) -> Result<u32, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_unsigned)
+	}
+	
+	pub fn expect_component_address_at(&self, path: impl Into<JecsPath>) -> Result<u32, JecsDecodeError> {
+		self.expect_at(path, JecsType::expect_component_address)
+	}
+}
+
+fn take_index(list: &mut Vec<JecsType>, index: usize) -> Option<JecsType> {
+	if index < list.len() {
+		Some(list.remove(index))
+	} else {
+		None
 	}
 }
@@ -0,0 +1,54 @@
+//Bindings for JavaScript. To get a module, build the library as 'cdylib' for 'wasm32-unknown-unknown' and run 'wasm-bindgen' on it:
+// cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::parser;
+use crate::types::JecsType;
+use crate::writer;
+
+//Parses JECS text into plain JavaScript objects, arrays and strings. Any entries become null.
+#[wasm_bindgen]
+pub fn parse(text: &str) -> Result<JsValue, JsError> {
+	let tree = JecsType::Map(parser::parse_jecs_string(text)?);
+	Ok(tree.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+//Writes a JavaScript object as JECS text. Numbers and booleans are written as their text.
+#[wasm_bindgen]
+pub fn write(value: JsValue) -> Result<String, JsError> {
+	let tree: JecsType = serde_wasm_bindgen::from_value(value)?;
+	match tree.get_map() {
+		Some(map) => Ok(writer::write_jecs_string(map)?),
+		None => Err(JsError::new("JECS data must have a map as root")),
+	}
+}
+
+//Returns all problems of the JECS text as array of '{ row, code, message }' objects, which is empty for valid text.
+#[wasm_bindgen]
+pub fn validate(text: &str) -> Result<JsValue, JsError> {
+	let errors = parser::validate_jecs_string(text).err().unwrap_or_default();
+	let diagnostics: Vec<_> = errors.into_iter().map(|error| Diagnostic {
+		row: error.row,
+		code: error.error_code().code(),
+		message: error.description,
+	}).collect();
+	Ok(diagnostics.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+struct Diagnostic {
+	row: usize,
+	code: &'static str,
+	message: String,
+}
+
+impl Serialize for Diagnostic {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut state = serializer.serialize_struct("Diagnostic", 3)?;
+		state.serialize_field("row", &self.row)?;
+		state.serialize_field("code", self.code)?;
+		state.serialize_field("message", &self.message)?;
+		state.end()
+	}
+}
@@ -0,0 +1,273 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter};
+
+use crate::errors::JecsWriteError;
+use crate::path::JecsPath;
+use crate::redact::JecsRedaction;
+use crate::types::{JecsMap, JecsType};
+
+pub(crate) const INDENTATION_STEP: usize = 4;
+
+//Differences to the default JECS writing, the counterpart of parser::ParserOptions.
+#[derive(Clone, Default)]
+#[derive(Debug)]
+pub struct WriterOptions {
+	//Surrounds values with '"', if they would otherwise be rejected or read differently: Empty values, values with leading/trailing spaces,
+	// values surrounded by '"' and 'null'. Such output has to be parsed with ParserOptions::unquote_values.
+	pub quote_values: bool,
+	pub key_order: KeyOrder,
+	//Writes Null entries as 'null', without it they cannot be written, as they would be read back as Any entry.
+	//Such output has to be parsed with ParserOptions::read_null. Values 'null' then need quote_values.
+	pub write_null: bool,
+}
+
+impl WriterOptions {
+	//Writes values the way the C# SUCC library does, to be read with ParserOptions::succ().
+	pub fn succ() -> Self {
+		Self {
+			quote_values: true,
+			..Self::default()
+		}
+	}
+}
+
+//Order in which the entries of maps are written. List entries always keep their order.
+#[derive(Clone, Default)]
+pub enum KeyOrder {
+	//Whatever order the map has, which changes between runs.
+	#[default]
+	Unordered,
+	Alphabetical,
+	//The listed keys first in that order, then all others alphabetically. Applies to the maps on every level.
+	Listed(Vec<String>),
+	//Gets the path of the map and two of its keys.
+	Custom(Arc<KeyComparator>),
+}
+
+pub type KeyComparator = dyn Fn(&JecsPath, &str, &str) -> Ordering + Send + Sync;
+
+impl KeyOrder {
+	pub(crate) fn sorted<'a>(&self, path: &JecsPath, map: &'a JecsMap) -> Vec<(&'a String, &'a JecsType)> {
+		let mut entries: Vec<_> = map.iter().collect();
+		match self {
+			KeyOrder::Unordered => {}
+			KeyOrder::Alphabetical => entries.sort_by_key(|(key, _)| *key),
+			KeyOrder::Listed(keys) => {
+				let position = |key: &str| keys.iter().position(|listed| listed == key).unwrap_or(keys.len());
+				entries.sort_by_key(|(key, _)| (position(key), *key));
+			}
+			KeyOrder::Custom(compare) => entries.sort_by(|(a, _), (b, _)| compare(path, a, b)),
+		}
+		entries
+	}
+}
+
+impl Debug for KeyOrder {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			KeyOrder::Unordered => write!(f, "Unordered"),
+			KeyOrder::Alphabetical => write!(f, "Alphabetical"),
+			KeyOrder::Listed(keys) => f.debug_tuple("Listed").field(keys).finish(),
+			KeyOrder::Custom(_) => write!(f, "Custom"),
+		}
+	}
+}
+
+pub fn write_jecs_string(root: &JecsMap) -> Result<String, JecsWriteError> {
+	write_jecs_string_with_options(root, &WriterOptions::default())
+}
+
+pub fn write_jecs_string_with_options(root: &JecsMap, options: &WriterOptions) -> Result<String, JecsWriteError> {
+	let mut output = String::new();
+	let mut path = JecsPath::root();
+	for (key, entry) in options.key_order.sorted(&path, root) {
+		path.push_key(key);
+		write_entry(&mut output, &mut path, 0, Some(key), entry, options)?;
+		path.pop();
+	}
+	Ok(output)
+}
+
+//Same as write_jecs_string(), but replaces sensitive values, so that the output can be shared.
+pub fn write_jecs_string_redacted(root: &JecsMap, redaction: &JecsRedaction) -> Result<String, JecsWriteError> {
+	let mut root = JecsType::Map(root.clone());
+	redaction.redact(&mut root);
+	write_jecs_string(root.get_map().unwrap())
+}
+
+pub(crate) fn write_entry(output: &mut String, path: &mut JecsPath, depth: usize, key: Option<&str>, entry: &JecsType, options: &WriterOptions) -> Result<(), JecsWriteError> {
+	match entry {
+		JecsType::Any() => {
+			write_line(output, path, depth, key, None, options)?;
+		}
+		JecsType::Null() => {
+			if !options.write_null {
+				return Err(write_error(path, "Null entries can only be written with write_null"));
+			}
+			write_line(output, path, depth, key, None, options)?;
+			//Added afterwards, as write_line() would quote it like a value:
+			output.insert_str(output.len() - 1, " null");
+		}
+		JecsType::Value(value) => {
+			write_line(output, path, depth, key, Some(value), options)?;
+		}
+		JecsType::Map(map) => {
+			write_line(output, path, depth, key, None, options)?;
+			for (child_key, child) in options.key_order.sorted(path, map) {
+				path.push_key(child_key);
+				write_entry(output, path, depth + 1, Some(child_key), child, options)?;
+				path.pop();
+			}
+		}
+		JecsType::List(list) => {
+			write_line(output, path, depth, key, None, options)?;
+			for (index, child) in list.iter().enumerate() {
+				path.push_index(index);
+				write_entry(output, path, depth + 1, None, child, options)?;
+				path.pop();
+			}
+		}
+	}
+	Ok(())
+}
+
+//Writes a single entry line. A missing key turns the line into a list entry, a missing value into a parent (or empty) entry.
+pub(crate) fn write_line(output: &mut String, path: &JecsPath, depth: usize, key: Option<&str>, value: Option<&str>, options: &WriterOptions) -> Result<(), JecsWriteError> {
+	push_indentation(output, depth);
+	match key {
+		Some(key) => {
+			validate_key(key).map_err(|description| write_error(path, description))?;
+			output.push_str(key);
+			output.push(':');
+		}
+		None => output.push('-'),
+	}
+	if let Some(value) = value.filter(|value| value.contains('\n')) {
+		return write_multi_line_value(output, path, depth, value);
+	}
+	if let Some(value) = value {
+		let quoted;
+		let value = if options.quote_values && needs_quotes(value) {
+			quoted = format!("\"{}\"", value);
+			&quoted
+		} else {
+			value
+		};
+		if options.write_null && value == "null" {
+			return Err(write_error(path, "Value 'null' would be read as Null entry, it needs quote_values"));
+		}
+		if value.is_empty() {
+			//A key without value is read as Any entry, an empty multi-line string stays a value:
+			output.push_str(" \"\"\"\n");
+			push_indentation(output, depth + 1);
+			output.push_str("\"\"\"\n");
+			return Ok(());
+		}
+		validate_value(value).map_err(|description| write_error(path, description))?;
+		output.push(' ');
+		push_escaped_value(output, value);
+	}
+	output.push('\n');
+	Ok(())
+}
+
+//Values with line breaks are written as multi-line string, one level deeper than the entry:
+//key: """
+//    first line
+//    second line
+//    """
+fn write_multi_line_value(output: &mut String, path: &JecsPath, depth: usize, value: &str) -> Result<(), JecsWriteError> {
+	validate_multi_line_value(value).map_err(|description| write_error(path, description))?;
+	output.push_str(" \"\"\"\n");
+	for line in value.split('\n') {
+		//Empty lines are kept by the parser, indenting them would only add trailing whitespace:
+		if !line.is_empty() {
+			push_indentation(output, depth + 1);
+			push_escaped_value(output, line);
+		}
+		output.push('\n');
+	}
+	push_indentation(output, depth + 1);
+	output.push_str("\"\"\"\n");
+	Ok(())
+}
+
+fn push_indentation(output: &mut String, depth: usize) {
+	for _ in 0..(depth * INDENTATION_STEP) {
+		output.push(' ');
+	}
+}
+
+pub(crate) fn write_error(path: &JecsPath, description: &str) -> JecsWriteError {
+	JecsWriteError {
+		path: path.clone(),
+		description: description.to_string(),
+	}
+}
+
+//Keys have to survive the parser unchanged, thus reject anything it would interpret differently.
+pub(crate) fn validate_key(key: &str) -> Result<(), &'static str> {
+	if key.is_empty() {
+		return Err("Keys may not be empty");
+	}
+	if key.starts_with(' ') || key.ends_with(' ') {
+		return Err("Keys may not start or end with spaces");
+	}
+	if key.starts_with('-') {
+		return Err("Keys may not start with '-', as that marks list entries");
+	}
+	if key.contains([':', '#', '\n', '\r']) {
+		return Err("Keys may not contain ':', '#' or line breaks");
+	}
+	Ok(())
+}
+
+pub(crate) fn validate_value(value: &str) -> Result<(), &'static str> {
+	if value.contains(['\n', '\r']) {
+		return Err("Values with line breaks are not supported");
+	}
+	if value.starts_with(' ') || value.ends_with(' ') {
+		return Err("Leading or trailing spaces of values would get lost");
+	}
+	if value == "\"\"\"" {
+		return Err("Value would be interpreted as the start of a multi-line string");
+	}
+	Ok(())
+}
+
+//The parser removes the indentation and trailing spaces of every line of a multi-line string.
+fn validate_multi_line_value(value: &str) -> Result<(), &'static str> {
+	if value.contains('\r') {
+		return Err("Values with carriage returns are not supported");
+	}
+	for line in value.split('\n') {
+		if line.starts_with(' ') || line.ends_with(' ') {
+			return Err("Leading or trailing spaces of lines in multi-line values would get lost");
+		}
+		if line == "\"\"\"" {
+			return Err("Value has a line which would terminate the multi-line string");
+		}
+	}
+	Ok(())
+}
+
+fn needs_quotes(value: &str) -> bool {
+	value.is_empty()
+		|| value.starts_with(' ') || value.ends_with(' ')
+		|| (value.len() >= 2 && value.starts_with('"') && value.ends_with('"'))
+		|| value == "null"
+}
+
+//The parser treats '#' as the start of a comment, unless it is escaped.
+pub(crate) fn push_escaped_value(output: &mut String, value: &str) {
+	for c in value.chars() {
+		if c == '#' {
+			output.push('\\');
+		}
+		output.push(c);
+	}
+}
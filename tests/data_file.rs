@@ -0,0 +1,19 @@
+use ecc_jecs_lib::data_file::MemoryDataFile;
+
+#[test]
+fn set_empty_value_survives_writing() {
+	let mut data = MemoryDataFile::parse("A: old\nB:\n").unwrap();
+	data.set("A", "").unwrap();
+	data.set("B", "").unwrap();
+	let text = data.to_jecs_string().unwrap();
+	let data = MemoryDataFile::parse(&text).unwrap();
+	assert_eq!(data.get::<String>("A").unwrap(), "");
+	assert_eq!(data.get::<String>("B").unwrap(), "");
+}
+
+#[test]
+fn set_value_with_outer_spaces_is_rejected() {
+	let mut data = MemoryDataFile::parse("A: old\n").unwrap();
+	data.set("A", " padded").unwrap();
+	assert!(data.to_jecs_string().is_err());
+}
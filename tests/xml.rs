@@ -0,0 +1,28 @@
+#![cfg(feature = "xml")]
+
+use ecc_jecs_lib::convert::{from_xml_str, to_xml_string};
+use ecc_jecs_lib::parser::parse_jecs_string;
+use ecc_jecs_lib::types::{JecsMap, JecsType};
+
+fn parse(text: &str) -> JecsType {
+	JecsType::Map(parse_jecs_string(text).unwrap())
+}
+
+#[test]
+fn round_trip() {
+	let tree = parse("Name: Wire\nInputs:\n    - 0\n    - 1\nSettings:\n    item: 5\n    Other: 6\n");
+	assert_eq!(from_xml_str(&to_xml_string(&tree).unwrap()).unwrap(), tree);
+}
+
+#[test]
+fn map_with_only_item_key_is_rejected() {
+	let tree = parse("Settings:\n    item: 5\n");
+	assert!(to_xml_string(&tree).is_err());
+}
+
+#[test]
+fn empty_value_is_rejected() {
+	let mut map = JecsMap::default();
+	map.insert("Name".into(), JecsType::value(""));
+	assert!(to_xml_string(&JecsType::Map(map)).is_err());
+}